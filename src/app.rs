@@ -1,23 +1,67 @@
-use chrono::{Datelike, Local, TimeZone};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{Datelike, Local, TimeZone, Timelike};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use log::{error, warn};
-use ratatui::{DefaultTerminal, Frame, widgets::ListState};
+use ratatui::{
+    DefaultTerminal, Frame,
+    style::{Modifier, Style},
+    widgets::ListState,
+};
 
 use crate::clock::{Clock, SystemClock};
-use crate::config::TICK_RATE;
+use crate::config::{self, TICK_RATE};
 use crate::db::{Database, DatabaseOps};
 use crate::event::{AppEvent, poll_event};
+use crate::hooks::{HookContext, HookRunner};
+use crate::keymap::{Action, Context, Key, Keymap};
 use crate::models::{
-    BoundedString, Category, CategoryId, CategoryStat, Config, DurationSecs, Session, Timestamp,
+    BoundedString, BreakId, BreakKind, BreakPeriod, BreakStat, Category, CategoryId, CategoryStat,
+    Config, CycleStep, CycleStepId, DailyInterruptions, DeliveryId, DurationOverrides,
+    DurationSecs, EstimateStat, Hook, HookEvent, HookId, Interruption, InterruptionKind, PauseStat,
+    Profile, ProfileStat, Session, SessionId, SessionOutcome, SessionSegment, Task, TaskId,
+    TimerMode, Timestamp, Webhook, WebhookDelivery, WebhookId,
 };
-use crate::timer::PomodoroTimer;
+use crate::mouse::{ClickTarget, HitMap};
+use crate::palette::{Command, matching_commands};
+use crate::terminal;
+use crate::theme::{self, Theme, Themes};
+use crate::timer::{PomodoroTimer, TimerPhase};
 use crate::ui::{
-    render_detail_modal, render_history, render_input_modal, render_settings_modal, render_stats,
-    render_timer,
+    render_compact, render_detail_modal, render_help_overlay, render_history, render_input_modal,
+    render_interruption_modal, render_palette_modal, render_settings_modal, render_stats,
+    render_task_input_modal, render_tasks, render_timer,
 };
 use crate::validation::{
-    validate_new_category_name, validate_session_name, validate_update_category_name,
+    parse_override_minutes, validate_cycle_step, validate_hook, validate_new_category_name,
+    validate_new_profile_name, validate_session_name, validate_update_category_name,
+    validate_webhook,
 };
+use crate::webhooks::WebhookSender;
+
+/// Terminals shorter than this many rows get the compact layout
+const COMPACT_BELOW_HEIGHT: u16 = 15;
+
+/// Shown for what the compact layout has no room for
+const COMPACT_TIMER_ONLY: &str = "Only the timer is shown in the compact layout";
+
+/// How often a running session is checkpointed for crash recovery
+const CHECKPOINT_INTERVAL_SECS: i64 = 30;
+
+/// How often the bell rings again while a finished phase awaits acknowledgement
+const ACK_ALERT_INTERVAL_SECS: i64 = 10;
+
+/// How often the webhook queue is checked for deliveries due a retry
+const WEBHOOK_RETRY_INTERVAL_SECS: i64 = 5;
+
+/// How long the screen stays in reverse video after a flashing alert
+const ALERT_FLASH_SECS: i64 = 1;
+
+/// Two clicks on the same target within this long make a double-click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
 
 /// The current view/screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -26,6 +70,7 @@ pub enum View {
     Timer,
     History,
     Stats,
+    Tasks,
 }
 
 /// The time period for statistics
@@ -68,6 +113,26 @@ impl StatsPeriod {
         (start.timestamp(), end.timestamp())
     }
 
+    /// Parse a period from its lowercase name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "day" => Some(StatsPeriod::Day),
+            "week" => Some(StatsPeriod::Week),
+            "month" => Some(StatsPeriod::Month),
+            "year" => Some(StatsPeriod::Year),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatsPeriod::Day => "today",
+            StatsPeriod::Week => "this week",
+            StatsPeriod::Month => "this month",
+            StatsPeriod::Year => "this year",
+        }
+    }
+
     pub fn next(&self) -> Self {
         match self {
             StatsPeriod::Day => StatsPeriod::Week,
@@ -93,13 +158,19 @@ pub enum ChartType {
     #[default]
     Bar,
     Pie,
+    Interruptions,
+    Estimates,
+    Profiles,
 }
 
 impl ChartType {
-    pub fn toggle(self) -> Self {
+    pub fn next(self) -> Self {
         match self {
             ChartType::Bar => ChartType::Pie,
-            ChartType::Pie => ChartType::Bar,
+            ChartType::Pie => ChartType::Interruptions,
+            ChartType::Interruptions => ChartType::Estimates,
+            ChartType::Estimates => ChartType::Profiles,
+            ChartType::Profiles => ChartType::Bar,
         }
     }
 }
@@ -111,6 +182,7 @@ pub enum InputField {
     Name,
     Description,
     Category,
+    Task,
 }
 
 impl InputField {
@@ -118,7 +190,84 @@ impl InputField {
         match self {
             InputField::Name => InputField::Description,
             InputField::Description => InputField::Category,
-            InputField::Category => InputField::Name,
+            InputField::Category => InputField::Task,
+            InputField::Task => InputField::Name,
+        }
+    }
+}
+
+/// Which field is focused in the task input modal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskField {
+    #[default]
+    Title,
+    Estimate,
+    Category,
+}
+
+impl TaskField {
+    pub fn next(&self) -> Self {
+        match self {
+            TaskField::Title => TaskField::Estimate,
+            TaskField::Estimate => TaskField::Category,
+            TaskField::Category => TaskField::Title,
+        }
+    }
+}
+
+/// Which field is focused in the cycle step form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepField {
+    #[default]
+    Label,
+    Kind,
+    Minutes,
+}
+
+impl StepField {
+    pub fn next(&self) -> Self {
+        match self {
+            StepField::Label => StepField::Kind,
+            StepField::Kind => StepField::Minutes,
+            StepField::Minutes => StepField::Label,
+        }
+    }
+}
+
+/// Which field is focused in the hook form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HookField {
+    #[default]
+    Event,
+    Command,
+    Timeout,
+}
+
+impl HookField {
+    pub fn next(&self) -> Self {
+        match self {
+            HookField::Event => HookField::Command,
+            HookField::Command => HookField::Timeout,
+            HookField::Timeout => HookField::Event,
+        }
+    }
+}
+
+/// Which field is focused in the webhook form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WebhookField {
+    #[default]
+    Url,
+    Headers,
+    Events,
+}
+
+impl WebhookField {
+    pub fn next(&self) -> Self {
+        match self {
+            WebhookField::Url => WebhookField::Headers,
+            WebhookField::Headers => WebhookField::Events,
+            WebhookField::Events => WebhookField::Url,
         }
     }
 }
@@ -131,6 +280,26 @@ pub enum SettingsField {
     ShortBreak,
     LongBreak,
     SessionsUntilLong,
+    ExtendBy,
+    AutoStartBreaks,
+    AutoStartWork,
+    Overtime,
+    FocusPause,
+    TimerMode,
+    FlowtimeRatio,
+    FlowtimeMinBreak,
+    FlowtimeMaxBreak,
+    FirstWarning,
+    SecondWarning,
+    AlertBells(TimerPhase),
+    AlertFlash(TimerPhase),
+    AlertGauge(TimerPhase),
+    QuietHours,
+    QuietStart,
+    QuietEnd,
+    DesktopNotification,
+    WindowTitle,
+    Theme,
 }
 
 /// Which mode/tab is active in the settings modal
@@ -138,9 +307,38 @@ pub enum SettingsField {
 pub enum SettingsMode {
     #[default]
     Timer,
+    Alerts,
+    Profiles,
+    Cycle,
+    Hooks,
+    Webhooks,
     Categories,
 }
 
+impl SettingsMode {
+    pub fn next(&self) -> Self {
+        match self {
+            SettingsMode::Timer => SettingsMode::Alerts,
+            SettingsMode::Alerts => SettingsMode::Profiles,
+            SettingsMode::Profiles => SettingsMode::Cycle,
+            SettingsMode::Cycle => SettingsMode::Hooks,
+            SettingsMode::Hooks => SettingsMode::Webhooks,
+            SettingsMode::Webhooks | SettingsMode::Categories => SettingsMode::Categories,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        match self {
+            SettingsMode::Timer | SettingsMode::Alerts => SettingsMode::Timer,
+            SettingsMode::Profiles => SettingsMode::Alerts,
+            SettingsMode::Cycle => SettingsMode::Profiles,
+            SettingsMode::Hooks => SettingsMode::Cycle,
+            SettingsMode::Webhooks => SettingsMode::Hooks,
+            SettingsMode::Categories => SettingsMode::Webhooks,
+        }
+    }
+}
+
 /// Which field is focused in category editing mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum CategoryField {
@@ -148,6 +346,23 @@ pub enum CategoryField {
     List,
     Name,
     Color,
+    WorkDuration,
+    ShortBreak,
+    LongBreak,
+}
+
+impl CategoryField {
+    /// Next field of the category form, wrapping back to the name
+    pub fn next(&self) -> Self {
+        match self {
+            CategoryField::Name => CategoryField::Color,
+            CategoryField::Color => CategoryField::WorkDuration,
+            CategoryField::WorkDuration => CategoryField::ShortBreak,
+            CategoryField::ShortBreak => CategoryField::LongBreak,
+            CategoryField::LongBreak => CategoryField::Name,
+            CategoryField::List => CategoryField::List,
+        }
+    }
 }
 
 /// The current modal state - only one modal can be open at a time
@@ -158,6 +373,9 @@ pub enum ModalState {
     Input,
     Settings,
     Detail,
+    Interruption,
+    TaskInput,
+    Palette,
 }
 
 /// The current session lifecycle state
@@ -172,12 +390,22 @@ pub enum SessionPhase {
     Active {
         session: Session,
         start_time: Timestamp,
+        /// Start of the current running segment, `None` while paused
+        segment_start: Option<Timestamp>,
     },
 }
 
+/// What was saved for the phase that ended last, undone if that phase is snoozed
+#[derive(Debug, Clone, Copy)]
+enum EndedRecord {
+    Session(SessionId),
+    Break { id: BreakId, started_at: Timestamp },
+}
+
 /// Notification severity level
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NotificationLevel {
+    Info,
     Warning,
     Error,
 }
@@ -191,22 +419,107 @@ pub struct Notification {
 
 impl SettingsField {
     pub fn next(&self) -> Self {
-        match self {
+        match *self {
             SettingsField::WorkDuration => SettingsField::ShortBreak,
             SettingsField::ShortBreak => SettingsField::LongBreak,
             SettingsField::LongBreak => SettingsField::SessionsUntilLong,
-            SettingsField::SessionsUntilLong => SettingsField::WorkDuration,
+            SettingsField::SessionsUntilLong => SettingsField::ExtendBy,
+            SettingsField::ExtendBy => SettingsField::AutoStartBreaks,
+            SettingsField::AutoStartBreaks => SettingsField::AutoStartWork,
+            SettingsField::AutoStartWork => SettingsField::Overtime,
+            SettingsField::Overtime => SettingsField::FocusPause,
+            SettingsField::FocusPause => SettingsField::TimerMode,
+            SettingsField::TimerMode => SettingsField::FlowtimeRatio,
+            SettingsField::FlowtimeRatio => SettingsField::FlowtimeMinBreak,
+            SettingsField::FlowtimeMinBreak => SettingsField::FlowtimeMaxBreak,
+            SettingsField::FlowtimeMaxBreak => SettingsField::WorkDuration,
+            SettingsField::FirstWarning => SettingsField::SecondWarning,
+            SettingsField::SecondWarning => SettingsField::AlertBells(TimerPhase::Work),
+            SettingsField::AlertBells(phase) => SettingsField::AlertFlash(phase),
+            SettingsField::AlertFlash(phase) => SettingsField::AlertGauge(phase),
+            SettingsField::AlertGauge(TimerPhase::LongBreak) => SettingsField::QuietHours,
+            SettingsField::AlertGauge(phase) => SettingsField::AlertBells(phase.next()),
+            SettingsField::QuietHours => SettingsField::QuietStart,
+            SettingsField::QuietStart => SettingsField::QuietEnd,
+            SettingsField::QuietEnd => SettingsField::DesktopNotification,
+            SettingsField::DesktopNotification => SettingsField::WindowTitle,
+            SettingsField::WindowTitle => SettingsField::Theme,
+            SettingsField::Theme => SettingsField::FirstWarning,
         }
     }
 
     pub fn prev(&self) -> Self {
-        match self {
-            SettingsField::WorkDuration => SettingsField::SessionsUntilLong,
+        match *self {
+            SettingsField::WorkDuration => SettingsField::FlowtimeMaxBreak,
             SettingsField::ShortBreak => SettingsField::WorkDuration,
             SettingsField::LongBreak => SettingsField::ShortBreak,
             SettingsField::SessionsUntilLong => SettingsField::LongBreak,
+            SettingsField::ExtendBy => SettingsField::SessionsUntilLong,
+            SettingsField::AutoStartBreaks => SettingsField::ExtendBy,
+            SettingsField::AutoStartWork => SettingsField::AutoStartBreaks,
+            SettingsField::Overtime => SettingsField::AutoStartWork,
+            SettingsField::FocusPause => SettingsField::Overtime,
+            SettingsField::TimerMode => SettingsField::FocusPause,
+            SettingsField::FlowtimeRatio => SettingsField::TimerMode,
+            SettingsField::FlowtimeMinBreak => SettingsField::FlowtimeRatio,
+            SettingsField::FlowtimeMaxBreak => SettingsField::FlowtimeMinBreak,
+            SettingsField::FirstWarning => SettingsField::Theme,
+            SettingsField::SecondWarning => SettingsField::FirstWarning,
+            SettingsField::AlertBells(TimerPhase::Work) => SettingsField::SecondWarning,
+            SettingsField::AlertBells(TimerPhase::ShortBreak) => {
+                SettingsField::AlertGauge(TimerPhase::Work)
+            }
+            SettingsField::AlertBells(TimerPhase::LongBreak) => {
+                SettingsField::AlertGauge(TimerPhase::ShortBreak)
+            }
+            SettingsField::AlertFlash(phase) => SettingsField::AlertBells(phase),
+            SettingsField::AlertGauge(phase) => SettingsField::AlertFlash(phase),
+            SettingsField::QuietHours => SettingsField::AlertGauge(TimerPhase::LongBreak),
+            SettingsField::QuietStart => SettingsField::QuietHours,
+            SettingsField::QuietEnd => SettingsField::QuietStart,
+            SettingsField::DesktopNotification => SettingsField::QuietEnd,
+            SettingsField::WindowTitle => SettingsField::DesktopNotification,
+            SettingsField::Theme => SettingsField::WindowTitle,
+        }
+    }
+
+    /// First field of the settings tab, where focus lands when switching to it
+    pub fn first_in(mode: SettingsMode) -> Self {
+        match mode {
+            SettingsMode::Alerts => SettingsField::FirstWarning,
+            _ => SettingsField::WorkDuration,
         }
     }
+
+    /// Whether the field is an on/off switch rather than a number
+    pub fn is_toggle(&self) -> bool {
+        matches!(
+            self,
+            SettingsField::AutoStartBreaks
+                | SettingsField::AutoStartWork
+                | SettingsField::Overtime
+                | SettingsField::TimerMode
+                | SettingsField::AlertFlash(_)
+                | SettingsField::AlertGauge(_)
+                | SettingsField::QuietHours
+                | SettingsField::DesktopNotification
+                | SettingsField::WindowTitle
+                | SettingsField::Theme
+        )
+    }
+
+    /// Whether zero is a meaningful value, such as a disabled warning or midnight
+    pub fn allows_zero(&self) -> bool {
+        matches!(
+            self,
+            SettingsField::FocusPause
+                | SettingsField::FirstWarning
+                | SettingsField::SecondWarning
+                | SettingsField::AlertBells(_)
+                | SettingsField::QuietStart
+                | SettingsField::QuietEnd
+        )
+    }
 }
 
 /// State for the input modal
@@ -216,6 +529,53 @@ pub struct InputState {
     pub name: BoundedString<100>,
     pub description: BoundedString<500>,
     pub selected_category: usize,
+    /// Index into the open tasks, `None` for a session not tied to a task
+    pub selected_task: Option<usize>,
+}
+
+/// State for the task input modal
+#[derive(Debug, Clone, Default)]
+pub struct TaskInputState {
+    pub field: TaskField,
+    pub title: BoundedString<100>,
+    pub estimate: String,
+    pub selected_category: usize,
+}
+
+/// A cycle step being created or edited in settings
+#[derive(Debug, Clone, Default)]
+pub struct StepForm {
+    /// Some when editing, None when creating
+    pub id: Option<CycleStepId>,
+    pub field: StepField,
+    pub label: BoundedString<20>,
+    pub kind: TimerPhase,
+    pub minutes: String,
+}
+
+/// A hook being created or edited in settings
+#[derive(Debug, Clone, Default)]
+pub struct HookForm {
+    /// Some when editing, None when creating
+    pub id: Option<HookId>,
+    pub field: HookField,
+    pub event: HookEvent,
+    pub command: BoundedString<200>,
+    pub timeout: String,
+}
+
+/// A webhook being created or edited in settings
+#[derive(Debug, Clone, Default)]
+pub struct WebhookForm {
+    /// Some when editing, None when creating
+    pub id: Option<WebhookId>,
+    pub field: WebhookField,
+    pub url: BoundedString<200>,
+    pub headers: BoundedString<200>,
+    /// Events to post, or empty for all of them
+    pub events: Vec<HookEvent>,
+    /// Position in `HookEvent::ALL` of the event under the cursor
+    pub event_index: usize,
 }
 
 /// State for the settings modal
@@ -232,6 +592,62 @@ pub struct SettingsState {
     pub new_category_name: BoundedString<50>,
     pub new_category_color: BoundedString<7>,
     pub editing_category_id: Option<CategoryId>, // Some when editing, None when creating
+    /// Duration overrides in minutes, blank to use the timer settings
+    pub new_category_work: String,
+    pub new_category_short_break: String,
+    pub new_category_long_break: String,
+    // Profile mode fields
+    pub profile_list_index: usize,
+    /// Name of the profile being created, `None` while browsing the list
+    pub new_profile_name: Option<BoundedString<30>>,
+    // Cycle mode fields
+    pub cycle_list_index: usize,
+    /// Step being created or edited, `None` while browsing the list
+    pub step_form: Option<StepForm>,
+    // Hook mode fields
+    pub hook_list_index: usize,
+    /// Hook being created or edited, `None` while browsing the list
+    pub hook_form: Option<HookForm>,
+    // Webhook mode fields
+    pub webhook_list_index: usize,
+    /// Webhook being created or edited, `None` while browsing the list
+    pub webhook_form: Option<WebhookForm>,
+}
+
+impl SettingsState {
+    /// Minutes buffer of the focused duration field in the category form
+    fn category_minutes_mut(&mut self) -> Option<&mut String> {
+        match self.category_field {
+            CategoryField::WorkDuration => Some(&mut self.new_category_work),
+            CategoryField::ShortBreak => Some(&mut self.new_category_short_break),
+            CategoryField::LongBreak => Some(&mut self.new_category_long_break),
+            CategoryField::List | CategoryField::Name | CategoryField::Color => None,
+        }
+    }
+}
+
+/// State for the interruption logging modal
+#[derive(Debug, Clone, Default)]
+pub struct InterruptionState {
+    pub kind: InterruptionKind,
+    pub note: BoundedString<100>,
+    /// When the interruption key was pressed
+    pub occurred_at: Timestamp,
+}
+
+/// State for the command palette
+#[derive(Debug, Clone, Default)]
+pub struct PaletteState {
+    pub query: BoundedString<100>,
+    /// Index into the commands matching the query
+    pub selected: usize,
+}
+
+impl PaletteState {
+    /// The commands matching the query, best first
+    pub fn matches(&self) -> Vec<Command> {
+        matching_commands(self.query.as_ref())
+    }
 }
 
 /// State for the session detail modal
@@ -240,6 +656,26 @@ pub struct DetailState {
     pub selected_session_index: usize,
 }
 
+/// A row in the history list
+#[derive(Debug, Clone, Copy)]
+pub enum HistoryEntry<'a> {
+    /// A work session and its index in `AppData::sessions`
+    Session {
+        index: usize,
+        session: &'a Session,
+    },
+    Break(&'a BreakPeriod),
+}
+
+impl HistoryEntry<'_> {
+    pub fn started_at(&self) -> Timestamp {
+        match self {
+            HistoryEntry::Session { session, .. } => session.started_at,
+            HistoryEntry::Break(period) => period.started_at,
+        }
+    }
+}
+
 /// Persisted application data
 #[derive(Debug, Clone, Default)]
 pub struct AppData {
@@ -250,6 +686,48 @@ pub struct AppData {
     pub stats_period: StatsPeriod,
     pub chart_type: ChartType,
     pub category_stats: Vec<CategoryStat>,
+    pub pause_stats: PauseStat,
+    pub daily_interruptions: Vec<DailyInterruptions>,
+    pub breaks: Vec<BreakPeriod>,
+    pub show_breaks: bool,
+    pub break_stats: BreakStat,
+    /// Open tasks in queue order, followed by recently finished ones
+    pub tasks: Vec<Task>,
+    pub task_state: ListState,
+    /// Estimated vs actual pomodoros per category for the stats period
+    pub estimate_stats: Vec<EstimateStat>,
+    /// Tasks finished in the stats period, worst estimates first
+    pub finished_tasks: Vec<Task>,
+    pub profiles: Vec<Profile>,
+    pub profile_stats: Vec<ProfileStat>,
+    /// Steps of the custom phase cycle, in order
+    pub cycle_steps: Vec<CycleStep>,
+    pub hooks: Vec<Hook>,
+    pub webhooks: Vec<Webhook>,
+    /// Deliveries out of attempts, by webhook, kept until retried
+    pub failed_deliveries: HashMap<WebhookId, i64>,
+}
+
+impl AppData {
+    /// Sessions, and breaks if shown, merged most recent first
+    pub fn history_entries(&self) -> Vec<HistoryEntry<'_>> {
+        let mut entries: Vec<HistoryEntry> = self
+            .sessions
+            .iter()
+            .enumerate()
+            .map(|(index, session)| HistoryEntry::Session { index, session })
+            .collect();
+        if self.show_breaks {
+            entries.extend(self.breaks.iter().map(HistoryEntry::Break));
+            entries.sort_by_key(|e| std::cmp::Reverse(e.started_at()));
+        }
+        entries
+    }
+
+    /// Tasks still waiting to be worked on, in queue order
+    pub fn open_tasks(&self) -> Vec<&Task> {
+        self.tasks.iter().filter(|t| t.is_open()).collect()
+    }
 }
 
 /// The main application state
@@ -262,23 +740,75 @@ pub struct App {
     pub input: InputState,
     pub settings: SettingsState,
     pub detail: DetailState,
+    pub interruption: InterruptionState,
+    pub palette: PaletteState,
+    pub task_input: TaskInputState,
     pub data: AppData,
     pub notification: Option<Notification>,
+    /// The keys bound to each action in the views
+    pub keymap: Keymap,
+    /// Built-in and custom themes the theme setting picks from
+    pub themes: Themes,
+    /// Colours everything is drawn with
+    pub theme: Theme,
+    /// `NO_COLOR` was set, so the theme setting is overridden by monochrome
+    no_color: bool,
+    /// Whether the key bindings overlay is shown over the current screen
+    pub help_open: bool,
+    /// `--compact` was given, so the compact layout is used whatever the terminal size
+    pub compact: bool,
+    /// Whether the last frame was too short for anything but the compact layout
+    short_terminal: bool,
+    /// Clickable regions of the last drawn frame
+    pub hits: HitMap,
+    /// The last click, to recognise a second one as a double-click
+    last_click: Option<(Instant, ClickTarget)>,
+    /// When the current break phase started running
+    break_started_at: Option<Timestamp>,
+    /// The saved record of the phase that ended last
+    ended_record: Option<EndedRecord>,
+    /// When the active session was last checkpointed for crash recovery
+    last_checkpoint_at: Timestamp,
+    /// When the bell last rang for a phase awaiting acknowledgement
+    last_alert_at: Timestamp,
+    /// Whether the bell has rung for the work period now in overtime
+    overtime_alerted: bool,
+    /// Which of the two pre-end warnings have been given for the current phase
+    warnings_given: [bool; 2],
+    /// The screen is shown in reverse video until this time
+    flash_until: Timestamp,
+    hook_runner: HookRunner,
+    webhook_sender: WebhookSender,
+    /// Where bells and other escape sequences outside the drawn frame are written
+    term_out: Box<dyn Write>,
+    /// Window title last set, empty while the terminal's own title is shown
+    window_title: String,
+    /// When the terminal lost focus, `None` while it has focus
+    focus_lost_at: Option<Timestamp>,
+    /// Deliveries being posted right now, so a retry check does not send them twice
+    webhooks_in_flight: HashMap<DeliveryId, WebhookDelivery>,
+    /// When the webhook queue was last checked for deliveries due a retry
+    last_webhook_check_at: Timestamp,
     db: Option<Box<dyn DatabaseOps>>,
-    clock: Box<dyn Clock>,
+    /// Shared with the timer, so both see the same time
+    clock: Arc<dyn Clock>,
 }
 
 impl Default for App {
     fn default() -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
         Self {
             running: false,
             view: View::Timer,
             modal: ModalState::None,
-            timer: PomodoroTimer::new(),
+            timer: PomodoroTimer::new(clock.clone()),
             session_phase: SessionPhase::Inactive,
             input: InputState::default(),
             settings: SettingsState::default(),
             detail: DetailState::default(),
+            interruption: InterruptionState::default(),
+            palette: PaletteState::default(),
+            task_input: TaskInputState::default(),
             data: AppData {
                 categories: Category::defaults(),
                 config: Config::default(),
@@ -287,10 +817,48 @@ impl Default for App {
                 stats_period: StatsPeriod::Day,
                 chart_type: ChartType::Bar,
                 category_stats: Vec::new(),
+                pause_stats: PauseStat::default(),
+                daily_interruptions: Vec::new(),
+                breaks: Vec::new(),
+                show_breaks: false,
+                break_stats: BreakStat::default(),
+                tasks: Vec::new(),
+                task_state: ListState::default(),
+                estimate_stats: Vec::new(),
+                finished_tasks: Vec::new(),
+                profiles: Vec::new(),
+                profile_stats: Vec::new(),
+                cycle_steps: Vec::new(),
+                hooks: Vec::new(),
+                webhooks: Vec::new(),
+                failed_deliveries: HashMap::new(),
             },
             notification: None,
+            keymap: Keymap::default(),
+            themes: Themes::default(),
+            theme: Theme::default(),
+            no_color: false,
+            help_open: false,
+            compact: false,
+            short_terminal: false,
+            hits: HitMap::default(),
+            last_click: None,
+            break_started_at: None,
+            ended_record: None,
+            last_checkpoint_at: Timestamp::default(),
+            last_alert_at: Timestamp::default(),
+            overtime_alerted: false,
+            warnings_given: [false; 2],
+            flash_until: Timestamp::default(),
+            hook_runner: HookRunner::default(),
+            webhook_sender: WebhookSender::default(),
+            term_out: Box::new(std::io::stdout()),
+            window_title: String::new(),
+            focus_lost_at: None,
+            webhooks_in_flight: HashMap::new(),
+            last_webhook_check_at: Timestamp::default(),
             db: None,
-            clock: Box::new(SystemClock),
+            clock,
         }
     }
 }
@@ -312,6 +880,10 @@ impl App {
                     app.data.categories = cats;
                 }
 
+                if let Ok(profiles) = db.get_profiles() {
+                    app.data.profiles = profiles;
+                }
+
                 // Load config and apply to timer
                 if let Ok(config) = db.get_config() {
                     app.timer.apply_config(&config);
@@ -319,6 +891,10 @@ impl App {
                 }
 
                 app.db = Some(db);
+                app.recover_checkpoint();
+                app.refresh_cycle();
+                app.refresh_hooks();
+                app.refresh_webhooks();
                 app.refresh_data();
             }
             Err(e) => {
@@ -327,9 +903,74 @@ impl App {
             }
         }
 
+        // A broken bindings file falls back to the defaults rather than locking the user out
+        if let Ok(path) = config::get_keymap_path() {
+            match Keymap::load(&path) {
+                Ok(keymap) => app.keymap = keymap,
+                Err(e) => {
+                    warn!("Using default key bindings: {}", e);
+                    app.notify(
+                        NotificationLevel::Warning,
+                        format!("Default keys in use: {}", e),
+                    );
+                }
+            }
+        }
+
+        if let Ok(path) = config::get_themes_path() {
+            match Themes::load(&path) {
+                Ok(themes) => app.themes = themes,
+                Err(e) => {
+                    warn!("Using built-in themes only: {}", e);
+                    app.notify(
+                        NotificationLevel::Warning,
+                        format!("Custom themes not loaded: {}", e),
+                    );
+                }
+            }
+        }
+        app.no_color = theme::no_color_requested();
+        app.apply_theme();
+
         Ok(app)
     }
 
+    /// Save any session left running by a crash
+    ///
+    /// The checkpoint is only cleared once the session is saved, so a failure loses nothing.
+    fn recover_checkpoint(&mut self) {
+        let result = match self.db {
+            Some(ref db) => db.recover_checkpoint(),
+            None => return,
+        };
+        match result {
+            Ok(Some(session)) => self.notify(
+                NotificationLevel::Warning,
+                format!("Recovered unfinished session \"{}\"", session.name),
+            ),
+            Ok(None) => {}
+            Err(e) => {
+                error!("Failed to recover unfinished session: {}", e);
+                self.notify(
+                    NotificationLevel::Error,
+                    format!("Unfinished session not recovered: {}", e),
+                );
+            }
+        }
+    }
+
+    /// Draw with the configured theme, or without colours if `NO_COLOR` asks for that
+    fn apply_theme(&mut self) {
+        self.theme = if self.no_color {
+            Theme::monochrome()
+        } else {
+            self.themes
+                .get(&self.data.config.theme)
+                .cloned()
+                .unwrap_or_default()
+        };
+    }
+
     /// Get the current session, if any
     pub fn current_session(&self) -> Option<&Session> {
         match &self.session_phase {
@@ -338,6 +979,103 @@ impl App {
         }
     }
 
+    /// Get the timer profile whose durations are in use, if any
+    pub fn active_profile(&self) -> Option<&Profile> {
+        let id = self.data.config.active_profile?;
+        self.data.profiles.iter().find(|p| p.id == Some(id))
+    }
+
+    /// Get the task the current session is working on, if any
+    pub fn current_task(&self) -> Option<&Task> {
+        let task_id = self.current_session()?.task_id?;
+        self.data.tasks.iter().find(|t| t.id == Some(task_id))
+    }
+
+    /// Check if a work period of an active session is underway (running or paused)
+    pub fn is_working(&self) -> bool {
+        self.timer.phase == TimerPhase::Work
+            && matches!(self.session_phase, SessionPhase::Active { .. })
+            && (self.timer.is_running() || self.timer.is_paused())
+    }
+
+    /// Whether the awaiting-acknowledgement prompt is in the highlighted half of its flash
+    pub fn alert_flash_on(&self) -> bool {
+        self.clock.now_timestamp() % 2 == 0
+    }
+
+    /// Whether the whole screen is flashing for a phase-end alert
+    pub fn screen_flash_on(&self) -> bool {
+        Timestamp::from_clock(&*self.clock) < self.flash_until
+    }
+
+    /// Whether the running phase has passed a warning point and its gauge should change colour
+    pub fn gauge_warning(&self) -> bool {
+        let config = &self.data.config;
+        let furthest = config.first_warning_secs.max(config.second_warning_secs);
+        !self.timer.counts_up()
+            && (self.timer.is_running() || self.timer.is_paused())
+            && config.alert_for(self.timer.phase).gauge
+            && furthest > 0
+            && self.timer.remaining().as_secs() as i64 <= furthest
+    }
+
+    /// Whether audible alerts are currently suppressed by quiet hours
+    pub fn is_quiet_time(&self) -> bool {
+        let hour = self.clock.now_datetime().hour() as i64;
+        self.data.config.quiet_hours.contains(hour)
+    }
+
+    /// Window title showing the phase and time left, or just the app name while idle
+    pub fn window_title_text(&self) -> String {
+        if !self.timer.is_running() && !self.timer.is_paused() {
+            return "Grindstone".to_string();
+        }
+        let paused = if self.timer.is_paused() {
+            " (paused)"
+        } else {
+            ""
+        };
+        format!(
+            "{} {}{} - Grindstone",
+            self.timer.display_time(),
+            self.timer.phase_label(),
+            paused
+        )
+    }
+
+    /// Keep the window title in step with the timer, if enabled
+    fn update_window_title(&mut self) {
+        if !self.data.config.window_title {
+            self.restore_window_title();
+            return;
+        }
+        let title = self.window_title_text();
+        if title == self.window_title {
+            return;
+        }
+        // Save the terminal's own title the first time it is replaced
+        if self.window_title.is_empty() {
+            let _ = terminal::push_title(&mut self.term_out);
+        }
+        let _ = terminal::write_title(&mut self.term_out, &title);
+        self.window_title = title;
+    }
+
+    /// Give the terminal back the title it had before ours
+    fn restore_window_title(&mut self) {
+        if !self.window_title.is_empty() {
+            let _ = terminal::pop_title(&mut self.term_out);
+            self.window_title.clear();
+        }
+    }
+
+    /// Whether only the timer is drawn, in the compact layout
+    ///
+    /// The view is kept for when the terminal grows again, but keys act on the timer.
+    pub fn compact_layout(&self) -> bool {
+        self.compact || self.short_terminal
+    }
+
     /// Check if a session exists (ready or active)
     fn has_session(&self) -> bool {
         !matches!(self.session_phase, SessionPhase::Inactive)
@@ -354,29 +1092,61 @@ impl App {
                 match event {
                     AppEvent::Key(key) => self.handle_key_event(key),
                     AppEvent::Tick => self.handle_tick(),
+                    AppEvent::FocusGained => self.handle_focus_change(true),
+                    AppEvent::FocusLost => self.handle_focus_change(false),
+                    // Lay out again at the new size straight away rather than on the next draw
+                    AppEvent::Resize => terminal.autoresize()?,
+                    AppEvent::Paste(text) => self.handle_paste(&text),
+                    AppEvent::Mouse(mouse) => self.handle_mouse(mouse),
                 }
             }
         }
 
+        self.restore_window_title();
         Ok(())
     }
 
     /// Render the current view
     fn render(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        self.hits.clear();
 
-        match self.view {
-            View::Timer => render_timer(frame, area, self),
-            View::History => render_history(frame, area, self),
-            View::Stats => render_stats(frame, area, self),
+        self.short_terminal = area.height < COMPACT_BELOW_HEIGHT;
+        if self.compact_layout() {
+            render_compact(frame, area, self);
+        } else {
+            match self.view {
+                View::Timer => render_timer(frame, area, self),
+                View::History => render_history(frame, area, self),
+                View::Stats => render_stats(frame, area, self),
+                View::Tasks => render_tasks(frame, area, self),
+            }
         }
 
-        // Render modal on top if visible
+        // Render modal on top if visible; only its own regions take clicks
+        if self.modal != ModalState::None {
+            self.hits.clear();
+        }
         match self.modal {
             ModalState::None => {}
             ModalState::Input => render_input_modal(frame, area, self),
             ModalState::Settings => render_settings_modal(frame, area, self),
             ModalState::Detail => render_detail_modal(frame, area, self),
+            ModalState::Interruption => render_interruption_modal(frame, area, self),
+            ModalState::TaskInput => render_task_input_modal(frame, area, self),
+            ModalState::Palette => render_palette_modal(frame, area, self),
+        }
+
+        // Help sits above everything and takes no clicks of its own
+        if self.help_open {
+            self.hits.clear();
+            render_help_overlay(frame, area, self);
+        }
+
+        if self.screen_flash_on() {
+            frame
+                .buffer_mut()
+                .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
         }
     }
 
@@ -385,83 +1155,178 @@ impl App {
         // Clear any notification on key press
         self.notification = None;
 
-        // Handle modal input first
+        // Any key closes the help overlay
+        if self.help_open {
+            self.help_open = false;
+            return;
+        }
+
+        // Ctrl-C always quits from a view, whatever the key bindings say
+        if self.modal == ModalState::None
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+            && key.code == KeyCode::Char('c')
+        {
+            self.quit();
+            return;
+        }
+
+        let action = match key.code {
+            KeyCode::Char(c) if self.types_char(key, c) => None,
+            _ => self.keymap.action(self.key_context(), Key::from(key)),
+        };
+        if action == Some(Action::Help) {
+            self.help_open = true;
+            return;
+        }
+        self.handle_modal_key(action, key);
+    }
+
+    /// Pass a key to the open modal, or perform its action in the current view
+    fn handle_modal_key(&mut self, action: Option<Action>, key: KeyEvent) {
         match self.modal {
-            ModalState::Settings => {
-                self.handle_settings_modal_key(key);
-                return;
-            }
-            ModalState::Input => {
-                self.handle_input_modal_key(key);
-                return;
-            }
+            ModalState::Settings => self.handle_settings_modal_key(action, key),
+            ModalState::Input => self.handle_input_modal_key(action, key),
             ModalState::Detail => {
-                // Close on Esc or q
-                if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                if action == Some(Action::Quit) {
                     self.modal = ModalState::None;
                 }
-                return;
             }
-            ModalState::None => {}
+            ModalState::Interruption => self.handle_interruption_modal_key(action, key),
+            ModalState::TaskInput => self.handle_task_input_modal_key(action, key),
+            ModalState::Palette => self.handle_palette_key(action, key),
+            ModalState::None => {
+                if let Some(action) = action {
+                    self.perform_action(action);
+                }
+            }
+        }
+    }
+
+    /// Where keys are being pressed, which decides the actions they can trigger
+    pub fn key_context(&self) -> Context {
+        match self.modal {
+            ModalState::None if self.compact_layout() => Context::Timer,
+            ModalState::None => Context::from(self.view),
+            ModalState::Input => Context::Input,
+            ModalState::TaskInput => Context::TaskInput,
+            ModalState::Palette => Context::Palette,
+            ModalState::Interruption => Context::Interruption,
+            ModalState::Detail => Context::Detail,
+            ModalState::Settings => match self.settings.mode {
+                SettingsMode::Timer | SettingsMode::Alerts => Context::SettingsFields,
+                mode if self.settings_form_open() => Context::SettingsForm(mode),
+                mode => Context::SettingsList(mode),
+            },
         }
+    }
+
+    /// Whether a plain character is typed into the focused field rather than looked up
+    ///
+    /// Settings fields only take digits, so other letters there still switch tabs.
+    fn types_char(&self, key: KeyEvent, c: char) -> bool {
+        let plain = !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let accepted = match self.key_context() {
+            Context::SettingsFields => c.is_ascii_digit(),
+            _ => true,
+        };
+        plain && accepted && self.text_field_focused()
+    }
 
-        // Global keys
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Char('q')) | (_, KeyCode::Esc) => self.quit(),
-            (KeyModifiers::CONTROL, KeyCode::Char('c')) => self.quit(),
-            (_, KeyCode::Tab) => self.view = View::Timer,
-            (_, KeyCode::Char('h')) if self.view != View::History => {
+    /// Carry out an action of the current view
+    fn perform_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.quit(),
+            Action::Help => self.help_open = true,
+            Action::Palette => {
+                self.palette = PaletteState::default();
+                self.modal = ModalState::Palette;
+            }
+            Action::ShowTimer => self.view = View::Timer,
+            Action::ShowHistory | Action::ShowStats | Action::ShowTasks
+                if self.compact_layout() =>
+            {
+                self.notify(NotificationLevel::Warning, COMPACT_TIMER_ONLY);
+            }
+            Action::ShowHistory => {
                 self.view = View::History;
                 self.refresh_data();
             }
-            (_, KeyCode::Char('t')) if self.view != View::Stats => {
+            Action::ShowStats => {
                 self.view = View::Stats;
                 self.refresh_data();
             }
-            _ => {
-                // View-specific keys
-                match self.view {
-                    View::Timer => self.handle_timer_key(key),
-                    View::History => self.handle_history_key(key),
-                    View::Stats => self.handle_stats_key(key),
-                }
+            Action::ShowTasks => {
+                self.view = View::Tasks;
+                self.refresh_data();
             }
+            _ if self.compact_layout() => self.handle_timer_action(action),
+            _ => match self.view {
+                View::Timer => self.handle_timer_action(action),
+                View::History => self.handle_history_action(action),
+                View::Stats => self.handle_stats_action(action),
+                View::Tasks => self.handle_tasks_action(action),
+            },
         }
     }
 
-    /// Handle timer view keys
-    fn handle_timer_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Char('s') => {
+    /// Handle timer view actions
+    fn handle_timer_action(&mut self, action: Action) {
+        match action {
+            Action::Confirm if self.timer.is_awaiting_ack() => {
+                self.start_next_phase();
+            }
+            Action::StartTimer => {
                 if self.timer.phase.is_break() {
+                    self.record_break(true);
                     self.timer.skip_break();
+                    self.apply_timer_config();
+                } else if self.timer.is_awaiting_ack() {
+                    self.start_next_phase();
                 } else if self.timer.is_paused() {
-                    self.timer.start();
+                    self.resume_timer();
                 } else if self.timer.is_idle() && self.has_session() {
                     self.start_timer();
                 }
             }
-            KeyCode::Char('p') => {
-                if self.timer.is_running() {
-                    self.timer.pause();
-                }
+            Action::PauseTimer if self.timer.is_running() => {
+                self.pause_timer();
             }
-            KeyCode::Char('r') => {
+            Action::ResetTimer => {
                 self.timer.reset();
             }
-            KeyCode::Char('x') => {
-                if self.timer.is_running() || self.timer.is_paused() {
-                    self.stop_session();
-                }
+            Action::StopTimer if self.timer.counts_up() && !self.timer.is_idle() => {
+                self.finish_flow();
+            }
+            Action::StopTimer if self.timer.in_overtime() => {
+                self.finish_overtime();
+            }
+            Action::StopTimer if self.timer.is_running() || self.timer.is_paused() => {
+                self.stop_session();
+            }
+            Action::ToggleMode => {
+                self.toggle_timer_mode();
+            }
+            Action::ToggleBigClock => {
+                self.toggle_big_clock();
+            }
+            Action::ExtendPhase => {
+                self.extend_phase();
             }
-            KeyCode::Char('n') => {
-                self.modal = ModalState::Input;
-                self.input.field = InputField::Name;
-                self.input.name.clear();
-                self.input.description.clear();
-                self.input.selected_category = 0;
+            Action::New => {
+                self.open_input_modal(None);
             }
-            KeyCode::Char('c') => {
+            Action::InternalInterruption if self.is_working() => {
+                self.open_interruption_modal(InterruptionKind::Internal);
+            }
+            Action::ExternalInterruption if self.is_working() => {
+                self.open_interruption_modal(InterruptionKind::External);
+            }
+            Action::CycleProfile => {
+                self.cycle_profile();
+            }
+            Action::OpenSettings => {
                 self.modal = ModalState::Settings;
                 self.settings.field = SettingsField::WorkDuration;
                 self.settings.editing_config = self.data.config.clone();
@@ -471,13 +1336,13 @@ impl App {
         }
     }
 
-    /// Count the number of rendered list items in history view (sessions + date headers)
-    fn count_history_list_items(sessions: &[Session]) -> usize {
+    /// Count the number of rendered list items in history view (entries + date headers)
+    fn count_history_list_items(entries: &[HistoryEntry]) -> usize {
         let mut count = 0;
         let mut current_date: Option<(i32, u32, u32)> = None;
 
-        for session in sessions {
-            let dt = session.start_datetime();
+        for entry in entries {
+            let dt = entry.started_at().to_datetime();
             let date = (dt.year(), dt.month(), dt.day());
 
             // Add header for new date
@@ -491,13 +1356,13 @@ impl App {
         count.max(1) // At least 1 for "No sessions" message
     }
 
-    /// Map a rendered list index to the actual session index (skipping headers)
-    fn list_index_to_session_index(sessions: &[Session], list_idx: usize) -> Option<usize> {
+    /// Map a rendered list index to the actual session index (skipping headers and breaks)
+    fn list_index_to_session_index(entries: &[HistoryEntry], list_idx: usize) -> Option<usize> {
         let mut rendered_idx = 0;
         let mut current_date: Option<(i32, u32, u32)> = None;
 
-        for (session_idx, session) in sessions.iter().enumerate() {
-            let dt = session.start_datetime();
+        for entry in entries {
+            let dt = entry.started_at().to_datetime();
             let date = (dt.year(), dt.month(), dt.day());
 
             // Check for date header
@@ -511,25 +1376,28 @@ impl App {
 
             // Check for session
             if rendered_idx == list_idx {
-                return Some(session_idx);
+                return match entry {
+                    HistoryEntry::Session { index, .. } => Some(*index),
+                    HistoryEntry::Break(_) => None,
+                };
             }
             rendered_idx += 1;
         }
         None
     }
 
-    /// Handle history view keys
-    fn handle_history_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                let len = Self::count_history_list_items(&self.data.sessions);
+    /// Handle history view actions
+    fn handle_history_action(&mut self, action: Action) {
+        match action {
+            Action::SelectNext => {
+                let len = Self::count_history_list_items(&self.data.history_entries());
                 if len > 0 {
                     let i = self.data.history_state.selected().map(|i| (i + 1) % len);
                     self.data.history_state.select(i.or(Some(0)));
                 }
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                let len = Self::count_history_list_items(&self.data.sessions);
+            Action::SelectPrev => {
+                let len = Self::count_history_list_items(&self.data.history_entries());
                 if len > 0 {
                     let i = self
                         .data
@@ -539,10 +1407,11 @@ impl App {
                     self.data.history_state.select(i.or(Some(0)));
                 }
             }
-            KeyCode::Char('d') => {
+            Action::Delete => {
                 // Delete selected session
-                if let Some(idx) = self.data.history_state.selected()
-                    && idx < self.data.sessions.len()
+                if let Some(list_idx) = self.data.history_state.selected()
+                    && let Some(idx) =
+                        Self::list_index_to_session_index(&self.data.history_entries(), list_idx)
                     && let Some(id) = self.data.sessions[idx].id
                     && let Some(ref db) = self.db
                 {
@@ -553,170 +1422,1410 @@ impl App {
                     self.refresh_data();
                 }
             }
-            KeyCode::Enter => {
+            Action::Confirm => {
                 // Open detail modal for selected session
                 if let Some(list_idx) = self.data.history_state.selected()
                     && let Some(session_idx) =
-                        Self::list_index_to_session_index(&self.data.sessions, list_idx)
+                        Self::list_index_to_session_index(&self.data.history_entries(), list_idx)
                 {
                     self.detail.selected_session_index = session_idx;
                     self.modal = ModalState::Detail;
                 }
             }
+            Action::ToggleBreaks => {
+                self.data.show_breaks = !self.data.show_breaks;
+                self.data.history_state.select(None);
+            }
             _ => {}
         }
     }
 
-    /// Handle stats view keys
-    fn handle_stats_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Left | KeyCode::Char('h') => {
+    /// Handle stats view actions
+    fn handle_stats_action(&mut self, action: Action) {
+        match action {
+            Action::PrevPeriod => {
                 self.data.stats_period = self.data.stats_period.prev();
                 self.refresh_data();
             }
-            KeyCode::Right | KeyCode::Char('l') => {
+            Action::NextPeriod => {
                 self.data.stats_period = self.data.stats_period.next();
                 self.refresh_data();
             }
-            KeyCode::Char('v') => {
-                self.data.chart_type = self.data.chart_type.toggle();
+            Action::NextChart => {
+                self.data.chart_type = self.data.chart_type.next();
             }
             _ => {}
         }
     }
 
-    /// Handle input modal keys
-    fn handle_input_modal_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Esc => {
-                self.modal = ModalState::None;
+    /// Handle tasks view actions
+    fn handle_tasks_action(&mut self, action: Action) {
+        let len = self.data.tasks.len();
+        let selected = self.data.task_state.selected().filter(|&i| i < len);
+
+        match action {
+            Action::SelectNext if len > 0 => {
+                let i = selected.map_or(0, |i| (i + 1) % len);
+                self.data.task_state.select(Some(i));
             }
-            KeyCode::Tab => {
-                self.input.field = self.input.field.next();
+            Action::SelectPrev if len > 0 => {
+                let i = selected.map_or(0, |i| if i == 0 { len - 1 } else { i - 1 });
+                self.data.task_state.select(Some(i));
             }
-            KeyCode::Enter => {
-                if validate_session_name(self.input.name.as_ref()) {
-                    self.create_session();
-                    self.modal = ModalState::None;
-                    self.start_timer();
+            Action::MoveDown => {
+                if let Some(i) = selected {
+                    self.move_task(i, i + 1);
                 }
             }
-            KeyCode::Backspace => match self.input.field {
-                InputField::Name => {
-                    self.input.name.pop();
+            Action::MoveUp => {
+                if let Some(i) = selected
+                    && i > 0
+                {
+                    self.move_task(i, i - 1);
                 }
-                InputField::Description => {
-                    self.input.description.pop();
+            }
+            Action::New => {
+                self.modal = ModalState::TaskInput;
+                self.task_input = TaskInputState {
+                    estimate: "1".to_string(),
+                    ..TaskInputState::default()
+                };
+            }
+            Action::Confirm => {
+                // Start a session for the selected open task
+                if let Some(i) = selected
+                    && self.data.tasks[i].is_open()
+                {
+                    let open_index = self.data.tasks[..i].iter().filter(|t| t.is_open()).count();
+                    self.view = View::Timer;
+                    self.open_input_modal(Some(open_index));
+                }
+            }
+            Action::CompleteTask => {
+                if let Some(i) = selected
+                    && self.data.tasks[i].is_open()
+                    && let Some(id) = self.data.tasks[i].id
+                {
+                    self.complete_task(id);
+                }
+            }
+            Action::Delete => {
+                if let Some(i) = selected
+                    && let Some(id) = self.data.tasks[i].id
+                {
+                    self.delete_task(id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Delete a task, unlinking the current session from it if it is the one being worked on
+    ///
+    /// The session would otherwise be saved against a task that no longer exists.
+    fn delete_task(&mut self, id: TaskId) {
+        let Some(ref db) = self.db else {
+            return;
+        };
+        if let Err(e) = db.delete_task(id) {
+            warn!("Failed to delete task: {}", e);
+            self.notify(NotificationLevel::Warning, "Failed to delete task");
+            self.refresh_data();
+            return;
+        }
+
+        if let SessionPhase::Ready(session) | SessionPhase::Active { session, .. } =
+            &mut self.session_phase
+            && session.task_id == Some(id)
+        {
+            session.task_id = None;
+            self.checkpoint_session();
+        }
+        self.refresh_data();
+    }
+
+    /// Swap an open task with its neighbour in the queue, keeping it selected
+    fn move_task(&mut self, from: usize, to: usize) {
+        let (Some(a), Some(b)) = (self.data.tasks.get(from), self.data.tasks.get(to)) else {
+            return;
+        };
+        if !a.is_open() || !b.is_open() {
+            return;
+        }
+        let (Some(a), Some(b)) = (a.id, b.id) else {
+            return;
+        };
+
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.swap_task_positions(a, b) {
+                warn!("Failed to reorder tasks: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to reorder tasks");
+                return;
+            }
+            self.data.task_state.select(Some(to));
+            self.refresh_data();
+        }
+    }
+
+    /// Mark a task as done, recording its actual pomodoros against the estimate
+    fn complete_task(&mut self, id: TaskId) {
+        let now = Timestamp::from_clock(&*self.clock);
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.complete_task(id, now) {
+                warn!("Failed to complete task: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to complete task");
+                return;
+            }
+            self.refresh_data();
+            if let Some(task) = self.data.tasks.iter().find(|t| t.id == Some(id)) {
+                let message = format!(
+                    "Finished \"{}\" in {} of {} estimated pomodoros",
+                    task.title, task.pomodoros, task.estimate
+                );
+                self.notify(NotificationLevel::Info, message);
+            }
+        }
+    }
+
+    /// Open the new session modal, optionally preselecting one of the open tasks
+    fn open_input_modal(&mut self, task: Option<usize>) {
+        self.modal = ModalState::Input;
+        self.input.field = InputField::Name;
+        self.input.name.clear();
+        self.input.description.clear();
+        self.input.selected_category = 0;
+        self.input.selected_task = None;
+        if task.is_some() {
+            self.select_task(task);
+        }
+    }
+
+    /// Select an open task in the input modal, filling in its title and category
+    fn select_task(&mut self, index: Option<usize>) {
+        self.input.selected_task = index;
+        let Some(task) = index.and_then(|i| self.data.open_tasks().get(i).copied()) else {
+            return;
+        };
+
+        let title = task.title.clone();
+        let category = self
+            .data
+            .categories
+            .iter()
+            .position(|c| c.name == task.category);
+        self.input.name.clear();
+        for c in title.chars() {
+            self.input.name.push(c);
+        }
+        if let Some(category) = category {
+            self.input.selected_category = category;
+        }
+    }
+
+    /// Handle task input modal keys
+    fn handle_task_input_modal_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::NextField), _) => {
+                self.task_input.field = self.task_input.field.next();
+            }
+            (Some(Action::Confirm), _) => {
+                self.save_task();
+            }
+            (Some(Action::Erase), _) => match self.task_input.field {
+                TaskField::Title => {
+                    self.task_input.title.pop();
+                }
+                TaskField::Estimate => {
+                    self.task_input.estimate.pop();
                 }
-                InputField::Category => {}
+                TaskField::Category => {}
             },
-            KeyCode::Left => {
-                if self.input.field == InputField::Category {
-                    if self.input.selected_category == 0 {
-                        self.input.selected_category = self.data.categories.len() - 1;
-                    } else {
-                        self.input.selected_category -= 1;
-                    }
+            (Some(Action::PrevChoice), _) if self.task_input.field == TaskField::Category => {
+                if self.task_input.selected_category == 0 {
+                    self.task_input.selected_category = self.data.categories.len() - 1;
+                } else {
+                    self.task_input.selected_category -= 1;
+                }
+            }
+            (Some(Action::NextChoice), _) if self.task_input.field == TaskField::Category => {
+                self.task_input.selected_category =
+                    (self.task_input.selected_category + 1) % self.data.categories.len();
+            }
+            (None, KeyCode::Char(c)) => match self.task_input.field {
+                TaskField::Title => {
+                    self.task_input.title.push(c);
                 }
+                TaskField::Estimate if c.is_ascii_digit() && self.task_input.estimate.len() < 3 => {
+                    self.task_input.estimate.push(c);
+                }
+                TaskField::Estimate | TaskField::Category => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Add the task being edited to the end of the queue
+    fn save_task(&mut self) {
+        let title = self.task_input.title.to_string();
+        if title.trim().is_empty() {
+            self.notify(NotificationLevel::Warning, "Task title cannot be empty");
+            return;
+        }
+        let estimate = match self.task_input.estimate.parse::<i64>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                self.notify(
+                    NotificationLevel::Warning,
+                    "Estimate must be at least 1 pomodoro",
+                );
+                return;
+            }
+        };
+        let category = self.data.categories[self.task_input.selected_category]
+            .name
+            .clone();
+
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.create_task(title.trim(), &category, estimate) {
+                warn!("Failed to create task: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to create task");
+                return;
+            }
+            self.modal = ModalState::None;
+            self.refresh_data();
+        } else {
+            self.notify(NotificationLevel::Warning, "No database connection");
+        }
+    }
+
+    /// Handle input modal keys
+    fn handle_input_modal_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::NextField), _) => {
+                self.input.field = self.input.field.next();
             }
-            KeyCode::Right => {
-                if self.input.field == InputField::Category {
-                    self.input.selected_category =
-                        (self.input.selected_category + 1) % self.data.categories.len();
+            (Some(Action::Confirm), _) if validate_session_name(self.input.name.as_ref()) => {
+                self.create_session();
+                self.modal = ModalState::None;
+                self.start_timer();
+            }
+            (Some(Action::Erase), _) => match self.input.field {
+                InputField::Name => {
+                    self.input.name.pop();
+                }
+                InputField::Description => {
+                    self.input.description.pop();
+                }
+                InputField::Category | InputField::Task => {}
+            },
+            (Some(Action::PrevChoice), _) if self.input.field == InputField::Category => {
+                if self.input.selected_category == 0 {
+                    self.input.selected_category = self.data.categories.len() - 1;
+                } else {
+                    self.input.selected_category -= 1;
                 }
             }
-            KeyCode::Char(c) => match self.input.field {
+            (Some(Action::NextChoice), _) if self.input.field == InputField::Category => {
+                self.input.selected_category =
+                    (self.input.selected_category + 1) % self.data.categories.len();
+            }
+            (Some(Action::PrevChoice), _) if self.input.field == InputField::Task => {
+                // Cycle through the open tasks, with "no task" before the first
+                let len = self.data.open_tasks().len();
+                let task = match self.input.selected_task {
+                    None => len.checked_sub(1),
+                    Some(0) => None,
+                    Some(i) => Some(i - 1),
+                };
+                self.select_task(task);
+            }
+            (Some(Action::NextChoice), _) if self.input.field == InputField::Task => {
+                let len = self.data.open_tasks().len();
+                let task = match self.input.selected_task {
+                    None if len > 0 => Some(0),
+                    Some(i) if i + 1 < len => Some(i + 1),
+                    _ => None,
+                };
+                self.select_task(task);
+            }
+            (None, KeyCode::Char(c)) => match self.input.field {
                 InputField::Name => {
                     self.input.name.push(c);
                 }
                 InputField::Description => {
                     self.input.description.push(c);
                 }
-                InputField::Category => {}
+                InputField::Category | InputField::Task => {}
             },
             _ => {}
         }
     }
 
+    /// Handle interruption modal keys
+    fn handle_interruption_modal_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::Confirm), _) => {
+                self.log_interruption();
+                self.modal = ModalState::None;
+            }
+            (Some(Action::Erase), _) => {
+                self.interruption.note.pop();
+            }
+            (None, KeyCode::Char(c)) => {
+                self.interruption.note.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_palette_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        let count = self.palette.matches().len();
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::NextField), _) if count > 0 => {
+                self.palette.selected = (self.palette.selected + 1) % count;
+            }
+            (Some(Action::PrevField), _) if count > 0 => {
+                self.palette.selected = (self.palette.selected + count - 1) % count;
+            }
+            (Some(Action::Confirm), _) => {
+                if let Some(&command) = self.palette.matches().get(self.palette.selected) {
+                    self.run_command(command);
+                }
+            }
+            (Some(Action::Erase), _) => {
+                self.palette.query.pop();
+                self.palette.selected = 0;
+            }
+            (None, KeyCode::Char(c)) => {
+                self.palette.query.push(c);
+                self.palette.selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Close the palette and perform a command as its key would, in the view it belongs to
+    fn run_command(&mut self, command: Command) {
+        self.modal = ModalState::None;
+        if self.compact_layout() && command.view.is_some_and(|view| view != View::Timer) {
+            self.notify(NotificationLevel::Warning, COMPACT_TIMER_ONLY);
+            return;
+        }
+        if let Some(view) = command.view
+            && view != self.view
+        {
+            self.perform_action(match view {
+                View::Timer => Action::ShowTimer,
+                View::History => Action::ShowHistory,
+                View::Stats => Action::ShowStats,
+                View::Tasks => Action::ShowTasks,
+            });
+        }
+        self.perform_action(command.action);
+    }
+
+    /// Whether a form is open on the current settings tab, which keeps the tab from switching
+    fn settings_form_open(&self) -> bool {
+        match self.settings.mode {
+            SettingsMode::Categories => self.settings.category_field != CategoryField::List,
+            SettingsMode::Profiles => self.settings.new_profile_name.is_some(),
+            SettingsMode::Cycle => self.settings.step_form.is_some(),
+            SettingsMode::Hooks => self.settings.hook_form.is_some(),
+            SettingsMode::Webhooks => self.settings.webhook_form.is_some(),
+            SettingsMode::Timer | SettingsMode::Alerts => false,
+        }
+    }
+
     /// Handle settings modal keys
-    fn handle_settings_modal_key(&mut self, key: KeyEvent) {
-        // Mode switching with arrows and vim keys (h/l)
-        // Only block when editing category form fields (Name/Color need text input)
-        let editing_category_form = self.settings.mode == SettingsMode::Categories
-            && self.settings.category_field != CategoryField::List;
-
-        if !editing_category_form {
-            match key.code {
-                KeyCode::Left | KeyCode::Char('h') => {
-                    self.settings.mode = SettingsMode::Timer;
-                    return;
+    fn handle_settings_modal_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        match action {
+            Some(Action::PrevTab) => self.switch_settings_mode(self.settings.mode.prev()),
+            Some(Action::NextTab) => self.switch_settings_mode(self.settings.mode.next()),
+            _ => match self.settings.mode {
+                SettingsMode::Timer | SettingsMode::Alerts => {
+                    self.handle_timer_settings_key(action, key)
+                }
+                SettingsMode::Profiles => self.handle_profile_settings_key(action, key),
+                SettingsMode::Cycle => self.handle_cycle_settings_key(action, key),
+                SettingsMode::Hooks => self.handle_hook_settings_key(action, key),
+                SettingsMode::Webhooks => self.handle_webhook_settings_key(action, key),
+                SettingsMode::Categories => self.handle_category_settings_key(action, key),
+            },
+        }
+    }
+
+    /// Move to another settings tab, keeping any value typed on the one being left
+    fn switch_settings_mode(&mut self, mode: SettingsMode) {
+        if mode == self.settings.mode {
+            return;
+        }
+        self.apply_editing_value();
+        self.settings.mode = mode;
+        self.settings.field = SettingsField::first_in(mode);
+        self.settings.editing_value = self.get_editing_field_value();
+    }
+
+    /// Handle timer and alert settings mode keys
+    fn handle_timer_settings_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::NextField), _) => {
+                self.apply_editing_value();
+                self.settings.field = self.settings.field.next();
+                self.settings.editing_value = self.get_editing_field_value();
+            }
+            (Some(Action::PrevField), _) => {
+                self.apply_editing_value();
+                self.settings.field = self.settings.field.prev();
+                self.settings.editing_value = self.get_editing_field_value();
+            }
+            (Some(Action::Confirm), _) => {
+                self.save_settings();
+                self.modal = ModalState::None;
+            }
+            (Some(Action::Erase), _) => {
+                self.settings.editing_value.pop();
+            }
+            (Some(Action::Toggle), _) if self.settings.field.is_toggle() => {
+                let config = &mut self.settings.editing_config;
+                match self.settings.field {
+                    SettingsField::AutoStartBreaks => {
+                        config.auto_start_breaks = !config.auto_start_breaks;
+                    }
+                    SettingsField::AutoStartWork => {
+                        config.auto_start_work = !config.auto_start_work;
+                    }
+                    SettingsField::Overtime => {
+                        config.overtime = !config.overtime;
+                    }
+                    SettingsField::TimerMode => {
+                        config.timer_mode = config.timer_mode.toggle();
+                    }
+                    SettingsField::AlertFlash(phase) => {
+                        let alert = config.alert_for_mut(phase);
+                        alert.flash = !alert.flash;
+                    }
+                    SettingsField::AlertGauge(phase) => {
+                        let alert = config.alert_for_mut(phase);
+                        alert.gauge = !alert.gauge;
+                    }
+                    SettingsField::QuietHours => {
+                        config.quiet_hours.enabled = !config.quiet_hours.enabled;
+                    }
+                    SettingsField::DesktopNotification => {
+                        config.desktop_notification = config.desktop_notification.next();
+                    }
+                    SettingsField::WindowTitle => {
+                        config.window_title = !config.window_title;
+                    }
+                    SettingsField::Theme => {
+                        config.theme = self.themes.next_name(&config.theme).to_string();
+                    }
+                    _ => {}
+                }
+            }
+            (None, KeyCode::Char(c)) if c.is_ascii_digit() && !self.settings.field.is_toggle() => {
+                self.settings.editing_value.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle profile settings mode keys
+    fn handle_profile_settings_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        // Typing the name of a new profile
+        if let Some(name) = &mut self.settings.new_profile_name {
+            match (action, key.code) {
+                (Some(Action::Cancel), _) => self.settings.new_profile_name = None,
+                (Some(Action::Confirm), _) => self.save_profile(),
+                (Some(Action::Erase), _) => {
+                    name.pop();
+                }
+                (None, KeyCode::Char(c)) => name.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        let len = self.data.profiles.len();
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::SelectNext), _) if len > 0 => {
+                self.settings.profile_list_index = (self.settings.profile_list_index + 1) % len;
+            }
+            (Some(Action::SelectPrev), _) if len > 0 => {
+                self.settings.profile_list_index =
+                    (self.settings.profile_list_index + len - 1) % len;
+            }
+            (Some(Action::Confirm), _) => {
+                self.switch_profile(self.settings.profile_list_index);
+                self.settings.editing_config = self.data.config.clone();
+                self.settings.editing_value = self.get_editing_field_value();
+            }
+            (Some(Action::New), _) => {
+                self.settings.new_profile_name = Some(BoundedString::default());
+            }
+            (Some(Action::Delete), _) => {
+                self.delete_selected_profile();
+            }
+            _ => {}
+        }
+    }
+
+    /// Create a profile named in the settings form from the current durations
+    fn save_profile(&mut self) {
+        let name = self
+            .settings
+            .new_profile_name
+            .as_ref()
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        if let Err(msg) = validate_new_profile_name(&name, &self.data.profiles) {
+            self.notify(NotificationLevel::Warning, msg);
+            return;
+        }
+
+        let profile = Profile::from_config(name.trim(), &self.data.config);
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.create_profile(&profile) {
+                warn!("Failed to create profile: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to create profile");
+                return;
+            }
+            self.refresh_profiles();
+            self.settings.new_profile_name = None;
+            if let Some(index) = self
+                .data
+                .profiles
+                .iter()
+                .position(|p| p.name == profile.name)
+            {
+                self.settings.profile_list_index = index;
+            }
+        } else {
+            self.notify(NotificationLevel::Warning, "No database connection");
+        }
+    }
+
+    /// Delete the profile selected in settings, unless it is in use
+    fn delete_selected_profile(&mut self) {
+        let Some(profile) = self.data.profiles.get(self.settings.profile_list_index) else {
+            return;
+        };
+        let Some(id) = profile.id else {
+            return;
+        };
+        if self.data.config.active_profile == Some(id) {
+            self.notify(
+                NotificationLevel::Warning,
+                "Cannot delete the active profile",
+            );
+            return;
+        }
+
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.delete_profile(id) {
+                warn!("Failed to delete profile: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to delete profile");
+                return;
+            }
+            self.refresh_profiles();
+            self.settings.profile_list_index = self
+                .settings
+                .profile_list_index
+                .min(self.data.profiles.len().saturating_sub(1));
+        }
+    }
+
+    /// Switch to the next profile from the timer view
+    fn cycle_profile(&mut self) {
+        let len = self.data.profiles.len();
+        if len == 0 {
+            return;
+        }
+        let next = self
+            .data
+            .config
+            .active_profile
+            .and_then(|id| self.data.profiles.iter().position(|p| p.id == Some(id)))
+            .map_or(0, |i| (i + 1) % len);
+        self.switch_profile(next);
+    }
+
+    /// Make a profile active, applying its durations to the timer
+    fn switch_profile(&mut self, index: usize) {
+        if self.timer.is_running() || self.timer.is_paused() {
+            self.notify(
+                NotificationLevel::Warning,
+                "Stop the timer before switching profiles",
+            );
+            return;
+        }
+        let Some(profile) = self.data.profiles.get(index) else {
+            return;
+        };
+
+        profile.apply_to(&mut self.data.config);
+        self.apply_timer_config();
+
+        if let Some(ref db) = self.db
+            && let Err(e) = db.save_config(&self.data.config)
+        {
+            warn!("Failed to save active profile: {}", e);
+            self.notify(NotificationLevel::Warning, "Failed to switch profile");
+        }
+    }
+
+    /// Refresh profiles from database
+    fn refresh_profiles(&mut self) {
+        if let Some(ref db) = self.db
+            && let Ok(profiles) = db.get_profiles()
+        {
+            self.data.profiles = profiles;
+        }
+    }
+
+    /// Handle cycle settings mode keys
+    fn handle_cycle_settings_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        if let Some(form) = &mut self.settings.step_form {
+            match (action, key.code) {
+                (Some(Action::Cancel), _) => self.settings.step_form = None,
+                (Some(Action::Confirm), _) => self.save_cycle_step(),
+                (Some(Action::NextField), _) => form.field = form.field.next(),
+                (Some(Action::Erase), _) => match form.field {
+                    StepField::Label => {
+                        form.label.pop();
+                    }
+                    StepField::Minutes => {
+                        form.minutes.pop();
+                    }
+                    StepField::Kind => {}
+                },
+                (Some(Action::Toggle), _) if form.field == StepField::Kind => {
+                    form.kind = form.kind.next();
+                }
+                (None, KeyCode::Char(c)) => match form.field {
+                    StepField::Label => form.label.push(c),
+                    StepField::Minutes if c.is_ascii_digit() => form.minutes.push(c),
+                    StepField::Minutes | StepField::Kind => {}
+                },
+                _ => {}
+            }
+            return;
+        }
+
+        let len = self.data.cycle_steps.len();
+        let index = self.settings.cycle_list_index;
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::SelectNext), _) if len > 0 => {
+                self.settings.cycle_list_index = (index + 1) % len;
+            }
+            (Some(Action::SelectPrev), _) if len > 0 => {
+                self.settings.cycle_list_index = (index + len - 1) % len;
+            }
+            (Some(Action::MoveDown), _) if index + 1 < len => {
+                self.move_cycle_step(index, index + 1);
+            }
+            (Some(Action::MoveUp), _) if index > 0 && index < len => {
+                self.move_cycle_step(index, index - 1);
+            }
+            (Some(Action::New), _) => {
+                self.settings.step_form = Some(StepForm {
+                    minutes: "25".to_string(),
+                    ..StepForm::default()
+                });
+            }
+            (Some(Action::Edit | Action::Confirm), _) => {
+                if let Some(step) = self.data.cycle_steps.get(index) {
+                    let mut label = BoundedString::default();
+                    step.label.chars().for_each(|c| label.push(c));
+                    self.settings.step_form = Some(StepForm {
+                        id: step.id,
+                        field: StepField::Label,
+                        label,
+                        kind: step.kind,
+                        minutes: (step.duration_secs / 60).to_string(),
+                    });
+                }
+            }
+            (Some(Action::Delete), _) => {
+                self.delete_selected_cycle_step();
+            }
+            (Some(Action::Toggle), _) => {
+                self.toggle_custom_cycle();
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the custom cycle is driving a started timer, warning that it cannot change
+    ///
+    /// Editing the steps then would swap the phase out from under the timer.
+    fn cycle_locked(&mut self) -> bool {
+        let locked =
+            self.data.config.custom_cycle && (self.timer.is_running() || self.timer.is_paused());
+        if locked {
+            self.notify(
+                NotificationLevel::Warning,
+                "Stop the timer before changing the cycle",
+            );
+        }
+        locked
+    }
+
+    /// Create or update the cycle step in the settings form
+    fn save_cycle_step(&mut self) {
+        if self.cycle_locked() {
+            return;
+        }
+        let Some(form) = &self.settings.step_form else {
+            return;
+        };
+        if let Err(msg) = validate_cycle_step(form.label.as_ref(), &form.minutes) {
+            self.notify(NotificationLevel::Warning, msg);
+            return;
+        }
+        let step = CycleStep {
+            id: form.id,
+            label: form.label.as_ref().trim().to_string(),
+            kind: form.kind,
+            duration_secs: form.minutes.parse::<i64>().unwrap_or_default() * 60,
+        };
+
+        if let Some(ref db) = self.db {
+            let result = match step.id {
+                Some(_) => db.update_cycle_step(&step).map(|_| ()),
+                None => db.create_cycle_step(&step).map(|_| ()),
+            };
+            if let Err(e) = result {
+                warn!("Failed to save cycle step: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to save cycle step");
+                return;
+            }
+            if step.id.is_none() {
+                self.settings.cycle_list_index = self.data.cycle_steps.len();
+            }
+            self.settings.step_form = None;
+            self.refresh_cycle();
+        } else {
+            self.notify(NotificationLevel::Warning, "No database connection");
+        }
+    }
+
+    /// Delete the cycle step selected in settings
+    fn delete_selected_cycle_step(&mut self) {
+        if self.cycle_locked() {
+            return;
+        }
+        let Some(id) = self
+            .data
+            .cycle_steps
+            .get(self.settings.cycle_list_index)
+            .and_then(|s| s.id)
+        else {
+            return;
+        };
+
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.delete_cycle_step(id) {
+                warn!("Failed to delete cycle step: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to delete cycle step");
+                return;
+            }
+            self.refresh_cycle();
+            self.settings.cycle_list_index = self
+                .settings
+                .cycle_list_index
+                .min(self.data.cycle_steps.len().saturating_sub(1));
+        }
+    }
+
+    /// Swap a cycle step with its neighbour, keeping it selected
+    fn move_cycle_step(&mut self, from: usize, to: usize) {
+        if self.cycle_locked() {
+            return;
+        }
+        let (Some(a), Some(b)) = (
+            self.data.cycle_steps.get(from).and_then(|s| s.id),
+            self.data.cycle_steps.get(to).and_then(|s| s.id),
+        ) else {
+            return;
+        };
+
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.swap_cycle_step_positions(a, b) {
+                warn!("Failed to reorder cycle steps: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to reorder cycle steps");
+                return;
+            }
+            self.settings.cycle_list_index = to;
+            self.refresh_cycle();
+        }
+    }
+
+    /// Switch between the custom cycle and the standard one
+    fn toggle_custom_cycle(&mut self) {
+        if self.timer.is_running() || self.timer.is_paused() {
+            self.notify(
+                NotificationLevel::Warning,
+                "Stop the timer before switching cycles",
+            );
+            return;
+        }
+
+        self.data.config.custom_cycle = !self.data.config.custom_cycle;
+        self.settings.editing_config.custom_cycle = self.data.config.custom_cycle;
+        self.apply_cycle();
+        self.timer.restart_cycle();
+
+        if let Some(ref db) = self.db
+            && let Err(e) = db.save_config(&self.data.config)
+        {
+            warn!("Failed to save cycle setting: {}", e);
+            self.notify(NotificationLevel::Warning, "Failed to switch cycles");
+        }
+    }
+
+    /// Reload the custom cycle from the database
+    fn refresh_cycle(&mut self) {
+        if let Some(ref db) = self.db
+            && let Ok(steps) = db.get_cycle_steps()
+        {
+            self.data.cycle_steps = steps;
+        }
+        self.apply_cycle();
+    }
+
+    /// Hand the custom cycle to the timer when it is enabled
+    fn apply_cycle(&mut self) {
+        let steps = if self.data.config.custom_cycle {
+            self.data.cycle_steps.clone()
+        } else {
+            Vec::new()
+        };
+        self.timer.set_cycle(steps);
+    }
+
+    /// Handle hook settings mode keys
+    fn handle_hook_settings_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        if let Some(form) = &mut self.settings.hook_form {
+            match (action, key.code) {
+                (Some(Action::Cancel), _) => self.settings.hook_form = None,
+                (Some(Action::Confirm), _) => self.save_hook(),
+                (Some(Action::NextField), _) => form.field = form.field.next(),
+                (Some(Action::Erase), _) => match form.field {
+                    HookField::Command => {
+                        form.command.pop();
+                    }
+                    HookField::Timeout => {
+                        form.timeout.pop();
+                    }
+                    HookField::Event => {}
+                },
+                (Some(Action::Toggle), _) if form.field == HookField::Event => {
+                    form.event = form.event.next();
+                }
+                (None, KeyCode::Char(c)) => match form.field {
+                    HookField::Command => form.command.push(c),
+                    HookField::Timeout if c.is_ascii_digit() => form.timeout.push(c),
+                    HookField::Timeout | HookField::Event => {}
+                },
+                _ => {}
+            }
+            return;
+        }
+
+        let len = self.data.hooks.len();
+        let index = self.settings.hook_list_index;
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::SelectNext), _) if len > 0 => {
+                self.settings.hook_list_index = (index + 1) % len;
+            }
+            (Some(Action::SelectPrev), _) if len > 0 => {
+                self.settings.hook_list_index = (index + len - 1) % len;
+            }
+            (Some(Action::New), _) => {
+                self.settings.hook_form = Some(HookForm {
+                    timeout: Hook::DEFAULT_TIMEOUT_SECS.to_string(),
+                    ..HookForm::default()
+                });
+            }
+            (Some(Action::Edit | Action::Confirm), _) => {
+                if let Some(hook) = self.data.hooks.get(index) {
+                    let mut command = BoundedString::default();
+                    hook.command.chars().for_each(|c| command.push(c));
+                    self.settings.hook_form = Some(HookForm {
+                        id: hook.id,
+                        field: HookField::Command,
+                        event: hook.event,
+                        command,
+                        timeout: hook.timeout_secs.to_string(),
+                    });
+                }
+            }
+            (Some(Action::Delete), _) => {
+                self.delete_selected_hook();
+            }
+            _ => {}
+        }
+    }
+
+    /// Create or update the hook in the settings form
+    fn save_hook(&mut self) {
+        let Some(form) = &self.settings.hook_form else {
+            return;
+        };
+        if let Err(msg) = validate_hook(form.command.as_ref(), &form.timeout) {
+            self.notify(NotificationLevel::Warning, msg);
+            return;
+        }
+        let hook = Hook {
+            id: form.id,
+            event: form.event,
+            command: form.command.as_ref().trim().to_string(),
+            timeout_secs: form
+                .timeout
+                .parse::<i64>()
+                .unwrap_or(Hook::DEFAULT_TIMEOUT_SECS),
+        };
+
+        if let Some(ref db) = self.db {
+            let result = match hook.id {
+                Some(_) => db.update_hook(&hook).map(|_| ()),
+                None => db.create_hook(&hook).map(|_| ()),
+            };
+            if let Err(e) = result {
+                warn!("Failed to save hook: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to save hook");
+                return;
+            }
+            if hook.id.is_none() {
+                self.settings.hook_list_index = self.data.hooks.len();
+            }
+            self.settings.hook_form = None;
+            self.refresh_hooks();
+        } else {
+            self.notify(NotificationLevel::Warning, "No database connection");
+        }
+    }
+
+    /// Delete the hook selected in settings
+    fn delete_selected_hook(&mut self) {
+        let Some(id) = self
+            .data
+            .hooks
+            .get(self.settings.hook_list_index)
+            .and_then(|h| h.id)
+        else {
+            return;
+        };
+
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.delete_hook(id) {
+                warn!("Failed to delete hook: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to delete hook");
+                return;
+            }
+            self.refresh_hooks();
+            self.settings.hook_list_index = self
+                .settings
+                .hook_list_index
+                .min(self.data.hooks.len().saturating_sub(1));
+        }
+    }
+
+    /// Reload hooks from the database
+    fn refresh_hooks(&mut self) {
+        if let Some(ref db) = self.db
+            && let Ok(hooks) = db.get_hooks()
+        {
+            self.data.hooks = hooks;
+        }
+    }
+
+    /// Run the hooks and queue the webhooks registered for an event, handing them the given session
+    fn emit_event(&mut self, event: HookEvent, session: Option<Session>) {
+        let wants_hooks = self.data.hooks.iter().any(|h| h.event == event);
+        let wants_webhooks = self.data.webhooks.iter().any(|w| w.wants(event));
+        if !wants_hooks && !wants_webhooks {
+            return;
+        }
+        let context = HookContext {
+            event,
+            timestamp: Timestamp::from_clock(&*self.clock),
+            phase: self.timer.phase,
+            elapsed_secs: self.timer.elapsed().as_secs() as i64,
+            session,
+        };
+        for hook in self.data.hooks.iter().filter(|h| h.event == event) {
+            self.hook_runner.run(hook, &context);
+        }
+        if wants_webhooks {
+            self.queue_webhooks(&context);
+        }
+    }
+
+    /// Report hooks that failed or timed out since the last tick
+    fn report_hook_failures(&mut self) {
+        for failure in self.hook_runner.failures() {
+            warn!("Hook `{}` {}", failure.command, failure.error);
+            self.notify(
+                NotificationLevel::Warning,
+                format!("Hook `{}` {}", failure.command, failure.error),
+            );
+        }
+    }
+
+    /// Handle webhook settings mode keys
+    fn handle_webhook_settings_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        if let Some(form) = &mut self.settings.webhook_form {
+            let events = HookEvent::ALL.len();
+            match (action, key.code) {
+                (Some(Action::Cancel), _) => self.settings.webhook_form = None,
+                (Some(Action::Confirm), _) => self.save_webhook(),
+                (Some(Action::NextField), _) => form.field = form.field.next(),
+                (Some(Action::Erase), _) => match form.field {
+                    WebhookField::Url => {
+                        form.url.pop();
+                    }
+                    WebhookField::Headers => {
+                        form.headers.pop();
+                    }
+                    WebhookField::Events => {}
+                },
+                (Some(Action::PrevChoice), _) if form.field == WebhookField::Events => {
+                    form.event_index = (form.event_index + events - 1) % events;
+                }
+                (Some(Action::NextChoice), _) if form.field == WebhookField::Events => {
+                    form.event_index = (form.event_index + 1) % events;
+                }
+                (Some(Action::Toggle), _) if form.field == WebhookField::Events => {
+                    let toggled = HookEvent::ALL[form.event_index];
+                    let selected = |e: &HookEvent| form.events.contains(e) != (*e == toggled);
+                    form.events = HookEvent::ALL.into_iter().filter(selected).collect();
+                }
+                (None, KeyCode::Char(c)) => match form.field {
+                    WebhookField::Url => form.url.push(c),
+                    WebhookField::Headers => form.headers.push(c),
+                    WebhookField::Events => {}
+                },
+                _ => {}
+            }
+            return;
+        }
+
+        let len = self.data.webhooks.len();
+        let index = self.settings.webhook_list_index;
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
+                self.modal = ModalState::None;
+            }
+            (Some(Action::SelectNext), _) if len > 0 => {
+                self.settings.webhook_list_index = (index + 1) % len;
+            }
+            (Some(Action::SelectPrev), _) if len > 0 => {
+                self.settings.webhook_list_index = (index + len - 1) % len;
+            }
+            (Some(Action::New), _) => {
+                self.settings.webhook_form = Some(WebhookForm::default());
+            }
+            (Some(Action::Edit | Action::Confirm), _) => {
+                if let Some(webhook) = self.data.webhooks.get(index) {
+                    let mut url = BoundedString::default();
+                    webhook.url.chars().for_each(|c| url.push(c));
+                    let mut headers = BoundedString::default();
+                    webhook.headers.chars().for_each(|c| headers.push(c));
+                    self.settings.webhook_form = Some(WebhookForm {
+                        id: webhook.id,
+                        url,
+                        headers,
+                        events: webhook.events.clone(),
+                        ..WebhookForm::default()
+                    });
+                }
+            }
+            (Some(Action::Delete), _) => {
+                self.delete_selected_webhook();
+            }
+            (Some(Action::Retry), _) => {
+                self.retry_failed_deliveries();
+            }
+            _ => {}
+        }
+    }
+
+    /// Create or update the webhook in the settings form
+    fn save_webhook(&mut self) {
+        let Some(form) = &self.settings.webhook_form else {
+            return;
+        };
+        if let Err(msg) = validate_webhook(form.url.as_ref(), form.headers.as_ref()) {
+            self.notify(NotificationLevel::Warning, msg);
+            return;
+        }
+        let webhook = Webhook {
+            id: form.id,
+            url: form.url.as_ref().trim().to_string(),
+            headers: form.headers.as_ref().trim().to_string(),
+            events: form.events.clone(),
+        };
+
+        if let Some(ref db) = self.db {
+            let result = match webhook.id {
+                Some(_) => db.update_webhook(&webhook).map(|_| ()),
+                None => db.create_webhook(&webhook).map(|_| ()),
+            };
+            if let Err(e) = result {
+                warn!("Failed to save webhook: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to save webhook");
+                return;
+            }
+            if webhook.id.is_none() {
+                self.settings.webhook_list_index = self.data.webhooks.len();
+            }
+            self.settings.webhook_form = None;
+            self.refresh_webhooks();
+        } else {
+            self.notify(NotificationLevel::Warning, "No database connection");
+        }
+    }
+
+    /// Delete the webhook selected in settings, dropping anything still queued for it
+    fn delete_selected_webhook(&mut self) {
+        let Some(id) = self
+            .data
+            .webhooks
+            .get(self.settings.webhook_list_index)
+            .and_then(|w| w.id)
+        else {
+            return;
+        };
+
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.delete_webhook(id) {
+                warn!("Failed to delete webhook: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to delete webhook");
+                return;
+            }
+            self.refresh_webhooks();
+            self.settings.webhook_list_index = self
+                .settings
+                .webhook_list_index
+                .min(self.data.webhooks.len().saturating_sub(1));
+        }
+    }
+
+    /// Reload webhooks from the database
+    fn refresh_webhooks(&mut self) {
+        if let Some(ref db) = self.db
+            && let Ok(webhooks) = db.get_webhooks()
+        {
+            self.data.webhooks = webhooks;
+            if let Ok(failed) = db.count_failed_deliveries() {
+                self.data.failed_deliveries = failed;
+            }
+        }
+    }
+
+    /// Give the selected webhook's failed deliveries another round of attempts
+    fn retry_failed_deliveries(&mut self) {
+        let Some(id) = self
+            .data
+            .webhooks
+            .get(self.settings.webhook_list_index)
+            .and_then(|w| w.id)
+        else {
+            return;
+        };
+        let now = Timestamp::from_clock(&*self.clock);
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.retry_failed_deliveries(id, now) {
+                warn!("Failed to retry webhook deliveries: {}", e);
+                self.notify(NotificationLevel::Warning, "Failed to retry deliveries");
+                return;
+            }
+            self.refresh_webhooks();
+            self.send_due_webhooks();
+        }
+    }
+
+    /// Queue an event for every webhook that wants it, then start sending
+    ///
+    /// Deliveries are stored before they are sent so nothing is lost if the
+    /// receiver is down or the app quits first.
+    fn queue_webhooks(&mut self, context: &HookContext) {
+        let Some(ref db) = self.db else {
+            return;
+        };
+        let payload = context.to_json();
+        for webhook in self.data.webhooks.iter().filter(|w| w.wants(context.event)) {
+            let Some(webhook_id) = webhook.id else {
+                continue;
+            };
+            let delivery = WebhookDelivery {
+                id: None,
+                webhook_id,
+                payload: payload.clone(),
+                attempts: 0,
+                next_attempt_at: context.timestamp,
+                last_error: None,
+            };
+            if let Err(e) = db.queue_delivery(&delivery) {
+                warn!("Failed to queue webhook for {}: {}", webhook.url, e);
+            }
+        }
+        self.send_due_webhooks();
+    }
+
+    /// Send queued deliveries whose next attempt is due
+    fn send_due_webhooks(&mut self) {
+        let now = Timestamp::from_clock(&*self.clock);
+        self.last_webhook_check_at = now;
+        let Some(ref db) = self.db else {
+            return;
+        };
+        let due = match db.get_due_deliveries(now) {
+            Ok(due) => due,
+            Err(e) => {
+                warn!("Failed to read webhook queue: {}", e);
+                return;
+            }
+        };
+        for delivery in due {
+            let Some(id) = delivery.id else {
+                continue;
+            };
+            if self.webhooks_in_flight.contains_key(&id) {
+                continue;
+            }
+            match self
+                .data
+                .webhooks
+                .iter()
+                .find(|w| w.id == Some(delivery.webhook_id))
+            {
+                Some(webhook) => {
+                    self.webhook_sender.send(id, webhook, &delivery.payload);
+                    self.webhooks_in_flight.insert(id, delivery);
                 }
-                KeyCode::Right | KeyCode::Char('l') => {
-                    self.settings.mode = SettingsMode::Categories;
-                    return;
+                None => {
+                    let _ = db.delete_delivery(id);
                 }
-                _ => {}
             }
         }
-
-        match self.settings.mode {
-            SettingsMode::Timer => self.handle_timer_settings_key(key),
-            SettingsMode::Categories => self.handle_category_settings_key(key),
-        }
     }
 
-    /// Handle timer settings mode keys
-    fn handle_timer_settings_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Esc => {
-                self.modal = ModalState::None;
-            }
-            KeyCode::Tab | KeyCode::Down => {
-                self.apply_editing_value();
-                self.settings.field = self.settings.field.next();
-                self.settings.editing_value = self.get_editing_field_value();
-            }
-            KeyCode::Up => {
-                self.apply_editing_value();
-                self.settings.field = self.settings.field.prev();
-                self.settings.editing_value = self.get_editing_field_value();
-            }
-            KeyCode::Enter => {
-                self.save_settings();
-                self.modal = ModalState::None;
-            }
-            KeyCode::Backspace => {
-                self.settings.editing_value.pop();
+    /// Remove sent deliveries from the queue and schedule retries for failed ones
+    fn process_webhook_results(&mut self) {
+        let now = Timestamp::from_clock(&*self.clock);
+        for result in self.webhook_sender.results() {
+            let Some(delivery) = self.webhooks_in_flight.remove(&result.id) else {
+                continue;
+            };
+            let Some(ref db) = self.db else {
+                continue;
+            };
+            let error = match result.result {
+                Ok(()) => {
+                    if let Err(e) = db.delete_delivery(result.id) {
+                        warn!("Failed to clear sent webhook: {}", e);
+                    }
+                    continue;
+                }
+                Err(error) => error,
+            };
+
+            let url = self
+                .data
+                .webhooks
+                .iter()
+                .find(|w| w.id == Some(delivery.webhook_id))
+                .map(|w| w.url.clone())
+                .unwrap_or_default();
+            let attempts = delivery.attempts + 1;
+            warn!(
+                "Webhook to {} failed (attempt {}): {}",
+                url, attempts, error
+            );
+
+            let retry = WebhookDelivery {
+                attempts,
+                next_attempt_at: now
+                    + DurationSecs::new(WebhookDelivery::retry_delay_secs(attempts)),
+                last_error: Some(error.to_string()),
+                ..delivery
+            };
+            if let Err(e) = db.reschedule_delivery(&retry) {
+                warn!("Failed to reschedule webhook: {}", e);
             }
-            KeyCode::Char(c) if c.is_ascii_digit() => {
-                self.settings.editing_value.push(c);
+            // Out of attempts, the delivery stays queued as failed until retried by hand
+            if attempts >= WebhookDelivery::MAX_ATTEMPTS {
+                self.refresh_webhooks();
+                self.notify(
+                    NotificationLevel::Error,
+                    format!(
+                        "Webhook to {} failed {} times; retry it in Settings > Webhooks",
+                        url, attempts
+                    ),
+                );
+            } else if attempts == 1 {
+                // Only the first failure is shown; retries are logged
+                self.notify(
+                    NotificationLevel::Warning,
+                    format!("Webhook to {} failed ({}), will retry", url, error),
+                );
             }
-            _ => {}
         }
     }
 
     /// Handle category settings mode keys
-    fn handle_category_settings_key(&mut self, key: KeyEvent) {
+    fn handle_category_settings_key(&mut self, action: Option<Action>, key: KeyEvent) {
         match self.settings.category_field {
-            CategoryField::List => self.handle_category_list_key(key),
-            CategoryField::Name | CategoryField::Color => self.handle_category_form_key(key),
+            CategoryField::List => self.handle_category_list_key(action, key),
+            CategoryField::Name
+            | CategoryField::Color
+            | CategoryField::WorkDuration
+            | CategoryField::ShortBreak
+            | CategoryField::LongBreak => self.handle_category_form_key(action, key),
         }
     }
 
     /// Handle keys when browsing the category list
-    fn handle_category_list_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Esc => {
+    fn handle_category_list_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
                 self.modal = ModalState::None;
             }
-            KeyCode::Char('j') | KeyCode::Down => {
+            (Some(Action::SelectNext), _) => {
                 let len = self.data.categories.len();
                 if len > 0 {
                     self.settings.category_list_index =
                         (self.settings.category_list_index + 1) % len;
                 }
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            (Some(Action::SelectPrev), _) => {
                 let len = self.data.categories.len();
                 if len > 0 {
                     if self.settings.category_list_index == 0 {
@@ -726,22 +2835,25 @@ impl App {
                     }
                 }
             }
-            KeyCode::Char('n') => {
+            (Some(Action::New), _) => {
                 // Start creating a new category
                 self.settings.category_field = CategoryField::Name;
                 self.settings.new_category_name.clear();
                 self.settings.new_category_color.clear();
+                self.settings.new_category_work.clear();
+                self.settings.new_category_short_break.clear();
+                self.settings.new_category_long_break.clear();
                 self.settings.editing_category_id = None;
                 // Pre-fill with a default color
                 for c in "#808080".chars() {
                     self.settings.new_category_color.push(c);
                 }
             }
-            KeyCode::Char('e') => {
+            (Some(Action::Edit | Action::Confirm), _) => {
                 // Edit selected category
                 self.start_editing_category();
             }
-            KeyCode::Char('d') => {
+            (Some(Action::Delete), _) => {
                 self.delete_selected_category();
             }
             _ => {}
@@ -749,33 +2861,35 @@ impl App {
     }
 
     /// Handle keys when editing the new category form
-    fn handle_category_form_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Esc => {
+    fn handle_category_form_key(&mut self, action: Option<Action>, key: KeyEvent) {
+        match (action, key.code) {
+            (Some(Action::Cancel), _) => {
                 // Cancel and return to list
                 self.settings.category_field = CategoryField::List;
             }
-            KeyCode::Tab => {
-                // Cycle between name and color fields
-                self.settings.category_field = match self.settings.category_field {
-                    CategoryField::Name => CategoryField::Color,
-                    CategoryField::Color => CategoryField::Name,
-                    CategoryField::List => CategoryField::List,
-                };
+            (Some(Action::NextField), _) => {
+                self.settings.category_field = self.settings.category_field.next();
             }
-            KeyCode::Enter => {
+            (Some(Action::Confirm), _) => {
                 self.save_category();
             }
-            KeyCode::Backspace => match self.settings.category_field {
+            (Some(Action::Erase), _) => match self.settings.category_field {
                 CategoryField::Name => {
                     self.settings.new_category_name.pop();
                 }
                 CategoryField::Color => {
                     self.settings.new_category_color.pop();
                 }
+                CategoryField::WorkDuration
+                | CategoryField::ShortBreak
+                | CategoryField::LongBreak => {
+                    if let Some(minutes) = self.settings.category_minutes_mut() {
+                        minutes.pop();
+                    }
+                }
                 CategoryField::List => {}
             },
-            KeyCode::Char(c) => match self.settings.category_field {
+            (None, KeyCode::Char(c)) => match self.settings.category_field {
                 CategoryField::Name => {
                     self.settings.new_category_name.push(c);
                 }
@@ -785,6 +2899,15 @@ impl App {
                         self.settings.new_category_color.push(c);
                     }
                 }
+                CategoryField::WorkDuration
+                | CategoryField::ShortBreak
+                | CategoryField::LongBreak => {
+                    if let Some(minutes) = self.settings.category_minutes_mut()
+                        && c.is_ascii_digit()
+                    {
+                        minutes.push(c);
+                    }
+                }
                 CategoryField::List => {}
             },
             _ => {}
@@ -812,6 +2935,11 @@ impl App {
             self.settings.new_category_color.push(c);
         }
 
+        let minutes = |secs: Option<i64>| secs.map(|s| (s / 60).to_string()).unwrap_or_default();
+        self.settings.new_category_work = minutes(category.durations.work_duration_secs);
+        self.settings.new_category_short_break = minutes(category.durations.short_break_secs);
+        self.settings.new_category_long_break = minutes(category.durations.long_break_secs);
+
         self.settings.editing_category_id = category.id;
         self.settings.category_field = CategoryField::Name;
     }
@@ -824,6 +2952,22 @@ impl App {
         // Parse color (use default if invalid)
         let color = crate::models::parse_hex_color(&color_str);
 
+        let durations = match (
+            parse_override_minutes(&self.settings.new_category_work),
+            parse_override_minutes(&self.settings.new_category_short_break),
+            parse_override_minutes(&self.settings.new_category_long_break),
+        ) {
+            (Ok(work), Ok(short_break), Ok(long_break)) => DurationOverrides {
+                work_duration_secs: work,
+                short_break_secs: short_break,
+                long_break_secs: long_break,
+            },
+            (Err(msg), _, _) | (_, Err(msg), _) | (_, _, Err(msg)) => {
+                self.notify(NotificationLevel::Warning, msg);
+                return;
+            }
+        };
+
         if let Some(ref db) = self.db {
             let result = if let Some(id) = self.settings.editing_category_id {
                 // Validate for update - find current name to allow keeping it
@@ -841,9 +2985,7 @@ impl App {
                     return;
                 }
                 // Update existing category
-                db.update_category(id, &name, color)
-                    .map(|_| ())
-                    .map_err(|e| e.to_string())
+                db.update_category(id, &name, color).map(|_| id)
             } else {
                 // Validate for create
                 if let Err(msg) = validate_new_category_name(&name, &self.data.categories) {
@@ -852,13 +2994,13 @@ impl App {
                 }
                 // Create new category
                 db.create_category(&name, color)
-                    .map(|_| ())
-                    .map_err(|e| e.to_string())
-            };
+            }
+            .and_then(|id| db.set_category_durations(id, &durations));
 
             match result {
-                Ok(()) => {
+                Ok(_) => {
                     self.refresh_categories();
+                    self.apply_timer_config();
                     self.settings.category_field = CategoryField::List;
                     self.settings.editing_category_id = None;
                 }
@@ -952,13 +3094,61 @@ impl App {
                 .editing_config
                 .sessions_until_long_break
                 .to_string(),
+            SettingsField::FocusPause => self.settings.editing_config.focus_pause_secs.to_string(),
+            SettingsField::ExtendBy => (self.settings.editing_config.extend_secs / 60).to_string(),
+            SettingsField::FlowtimeRatio => self
+                .settings
+                .editing_config
+                .flowtime_break_ratio
+                .to_string(),
+            SettingsField::FlowtimeMinBreak => {
+                (self.settings.editing_config.flowtime_min_break_secs / 60).to_string()
+            }
+            SettingsField::FlowtimeMaxBreak => {
+                (self.settings.editing_config.flowtime_max_break_secs / 60).to_string()
+            }
+            SettingsField::FirstWarning => {
+                self.settings.editing_config.first_warning_secs.to_string()
+            }
+            SettingsField::SecondWarning => {
+                self.settings.editing_config.second_warning_secs.to_string()
+            }
+            SettingsField::AlertBells(phase) => self
+                .settings
+                .editing_config
+                .alert_for(phase)
+                .bells
+                .to_string(),
+            SettingsField::QuietStart => self
+                .settings
+                .editing_config
+                .quiet_hours
+                .start_hour
+                .to_string(),
+            SettingsField::QuietEnd => self
+                .settings
+                .editing_config
+                .quiet_hours
+                .end_hour
+                .to_string(),
+            // Toggles are flipped in place rather than typed
+            SettingsField::AutoStartBreaks
+            | SettingsField::AutoStartWork
+            | SettingsField::Overtime
+            | SettingsField::TimerMode
+            | SettingsField::AlertFlash(_)
+            | SettingsField::AlertGauge(_)
+            | SettingsField::QuietHours
+            | SettingsField::DesktopNotification
+            | SettingsField::WindowTitle
+            | SettingsField::Theme => String::new(),
         }
     }
 
     /// Apply the current editing value to editing_config
     fn apply_editing_value(&mut self) {
         if let Ok(value) = self.settings.editing_value.parse::<i64>()
-            && value > 0
+            && (value > 0 || (value == 0 && self.settings.field.allows_zero()))
         {
             match self.settings.field {
                 SettingsField::WorkDuration => {
@@ -973,6 +3163,46 @@ impl App {
                 SettingsField::SessionsUntilLong => {
                     self.settings.editing_config.sessions_until_long_break = value;
                 }
+                SettingsField::ExtendBy => {
+                    self.settings.editing_config.extend_secs = value * 60;
+                }
+                SettingsField::FlowtimeRatio => {
+                    self.settings.editing_config.flowtime_break_ratio = value;
+                }
+                SettingsField::FlowtimeMinBreak => {
+                    self.settings.editing_config.flowtime_min_break_secs = value * 60;
+                }
+                SettingsField::FlowtimeMaxBreak => {
+                    self.settings.editing_config.flowtime_max_break_secs = value * 60;
+                }
+                SettingsField::FocusPause => {
+                    self.settings.editing_config.focus_pause_secs = value;
+                }
+                SettingsField::FirstWarning => {
+                    self.settings.editing_config.first_warning_secs = value;
+                }
+                SettingsField::SecondWarning => {
+                    self.settings.editing_config.second_warning_secs = value;
+                }
+                SettingsField::AlertBells(phase) => {
+                    self.settings.editing_config.alert_for_mut(phase).bells = value;
+                }
+                SettingsField::QuietStart => {
+                    self.settings.editing_config.quiet_hours.start_hour = value;
+                }
+                SettingsField::QuietEnd => {
+                    self.settings.editing_config.quiet_hours.end_hour = value;
+                }
+                SettingsField::AutoStartBreaks
+                | SettingsField::AutoStartWork
+                | SettingsField::Overtime
+                | SettingsField::TimerMode
+                | SettingsField::AlertFlash(_)
+                | SettingsField::AlertGauge(_)
+                | SettingsField::QuietHours
+                | SettingsField::DesktopNotification
+                | SettingsField::WindowTitle
+                | SettingsField::Theme => {}
             }
         }
     }
@@ -991,11 +3221,25 @@ impl App {
             return;
         }
 
+        let mode_changed = self.settings.editing_config.timer_mode != self.data.config.timer_mode;
+        if mode_changed && (self.timer.is_running() || self.timer.is_paused()) {
+            self.notify(
+                NotificationLevel::Warning,
+                "Stop the timer before switching timer mode",
+            );
+            return;
+        }
+        if mode_changed {
+            // Durations of the pending phase change meaning with the mode
+            self.timer.skip_break();
+        }
+
         // Commit editing_config to config
         self.data.config = self.settings.editing_config.clone();
 
         // Apply to timer
-        self.timer.apply_config(&self.data.config);
+        self.apply_timer_config();
+        self.apply_theme();
 
         // Save to database
         if let Some(ref db) = self.db
@@ -1004,43 +3248,570 @@ impl App {
             warn!("Failed to save config: {}", e);
             self.notify(NotificationLevel::Warning, "Failed to save settings");
         }
+
+        // The active profile now holds the edited durations
+        self.refresh_profiles();
     }
 
     /// Handle a timer tick
     fn handle_tick(&mut self) {
+        if self.timer.is_running()
+            && matches!(self.session_phase, SessionPhase::Active { .. })
+            && Timestamp::from_clock(&*self.clock) - self.last_checkpoint_at
+                >= DurationSecs::new(CHECKPOINT_INTERVAL_SECS)
+        {
+            self.checkpoint_session();
+        }
+
+        // Keep ringing until a finished phase is acknowledged
+        let now = Timestamp::from_clock(&*self.clock);
+        if self.timer.is_awaiting_ack()
+            && now - self.last_alert_at >= DurationSecs::new(ACK_ALERT_INTERVAL_SECS)
+        {
+            self.ring_bells(1);
+        }
+
+        self.check_warnings();
+        self.check_focus_pause();
+        self.update_window_title();
+        self.report_hook_failures();
+        self.process_webhook_results();
+        if now - self.last_webhook_check_at >= DurationSecs::new(WEBHOOK_RETRY_INTERVAL_SECS) {
+            self.send_due_webhooks();
+        }
+
+        // Alert once as a work period runs past zero into overtime
+        let in_overtime = self.timer.in_overtime();
+        if in_overtime && !self.overtime_alerted {
+            self.alert_phase_end();
+        }
+        self.overtime_alerted = in_overtime;
+
         if self.timer.is_running() && self.timer.is_finished() {
-            // Phase completed - ring terminal bell
-            print!("\x07");
-            let _ = std::io::Write::flush(&mut std::io::stdout());
+            self.alert_phase_end();
 
-            if self.timer.phase == crate::timer::TimerPhase::Work {
+            if self.timer.phase == TimerPhase::Work {
                 // Save the completed work session
                 self.complete_session();
+            } else {
+                self.record_break(false);
             }
             self.timer.advance_phase();
+            self.begin_next_phase();
+        }
+    }
+
+    /// Start the phase the timer advanced to, or hold it until acknowledged
+    fn begin_next_phase(&mut self) {
+        // Durations changed while the last phase ran, such as by a new session's category
+        self.apply_timer_config();
+        let auto_start = if self.timer.phase.is_break() {
+            self.data.config.auto_start_breaks
+        } else {
+            self.data.config.auto_start_work
+        };
+        if auto_start {
+            self.start_next_phase();
+        } else {
+            self.timer.await_ack();
+        }
+    }
+
+    /// Add the configured extension to the current phase, if it is underway
+    ///
+    /// While the next phase awaits acknowledgement, the phase that just ended is snoozed
+    /// instead.
+    fn extend_phase(&mut self) {
+        let by = std::time::Duration::from_secs(self.data.config.extend_secs as u64);
+        if self.timer.is_awaiting_ack() {
+            self.snooze_phase(by);
+        } else {
+            self.timer.extend(by);
+        }
+    }
+
+    /// Resume the phase that just ended for `by` more, taking back what its end saved
+    ///
+    /// The session or break is saved again when the snoozed phase ends, so it is recorded
+    /// once, with the extra time.
+    fn snooze_phase(&mut self, by: std::time::Duration) {
+        let Some(ended) = self.timer.ended_phase() else {
+            return;
+        };
+        let deleted = match (ended, self.ended_record.take()) {
+            (TimerPhase::Work, Some(EndedRecord::Session(id))) => {
+                let SessionPhase::Ready(session) = &self.session_phase else {
+                    return;
+                };
+                let deleted = self.db.as_ref().map(|db| db.delete_session(id));
+                if matches!(deleted, Some(Ok(_))) {
+                    let mut session = session.clone();
+                    session.id = None;
+                    self.session_phase = SessionPhase::Active {
+                        start_time: session.started_at,
+                        session,
+                        segment_start: Some(Timestamp::from_clock(&*self.clock)),
+                    };
+                }
+                deleted
+            }
+            (
+                TimerPhase::ShortBreak | TimerPhase::LongBreak,
+                Some(EndedRecord::Break { id, started_at }),
+            ) => {
+                let deleted = self.db.as_ref().map(|db| db.delete_break(id));
+                if matches!(deleted, Some(Ok(_))) {
+                    self.break_started_at = Some(started_at);
+                }
+                deleted
+            }
+            _ => return,
+        };
+        if let Some(Err(e)) = deleted {
+            warn!("Failed to take back the ended phase: {}", e);
+            self.notify(
+                NotificationLevel::Warning,
+                "Failed to extend the ended phase",
+            );
+            return;
+        }
+
+        self.timer.snooze(by);
+        self.checkpoint_session();
+        self.refresh_data();
+    }
+
+    /// Finish a work period that ran into overtime and move on to the break
+    fn finish_overtime(&mut self) {
+        self.complete_session();
+        self.timer.advance_phase();
+        self.begin_next_phase();
+    }
+
+    /// End a Flowtime work period at a natural stopping point and move to its break
+    fn finish_flow(&mut self) {
+        if self.timer.elapsed().as_secs() == 0 {
+            self.timer.reset();
+            return;
+        }
+        self.complete_session();
+        self.timer.finish_flow();
+        self.begin_next_phase();
+    }
 
-            // Auto-start the next phase
+    /// Switch the timer view between the big clock and the regular one
+    fn toggle_big_clock(&mut self) {
+        self.data.config.big_clock = !self.data.config.big_clock;
+        if let Some(ref db) = self.db
+            && let Err(e) = db.save_config(&self.data.config)
+        {
+            warn!("Failed to save clock size: {}", e);
+        }
+    }
+
+    /// Switch between Pomodoro and Flowtime from the timer view
+    fn toggle_timer_mode(&mut self) {
+        if self.timer.is_running() || self.timer.is_paused() {
+            self.notify(
+                NotificationLevel::Warning,
+                "Stop the timer before switching timer mode",
+            );
+            return;
+        }
+
+        self.data.config.timer_mode = self.data.config.timer_mode.toggle();
+        self.apply_timer_config();
+        self.timer.skip_break();
+
+        if let Some(ref db) = self.db
+            && let Err(e) = db.save_config(&self.data.config)
+        {
+            warn!("Failed to save timer mode: {}", e);
+            self.notify(NotificationLevel::Warning, "Failed to switch timer mode");
+        }
+    }
+
+    /// Warn once as the running phase passes each configured warning point
+    ///
+    /// A warning is re-armed when the remaining time rises above its point again,
+    /// as it does when the next phase starts or the current one is extended.
+    fn check_warnings(&mut self) {
+        let remaining = self.timer.remaining().as_secs() as i64;
+        let duration = self.timer.current_phase_duration().as_secs() as i64;
+        let points = [
+            self.data.config.first_warning_secs,
+            self.data.config.second_warning_secs,
+        ];
+        for (i, secs) in points.into_iter().enumerate() {
+            let passed = secs > 0 && remaining <= secs;
+            let due = passed
+                && !self.warnings_given[i]
+                && self.timer.is_running()
+                && !self.timer.counts_up()
+                && remaining > 0
+                && secs < duration;
+            self.warnings_given[i] = passed;
+            if due {
+                self.ring_bells(1);
+                let left = if remaining >= 60 {
+                    format!("{}m", (remaining + 59) / 60)
+                } else {
+                    format!("{}s", remaining)
+                };
+                let message = format!("{} left in {}", left, self.timer.phase_label());
+                self.notify(NotificationLevel::Warning, message);
+            }
+        }
+    }
+
+    /// Announce the end of the current phase with its alert pattern
+    fn alert_phase_end(&mut self) {
+        let alert = self.data.config.alert_for(self.timer.phase);
+        self.ring_bells(alert.bells);
+        if alert.flash {
+            self.flash_until =
+                Timestamp::from_clock(&*self.clock) + DurationSecs::new(ALERT_FLASH_SECS);
+        }
+        let body = format!("Time's up: {}", self.timer.phase_label());
+        let _ = terminal::write_notification(
+            &mut self.term_out,
+            self.data.config.desktop_notification,
+            "Grindstone",
+            &body,
+        );
+    }
+
+    /// Ring the terminal bell, staying silent during quiet hours
+    fn ring_bells(&mut self, count: i64) {
+        self.last_alert_at = Timestamp::from_clock(&*self.clock);
+        if count <= 0 || self.is_quiet_time() {
+            return;
+        }
+        let _ = terminal::write_bells(&mut self.term_out, count as usize);
+    }
+
+    /// Start the phase the timer has just advanced to
+    ///
+    /// Work periods go through `start_timer` so the session is recorded again.
+    fn start_next_phase(&mut self) {
+        if self.timer.phase.is_break() {
             self.timer.start();
+            self.break_started_at = Some(Timestamp::from_clock(&*self.clock));
+            self.emit_event(HookEvent::BreakStarted, self.current_session().cloned());
+        } else {
+            self.start_timer();
         }
     }
 
+    /// Save the current break phase, ending now
+    ///
+    /// A break skipped before it ran, such as one still awaiting acknowledgement, is not a
+    /// break taken and leaves no record.
+    fn record_break(&mut self, skipped: bool) {
+        let kind = match self.timer.phase {
+            TimerPhase::LongBreak => BreakKind::Long,
+            TimerPhase::ShortBreak => BreakKind::Short,
+            TimerPhase::Work => return,
+        };
+        let ended_at = Timestamp::from_clock(&*self.clock);
+        let Some(started_at) = self.break_started_at.take().filter(|&at| at < ended_at) else {
+            return;
+        };
+        let period = BreakPeriod {
+            kind,
+            started_at,
+            ended_at,
+            skipped,
+        };
+
+        if let Some(ref db) = self.db {
+            match db.save_break(&period) {
+                Ok(id) if !skipped => {
+                    self.ended_record = Some(EndedRecord::Break { id, started_at })
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("Failed to save break: {}", e);
+                    self.notify(NotificationLevel::Warning, "Failed to save break");
+                }
+            }
+        }
+        self.emit_event(HookEvent::BreakEnded, self.current_session().cloned());
+    }
+
     /// Start the timer
     fn start_timer(&mut self) {
         let phase = std::mem::take(&mut self.session_phase);
+        let now = Timestamp::from_clock(&*self.clock);
+        let profile = self.active_profile().map(|p| p.name.clone());
 
         self.session_phase = match phase {
-            SessionPhase::Ready(session) => SessionPhase::Active {
-                session,
-                start_time: Timestamp::from_clock(&*self.clock),
-            },
-            SessionPhase::Active { session, .. } => SessionPhase::Active {
-                session,
-                start_time: Timestamp::from_clock(&*self.clock),
-            },
+            SessionPhase::Ready(mut session) | SessionPhase::Active { mut session, .. } => {
+                session.segments.clear();
+                session.interruptions.clear();
+                session.profile = profile;
+                session.mode = self.timer.mode;
+                SessionPhase::Active {
+                    session,
+                    start_time: now,
+                    segment_start: Some(now),
+                }
+            }
             SessionPhase::Inactive => SessionPhase::Inactive,
         };
 
-        self.timer.start();
+        self.timer.start();
+        self.checkpoint_session();
+        if matches!(self.session_phase, SessionPhase::Active { .. }) {
+            self.emit_event(HookEvent::WorkStarted, self.current_session().cloned());
+        }
+    }
+
+    /// Pause the timer, closing the active session's running segment
+    fn pause_timer(&mut self) {
+        self.pause_timer_since(Timestamp::from_clock(&*self.clock));
+    }
+
+    /// Pause the timer as of an earlier moment, so the time since counts as paused
+    fn pause_timer_since(&mut self, since: Timestamp) {
+        // Never reach back past the start of the running segment
+        let since = match &self.session_phase {
+            SessionPhase::Active {
+                segment_start: Some(started_at),
+                ..
+            } => since.max(*started_at),
+            _ => since,
+        };
+        let now = Timestamp::from_clock(&*self.clock);
+        let rewind = (now - since).as_secs().max(0) as u64;
+        self.timer.pause();
+        self.timer
+            .rewind_pause(std::time::Duration::from_secs(rewind));
+        Self::close_segment(&mut self.session_phase, since);
+        self.checkpoint_session();
+        self.emit_event(HookEvent::Paused, self.current_session().cloned());
+    }
+
+    /// Note when the terminal loses focus, for pausing work left running
+    fn handle_focus_change(&mut self, focused: bool) {
+        self.focus_lost_at = if focused {
+            None
+        } else {
+            Some(Timestamp::from_clock(&*self.clock))
+        };
+    }
+
+    /// Pause work that has run on while the terminal was unfocused past the threshold
+    fn check_focus_pause(&mut self) {
+        let threshold = self.data.config.focus_pause_secs;
+        let Some(lost_at) = self.focus_lost_at else {
+            return;
+        };
+        if threshold <= 0
+            || !self.timer.is_running()
+            || self.timer.phase.is_break()
+            || Timestamp::from_clock(&*self.clock) - lost_at < DurationSecs::new(threshold)
+        {
+            return;
+        }
+        self.pause_timer_since(lost_at);
+        self.focus_lost_at = None;
+        let since = lost_at.to_datetime().format("%H:%M");
+        self.notify(
+            NotificationLevel::Warning,
+            format!("Paused while away since {}", since),
+        );
+    }
+
+    /// Handle a click or wheel scroll on the regions drawn in the last frame
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        // Like a key, a click anywhere closes the help overlay
+        if self.help_open {
+            if matches!(mouse.kind, MouseEventKind::Down(_)) {
+                self.help_open = false;
+            }
+            return;
+        }
+        let target = self.hits.target_at(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollDown if target.is_some_and(ClickTarget::scrolls) => {
+                self.handle_key_event(KeyEvent::from(KeyCode::Down));
+            }
+            MouseEventKind::ScrollUp if target.is_some_and(ClickTarget::scrolls) => {
+                self.handle_key_event(KeyEvent::from(KeyCode::Up));
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(target) = target else {
+                    return;
+                };
+                let now = self.clock.instant();
+                let double = matches!(
+                    self.last_click,
+                    Some((at, last)) if last == target && now.duration_since(at) < DOUBLE_CLICK
+                );
+                // A third click starts a new pair rather than making another double-click
+                self.last_click = if double { None } else { Some((now, target)) };
+                self.handle_click(target, double);
+            }
+            _ => {}
+        }
+    }
+
+    /// Act on a click, which mostly does what the matching key would
+    fn handle_click(&mut self, target: ClickTarget, double: bool) {
+        match target {
+            ClickTarget::Key(key) => self.handle_key_event(KeyEvent::from(key)),
+            ClickTarget::List => {}
+            ClickTarget::HistoryRow(index) => {
+                self.notification = None;
+                self.data.history_state.select(Some(index));
+                if double {
+                    self.handle_key_event(KeyEvent::from(KeyCode::Enter));
+                }
+            }
+            ClickTarget::StatsPeriod(period) => {
+                self.notification = None;
+                self.data.stats_period = period;
+                self.refresh_data();
+            }
+            ClickTarget::ChartType(chart) => {
+                self.notification = None;
+                self.data.chart_type = chart;
+            }
+            ClickTarget::InputField(field, key) => {
+                self.input.field = field;
+                if let Some(code) = key {
+                    self.handle_key_event(KeyEvent::from(code));
+                }
+            }
+            ClickTarget::PaletteCommand(index) => {
+                if let Some(&command) = self.palette.matches().get(index) {
+                    self.run_command(command);
+                }
+            }
+            ClickTarget::SettingsCategory(index) => {
+                self.notification = None;
+                self.settings.category_list_index = index;
+                if double {
+                    self.start_editing_category();
+                }
+            }
+        }
+    }
+
+    /// Type pasted text into the focused text field, if there is one
+    fn handle_paste(&mut self, text: &str) {
+        if !self.text_field_focused() {
+            return;
+        }
+        // Fields are single-line, so line breaks and tabs become spaces. Each character goes
+        // straight to the field, which drops any it does not accept, rather than being
+        // looked up as a key.
+        let text = text.trim_end_matches(['\r', '\n']);
+        for c in text.chars() {
+            let c = if c.is_control() { ' ' } else { c };
+            self.handle_modal_key(None, KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    /// Whether typed characters go into a text field rather than acting as commands
+    fn text_field_focused(&self) -> bool {
+        match self.modal {
+            ModalState::Input => {
+                matches!(self.input.field, InputField::Name | InputField::Description)
+            }
+            ModalState::TaskInput => self.task_input.field != TaskField::Category,
+            ModalState::Interruption | ModalState::Palette => true,
+            ModalState::Settings => match self.settings.mode {
+                SettingsMode::Timer | SettingsMode::Alerts => !self.settings.field.is_toggle(),
+                SettingsMode::Profiles => self.settings.new_profile_name.is_some(),
+                SettingsMode::Cycle => self
+                    .settings
+                    .step_form
+                    .as_ref()
+                    .is_some_and(|f| f.field != StepField::Kind),
+                SettingsMode::Hooks => self
+                    .settings
+                    .hook_form
+                    .as_ref()
+                    .is_some_and(|f| f.field != HookField::Event),
+                SettingsMode::Webhooks => self
+                    .settings
+                    .webhook_form
+                    .as_ref()
+                    .is_some_and(|f| f.field != WebhookField::Events),
+                SettingsMode::Categories => self.settings.category_field != CategoryField::List,
+            },
+            ModalState::None | ModalState::Detail => false,
+        }
+    }
+
+    /// Resume a paused timer, opening a new running segment for the active session
+    fn resume_timer(&mut self) {
+        self.timer.start();
+        if let SessionPhase::Active { segment_start, .. } = &mut self.session_phase
+            && segment_start.is_none()
+        {
+            *segment_start = Some(Timestamp::from_clock(&*self.clock));
+        }
+        self.checkpoint_session();
+        self.emit_event(HookEvent::Resumed, self.current_session().cloned());
+    }
+
+    /// Open the interruption modal, stamping the interruption with the current time
+    fn open_interruption_modal(&mut self, kind: InterruptionKind) {
+        self.interruption.kind = kind;
+        self.interruption.note.clear();
+        self.interruption.occurred_at = Timestamp::from_clock(&*self.clock);
+        self.modal = ModalState::Interruption;
+    }
+
+    /// Attach the interruption being edited to the active session
+    fn log_interruption(&mut self) {
+        let note = if self.interruption.note.is_empty() {
+            None
+        } else {
+            Some(self.interruption.note.to_string())
+        };
+
+        if let SessionPhase::Active { session, .. } = &mut self.session_phase {
+            session.interruptions.push(Interruption {
+                kind: self.interruption.kind,
+                note,
+                occurred_at: self.interruption.occurred_at,
+            });
+        }
+    }
+
+    /// Record the open running segment of an active session as ending at `now`
+    fn close_segment(phase: &mut SessionPhase, now: Timestamp) {
+        if let SessionPhase::Active {
+            session,
+            segment_start,
+            ..
+        } = phase
+            && let Some(started_at) = segment_start.take()
+        {
+            session.segments.push(SessionSegment {
+                started_at,
+                ended_at: now,
+            });
+        }
+    }
+
+    /// Apply the timer settings, overridden by the current session's category durations
+    fn apply_timer_config(&mut self) {
+        let mut config = self.data.config.clone();
+        if let Some(session) = self.current_session()
+            && let Some(category) = self
+                .data
+                .categories
+                .iter()
+                .find(|c| c.name == session.category)
+        {
+            category.durations.apply_to(&mut config);
+        }
+        self.timer.apply_config(&config);
     }
 
     /// Create a new session from input
@@ -1053,6 +3824,10 @@ impl App {
         } else {
             Some(self.input.description.to_string())
         };
+        let task_id = self
+            .input
+            .selected_task
+            .and_then(|i| self.data.open_tasks().get(i).and_then(|t| t.id));
 
         let session = Session::builder()
             .name(self.input.name.to_string())
@@ -1061,35 +3836,41 @@ impl App {
             .started_at(Timestamp::new(0))
             .ended_at(Timestamp::new(0))
             .duration_secs(DurationSecs::new(0))
+            .task_id(task_id)
             .build()
             .expect("session fields validated by UI");
 
         self.session_phase = SessionPhase::Ready(session);
+
+        // A phase underway keeps its length; the category's durations apply from the next
+        if !self.timer.is_running() && !self.timer.is_paused() {
+            self.apply_timer_config();
+        }
     }
 
     /// Complete the current session and save to database
     fn complete_session(&mut self) {
+        let end_time = Timestamp::from_clock(&*self.clock);
+        Self::close_segment(&mut self.session_phase, end_time);
         let phase = std::mem::take(&mut self.session_phase);
 
         self.session_phase = match phase {
             SessionPhase::Active {
                 mut session,
                 start_time,
+                ..
             } => {
-                let end_time = Timestamp::from_clock(&*self.clock);
-                session.started_at = start_time;
-                session.ended_at = end_time;
-                // Use configured work duration, not wall-clock time
-                session.duration_secs =
-                    DurationSecs::new(self.timer.work_duration.as_secs() as i64);
-
-                if let Some(ref db) = self.db
-                    && let Err(e) = db.save_session(&session)
-                {
-                    error!("Failed to save session: {}", e);
-                    self.notify(NotificationLevel::Error, "Failed to save session!");
+                self.ended_record = self
+                    .save_finished_session(
+                        &mut session,
+                        start_time,
+                        end_time,
+                        SessionOutcome::Completed,
+                    )
+                    .map(EndedRecord::Session);
+                if session.task_id.is_some() {
+                    self.refresh_data();
                 }
-
                 SessionPhase::Ready(session)
             }
             other => other,
@@ -1098,39 +3879,125 @@ impl App {
 
     /// Stop the current session early and save actual elapsed time
     fn stop_session(&mut self) {
+        if self.end_session(SessionOutcome::Stopped) {
+            self.timer.reset();
+        }
+    }
+
+    /// Save a session still running at quit as abandoned
+    fn abandon_session(&mut self) {
+        self.end_session(SessionOutcome::Abandoned);
+    }
+
+    /// End the active session with the given outcome, returning whether it was saved
+    ///
+    /// Sessions that never accumulated any running time are left untouched.
+    fn end_session(&mut self, outcome: SessionOutcome) -> bool {
         if !matches!(self.session_phase, SessionPhase::Active { .. }) {
-            return;
+            return false;
         }
 
-        let elapsed_secs = self.timer.elapsed().as_secs() as i64;
-        if elapsed_secs == 0 {
-            return;
+        if self.timer.elapsed().as_secs() == 0 {
+            return false;
         }
 
+        let end_time = Timestamp::from_clock(&*self.clock);
+        Self::close_segment(&mut self.session_phase, end_time);
         let phase = std::mem::take(&mut self.session_phase);
 
-        self.session_phase = match phase {
-            SessionPhase::Active {
-                mut session,
-                start_time,
-            } => {
-                let end_time = Timestamp::from_clock(&*self.clock);
-                session.started_at = start_time;
-                session.ended_at = end_time;
-                session.duration_secs = DurationSecs::new(elapsed_secs);
+        if let SessionPhase::Active {
+            mut session,
+            start_time,
+            ..
+        } = phase
+        {
+            self.save_finished_session(&mut session, start_time, end_time, outcome);
+        }
+        true
+    }
 
-                if let Some(ref db) = self.db
-                    && let Err(e) = db.save_session(&session)
-                {
+    /// Fill in the timing and outcome of a finished session and save it, returning its id
+    fn save_finished_session(
+        &mut self,
+        session: &mut Session,
+        start_time: Timestamp,
+        end_time: Timestamp,
+        outcome: SessionOutcome,
+    ) -> Option<SessionId> {
+        session.started_at = start_time;
+        session.ended_at = end_time;
+        session.duration_secs = DurationSecs::new(self.timer.elapsed().as_secs() as i64);
+        session.planned_secs = self.planned_secs(session);
+        session.overtime_secs = DurationSecs::new(self.timer.overtime_elapsed().as_secs() as i64);
+        session.outcome = outcome;
+
+        // The checkpoint is kept for recovery unless the session is safely saved
+        let mut saved = None;
+        if let Some(ref db) = self.db {
+            match db.save_session(session) {
+                Ok(id) => {
+                    saved = Some(id);
+                    if let Err(e) = db.clear_checkpoint() {
+                        warn!("Failed to clear session checkpoint: {}", e);
+                    }
+                }
+                Err(e) => {
                     error!("Failed to save session: {}", e);
                     self.notify(NotificationLevel::Error, "Failed to save session!");
                 }
+            }
+        }
 
-                self.timer.reset();
-                SessionPhase::Inactive
+        let event = match outcome {
+            SessionOutcome::Completed => HookEvent::WorkCompleted,
+            _ => HookEvent::WorkStopped,
+        };
+        self.emit_event(event, Some(session.clone()));
+        saved
+    }
+
+    /// Work duration the session was set to run for, zero for open-ended Flowtime work
+    fn planned_secs(&self, session: &Session) -> DurationSecs {
+        match session.mode {
+            TimerMode::Pomodoro => {
+                DurationSecs::new(self.timer.planned_phase_duration().as_secs() as i64)
             }
-            other => other,
+            TimerMode::Flowtime => DurationSecs::default(),
+        }
+    }
+
+    /// Persist a snapshot of the active session so it can be recovered after a crash
+    fn checkpoint_session(&mut self) {
+        let now = Timestamp::from_clock(&*self.clock);
+        self.last_checkpoint_at = now;
+
+        let SessionPhase::Active {
+            session,
+            start_time,
+            segment_start,
+        } = &self.session_phase
+        else {
+            return;
         };
+
+        let mut snapshot = session.clone();
+        if let Some(started_at) = *segment_start {
+            snapshot.segments.push(SessionSegment {
+                started_at,
+                ended_at: now,
+            });
+        }
+        snapshot.started_at = *start_time;
+        snapshot.ended_at = now;
+        snapshot.duration_secs = DurationSecs::new(self.timer.elapsed().as_secs() as i64);
+        snapshot.planned_secs = self.planned_secs(&snapshot);
+        snapshot.overtime_secs = DurationSecs::new(self.timer.overtime_elapsed().as_secs() as i64);
+
+        if let Some(ref db) = self.db
+            && let Err(e) = db.save_checkpoint(&snapshot)
+        {
+            warn!("Failed to checkpoint session: {}", e);
+        }
     }
 
     /// Refresh data from database
@@ -1142,12 +4009,38 @@ impl App {
             if let Ok(sessions) = db.get_sessions_in_range(thirty_days_ago, now) {
                 self.data.sessions = sessions;
             }
+            if let Ok(breaks) = db.get_breaks_in_range(thirty_days_ago, now) {
+                self.data.breaks = breaks;
+            }
 
             // Load category stats for current period
             let (start, end) = self.data.stats_period.time_range_with_clock(&*self.clock);
             if let Ok(stats) = db.get_time_by_category(start, end) {
                 self.data.category_stats = stats;
             }
+            if let Ok(stats) = db.get_time_by_profile(start, end) {
+                self.data.profile_stats = stats;
+            }
+            if let Ok(stats) = db.get_pause_stats(start, end) {
+                self.data.pause_stats = stats;
+            }
+            if let Ok(days) = db.get_interruptions_by_day(start, end) {
+                self.data.daily_interruptions = days;
+            }
+            if let Ok(stats) = db.get_break_stats(start, end) {
+                self.data.break_stats = stats;
+            }
+            if let Ok(stats) = db.get_estimates_by_category(start, end) {
+                self.data.estimate_stats = stats;
+            }
+            if let Ok(tasks) = db.get_finished_tasks(start, end) {
+                self.data.finished_tasks = tasks;
+            }
+
+            // Load open tasks and those finished within the history window
+            if let Ok(tasks) = db.get_tasks(thirty_days_ago) {
+                self.data.tasks = tasks;
+            }
         }
     }
 
@@ -1159,8 +4052,9 @@ impl App {
         });
     }
 
-    /// Quit the application
+    /// Quit the application, saving any running session as abandoned
     fn quit(&mut self) {
+        self.abandon_session();
         self.running = false;
     }
 }
@@ -1168,16 +4062,115 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{DesktopNotification, ProfileId, TaskStatus};
     use ratatui::style::Color;
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    /// Clock that only moves when told to, shared between the test and the App
+    ///
+    /// Its instants move with its timestamp, so the timer sees the time set here too.
+    #[derive(Debug, Clone)]
+    struct MockClock(Arc<AtomicI64>, std::time::Instant);
+
+    impl Default for MockClock {
+        fn default() -> Self {
+            Self(Arc::default(), std::time::Instant::now())
+        }
+    }
+
+    impl MockClock {
+        fn set(&self, ts: i64) {
+            self.0.store(ts, Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now_timestamp(&self) -> i64 {
+            self.0.load(Ordering::SeqCst)
+        }
+
+        fn now_datetime(&self) -> chrono::DateTime<Local> {
+            Local
+                .timestamp_opt(self.now_timestamp(), 0)
+                .single()
+                .unwrap_or_else(Local::now)
+        }
+
+        fn instant(&self) -> std::time::Instant {
+            self.1 + Duration::from_secs(self.now_timestamp().max(0) as u64)
+        }
+    }
+
+    /// Terminal output shared between the test and the App
+    #[derive(Clone, Default)]
+    struct CapturedOutput(Rc<RefCell<Vec<u8>>>);
+
+    impl CapturedOutput {
+        /// Everything written since the last call
+        fn take(&self) -> String {
+            String::from_utf8(std::mem::take(&mut *self.0.borrow_mut())).unwrap()
+        }
+    }
+
+    impl Write for CapturedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Build an App whose timer and timestamps both follow a controllable clock
+    fn app_with_clock(clock: &MockClock) -> App {
+        let clock: Arc<dyn Clock> = Arc::new(clock.clone());
+        App {
+            timer: PomodoroTimer::new(clock.clone()),
+            clock,
+            ..App::default()
+        }
+    }
+
+    /// Build an App with a ready session, a mock database and a controllable clock
+    fn app_with_session(clock: &MockClock) -> App {
+        let mut app = App {
+            db: Some(Box::new(MockDatabase::new())),
+            term_out: Box::new(std::io::sink()),
+            ..app_with_clock(clock)
+        };
+        app.data.categories = vec![Category {
+            id: None,
+            name: "coding".to_string(),
+            color: Color::Gray,
+            durations: DurationOverrides::default(),
+        }];
+        app.input.name.push('x');
+        app.create_session();
+        app
+    }
 
     /// Mock database for testing App without real database
     struct MockDatabase {
         categories: RefCell<Vec<Category>>,
         sessions: RefCell<Vec<Session>>,
+        breaks: RefCell<Vec<BreakPeriod>>,
         config: RefCell<Config>,
+        checkpoint: RefCell<Option<Session>>,
+        tasks: RefCell<Vec<Task>>,
+        profiles: RefCell<Vec<Profile>>,
+        cycle_steps: RefCell<Vec<CycleStep>>,
+        hooks: RefCell<Vec<Hook>>,
+        webhooks: RefCell<Vec<Webhook>>,
+        deliveries: RefCell<Vec<WebhookDelivery>>,
         next_session_id: RefCell<i64>,
         next_category_id: RefCell<i64>,
+        /// Make `save_session` fail, as a full disk would
+        fail_saves: Cell<bool>,
     }
 
     impl MockDatabase {
@@ -1187,11 +4180,21 @@ mod tests {
                     id: None,
                     name: "Default".to_string(),
                     color: Color::Gray,
+                    durations: DurationOverrides::default(),
                 }]),
                 sessions: RefCell::new(Vec::new()),
+                breaks: RefCell::new(Vec::new()),
                 config: RefCell::new(Config::default()),
+                checkpoint: RefCell::new(None),
+                tasks: RefCell::new(Vec::new()),
+                profiles: RefCell::new(Vec::new()),
+                cycle_steps: RefCell::new(Vec::new()),
+                hooks: RefCell::new(Vec::new()),
+                webhooks: RefCell::new(Vec::new()),
+                deliveries: RefCell::new(Vec::new()),
                 next_session_id: RefCell::new(1),
                 next_category_id: RefCell::new(1),
+                fail_saves: Cell::new(false),
             }
         }
     }
@@ -1201,6 +4204,9 @@ mod tests {
             &self,
             session: &Session,
         ) -> crate::error::Result<crate::models::SessionId> {
+            if self.fail_saves.get() {
+                return Err(std::io::Error::other("disk full").into());
+            }
             let mut sessions = self.sessions.borrow_mut();
             let mut next_id = self.next_session_id.borrow_mut();
             let id = crate::models::SessionId::from(*next_id);
@@ -1242,6 +4248,64 @@ mod tests {
             Ok(Vec::new())
         }
 
+        fn get_pause_stats(&self, _start: i64, _end: i64) -> crate::error::Result<PauseStat> {
+            let sessions = self.sessions.borrow();
+            Ok(PauseStat {
+                pause_count: sessions.iter().map(|s| s.pause_count() as i64).sum(),
+                paused_secs: sessions.iter().map(|s| s.paused_secs().as_secs()).sum(),
+            })
+        }
+
+        fn get_time_by_profile(
+            &self,
+            _start: i64,
+            _end: i64,
+        ) -> crate::error::Result<Vec<ProfileStat>> {
+            Ok(Vec::new())
+        }
+
+        fn get_interruptions_by_day(
+            &self,
+            _start: i64,
+            _end: i64,
+        ) -> crate::error::Result<Vec<DailyInterruptions>> {
+            Ok(Vec::new())
+        }
+
+        fn save_break(&self, period: &BreakPeriod) -> crate::error::Result<BreakId> {
+            let mut breaks = self.breaks.borrow_mut();
+            breaks.push(period.clone());
+            Ok(BreakId::new(breaks.len() as i64))
+        }
+
+        fn delete_break(&self, id: BreakId) -> crate::error::Result<usize> {
+            let mut breaks = self.breaks.borrow_mut();
+            let index = (i64::from(id) - 1) as usize;
+            if index < breaks.len() {
+                breaks.remove(index);
+                Ok(1)
+            } else {
+                Ok(0)
+            }
+        }
+
+        fn get_breaks_in_range(
+            &self,
+            _start: i64,
+            _end: i64,
+        ) -> crate::error::Result<Vec<BreakPeriod>> {
+            Ok(self.breaks.borrow().clone())
+        }
+
+        fn get_break_stats(&self, _start: i64, _end: i64) -> crate::error::Result<BreakStat> {
+            let breaks = self.breaks.borrow();
+            Ok(BreakStat {
+                count: breaks.len() as i64,
+                skipped: breaks.iter().filter(|b| b.skipped).count() as i64,
+                total_seconds: breaks.iter().map(|b| b.duration().as_secs()).sum(),
+            })
+        }
+
         fn get_categories(&self) -> crate::error::Result<Vec<Category>> {
             Ok(self.categories.borrow().clone())
         }
@@ -1259,6 +4323,7 @@ mod tests {
                 id: Some(id),
                 name: name.to_string(),
                 color,
+                durations: DurationOverrides::default(),
             });
             Ok(id)
         }
@@ -1284,12 +4349,361 @@ mod tests {
                     return Ok(1);
                 }
             }
-            Ok(0)
-        }
-
-        fn is_category_in_use(&self, name: &str) -> crate::error::Result<bool> {
-            let sessions = self.sessions.borrow();
-            Ok(sessions.iter().any(|s| s.category == name))
+            Ok(0)
+        }
+
+        fn set_category_durations(
+            &self,
+            id: crate::models::CategoryId,
+            durations: &DurationOverrides,
+        ) -> crate::error::Result<usize> {
+            let mut categories = self.categories.borrow_mut();
+            match categories.iter_mut().find(|c| c.id == Some(id)) {
+                Some(cat) => {
+                    cat.durations = *durations;
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+
+        fn is_category_in_use(&self, name: &str) -> crate::error::Result<bool> {
+            let sessions = self.sessions.borrow();
+            Ok(sessions.iter().any(|s| s.category == name))
+        }
+
+        fn save_checkpoint(&self, session: &Session) -> crate::error::Result<()> {
+            *self.checkpoint.borrow_mut() = Some(session.clone());
+            Ok(())
+        }
+
+        fn load_checkpoint(&self) -> crate::error::Result<Option<Session>> {
+            Ok(self.checkpoint.borrow().clone().map(|mut s| {
+                s.outcome = SessionOutcome::Recovered;
+                s
+            }))
+        }
+
+        fn recover_checkpoint(&self) -> crate::error::Result<Option<Session>> {
+            let Some(mut session) = self.load_checkpoint()? else {
+                return Ok(None);
+            };
+            session.id = Some(self.save_session(&session)?);
+            self.clear_checkpoint()?;
+            Ok(Some(session))
+        }
+
+        fn clear_checkpoint(&self) -> crate::error::Result<()> {
+            *self.checkpoint.borrow_mut() = None;
+            Ok(())
+        }
+
+        fn get_tasks(&self, _done_since: i64) -> crate::error::Result<Vec<Task>> {
+            let sessions = self.sessions.borrow();
+            let mut tasks = self.tasks.borrow().clone();
+            for task in tasks.iter_mut().filter(|t| t.is_open()) {
+                task.pomodoros = sessions
+                    .iter()
+                    .filter(|s| s.task_id == task.id && s.outcome == SessionOutcome::Completed)
+                    .count() as i64;
+            }
+            tasks.sort_by_key(|t| (!t.is_open(), t.position));
+            Ok(tasks)
+        }
+
+        fn get_finished_tasks(&self, _start: i64, _end: i64) -> crate::error::Result<Vec<Task>> {
+            let mut tasks: Vec<Task> = self
+                .tasks
+                .borrow()
+                .iter()
+                .filter(|t| !t.is_open())
+                .cloned()
+                .collect();
+            tasks.sort_by_key(|t| std::cmp::Reverse(t.estimate_error().abs()));
+            Ok(tasks)
+        }
+
+        fn get_estimates_by_category(
+            &self,
+            start: i64,
+            end: i64,
+        ) -> crate::error::Result<Vec<EstimateStat>> {
+            let tasks = self.get_finished_tasks(start, end)?;
+            let mut stats: Vec<EstimateStat> = Vec::new();
+            for task in tasks {
+                let index = match stats.iter().position(|s| s.name == task.category) {
+                    Some(i) => i,
+                    None => {
+                        stats.push(EstimateStat {
+                            name: task.category.clone(),
+                            ..EstimateStat::default()
+                        });
+                        stats.len() - 1
+                    }
+                };
+                stats[index].task_count += 1;
+                stats[index].estimated += task.estimate;
+                stats[index].actual += task.pomodoros;
+            }
+            Ok(stats)
+        }
+
+        fn create_task(
+            &self,
+            title: &str,
+            category: &str,
+            estimate: i64,
+        ) -> crate::error::Result<TaskId> {
+            let mut tasks = self.tasks.borrow_mut();
+            let id = TaskId::from(tasks.len() as i64 + 1);
+            tasks.push(Task {
+                id: Some(id),
+                title: title.to_string(),
+                category: category.to_string(),
+                estimate,
+                status: TaskStatus::Open,
+                position: id.into(),
+                pomodoros: 0,
+                completed_at: None,
+            });
+            Ok(id)
+        }
+
+        fn complete_task(
+            &self,
+            id: TaskId,
+            completed_at: Timestamp,
+        ) -> crate::error::Result<usize> {
+            let pomodoros = self
+                .get_tasks(0)?
+                .iter()
+                .find(|t| t.id == Some(id))
+                .map(|t| t.pomodoros);
+            let mut tasks = self.tasks.borrow_mut();
+            let Some(task) = tasks.iter_mut().find(|t| t.id == Some(id)) else {
+                return Ok(0);
+            };
+            task.status = TaskStatus::Done;
+            task.pomodoros = pomodoros.unwrap_or_default();
+            task.completed_at = Some(completed_at);
+            Ok(1)
+        }
+
+        fn delete_task(&self, id: TaskId) -> crate::error::Result<usize> {
+            let mut tasks = self.tasks.borrow_mut();
+            let len_before = tasks.len();
+            tasks.retain(|t| t.id != Some(id));
+            Ok(len_before - tasks.len())
+        }
+
+        fn swap_task_positions(&self, a: TaskId, b: TaskId) -> crate::error::Result<()> {
+            let mut tasks = self.tasks.borrow_mut();
+            let position = |tasks: &[Task], id| tasks.iter().position(|t| t.id == Some(id));
+            if let (Some(i), Some(j)) = (position(&tasks, a), position(&tasks, b)) {
+                let p = tasks[i].position;
+                tasks[i].position = tasks[j].position;
+                tasks[j].position = p;
+            }
+            Ok(())
+        }
+
+        fn get_profiles(&self) -> crate::error::Result<Vec<Profile>> {
+            Ok(self.profiles.borrow().clone())
+        }
+
+        fn create_profile(&self, profile: &Profile) -> crate::error::Result<ProfileId> {
+            let mut profiles = self.profiles.borrow_mut();
+            let id = ProfileId::from(profiles.len() as i64 + 1);
+            profiles.push(Profile {
+                id: Some(id),
+                ..profile.clone()
+            });
+            Ok(id)
+        }
+
+        fn delete_profile(&self, id: ProfileId) -> crate::error::Result<usize> {
+            let mut profiles = self.profiles.borrow_mut();
+            let len_before = profiles.len();
+            profiles.retain(|p| p.id != Some(id));
+            Ok(len_before - profiles.len())
+        }
+
+        fn get_cycle_steps(&self) -> crate::error::Result<Vec<CycleStep>> {
+            Ok(self.cycle_steps.borrow().clone())
+        }
+
+        fn create_cycle_step(&self, step: &CycleStep) -> crate::error::Result<CycleStepId> {
+            let mut steps = self.cycle_steps.borrow_mut();
+            let next = steps.iter().filter_map(|s| s.id).map(i64::from).max();
+            let id = CycleStepId::from(next.unwrap_or(0) + 1);
+            steps.push(CycleStep {
+                id: Some(id),
+                ..step.clone()
+            });
+            Ok(id)
+        }
+
+        fn update_cycle_step(&self, step: &CycleStep) -> crate::error::Result<usize> {
+            let mut steps = self.cycle_steps.borrow_mut();
+            match steps.iter_mut().find(|s| s.id == step.id) {
+                Some(existing) => {
+                    *existing = step.clone();
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+
+        fn delete_cycle_step(&self, id: CycleStepId) -> crate::error::Result<usize> {
+            let mut steps = self.cycle_steps.borrow_mut();
+            let len_before = steps.len();
+            steps.retain(|s| s.id != Some(id));
+            Ok(len_before - steps.len())
+        }
+
+        fn swap_cycle_step_positions(
+            &self,
+            a: CycleStepId,
+            b: CycleStepId,
+        ) -> crate::error::Result<()> {
+            let mut steps = self.cycle_steps.borrow_mut();
+            let position = |steps: &[CycleStep], id| steps.iter().position(|s| s.id == Some(id));
+            if let (Some(i), Some(j)) = (position(&steps, a), position(&steps, b)) {
+                steps.swap(i, j);
+            }
+            Ok(())
+        }
+
+        fn get_hooks(&self) -> crate::error::Result<Vec<Hook>> {
+            Ok(self.hooks.borrow().clone())
+        }
+
+        fn create_hook(&self, hook: &Hook) -> crate::error::Result<HookId> {
+            let mut hooks = self.hooks.borrow_mut();
+            let next = hooks.iter().filter_map(|h| h.id).map(i64::from).max();
+            let id = HookId::from(next.unwrap_or(0) + 1);
+            hooks.push(Hook {
+                id: Some(id),
+                ..hook.clone()
+            });
+            Ok(id)
+        }
+
+        fn update_hook(&self, hook: &Hook) -> crate::error::Result<usize> {
+            let mut hooks = self.hooks.borrow_mut();
+            match hooks.iter_mut().find(|h| h.id == hook.id) {
+                Some(existing) => {
+                    *existing = hook.clone();
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+
+        fn delete_hook(&self, id: HookId) -> crate::error::Result<usize> {
+            let mut hooks = self.hooks.borrow_mut();
+            let len_before = hooks.len();
+            hooks.retain(|h| h.id != Some(id));
+            Ok(len_before - hooks.len())
+        }
+
+        fn get_webhooks(&self) -> crate::error::Result<Vec<Webhook>> {
+            Ok(self.webhooks.borrow().clone())
+        }
+
+        fn create_webhook(&self, webhook: &Webhook) -> crate::error::Result<WebhookId> {
+            let mut webhooks = self.webhooks.borrow_mut();
+            let next = webhooks.iter().filter_map(|w| w.id).map(i64::from).max();
+            let id = WebhookId::from(next.unwrap_or(0) + 1);
+            webhooks.push(Webhook {
+                id: Some(id),
+                ..webhook.clone()
+            });
+            Ok(id)
+        }
+
+        fn update_webhook(&self, webhook: &Webhook) -> crate::error::Result<usize> {
+            let mut webhooks = self.webhooks.borrow_mut();
+            match webhooks.iter_mut().find(|w| w.id == webhook.id) {
+                Some(existing) => {
+                    *existing = webhook.clone();
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+
+        fn delete_webhook(&self, id: WebhookId) -> crate::error::Result<usize> {
+            self.deliveries.borrow_mut().retain(|d| d.webhook_id != id);
+            let mut webhooks = self.webhooks.borrow_mut();
+            let len_before = webhooks.len();
+            webhooks.retain(|w| w.id != Some(id));
+            Ok(len_before - webhooks.len())
+        }
+
+        fn queue_delivery(&self, delivery: &WebhookDelivery) -> crate::error::Result<DeliveryId> {
+            let mut deliveries = self.deliveries.borrow_mut();
+            let next = deliveries.iter().filter_map(|d| d.id).map(i64::from).max();
+            let id = DeliveryId::from(next.unwrap_or(0) + 1);
+            deliveries.push(WebhookDelivery {
+                id: Some(id),
+                ..delivery.clone()
+            });
+            Ok(id)
+        }
+
+        fn get_due_deliveries(&self, now: Timestamp) -> crate::error::Result<Vec<WebhookDelivery>> {
+            Ok(self
+                .deliveries
+                .borrow()
+                .iter()
+                .filter(|d| d.next_attempt_at <= now && d.attempts < WebhookDelivery::MAX_ATTEMPTS)
+                .cloned()
+                .collect())
+        }
+
+        fn reschedule_delivery(&self, delivery: &WebhookDelivery) -> crate::error::Result<usize> {
+            let mut deliveries = self.deliveries.borrow_mut();
+            match deliveries.iter_mut().find(|d| d.id == delivery.id) {
+                Some(existing) => {
+                    *existing = delivery.clone();
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+
+        fn delete_delivery(&self, id: DeliveryId) -> crate::error::Result<usize> {
+            let mut deliveries = self.deliveries.borrow_mut();
+            let len_before = deliveries.len();
+            deliveries.retain(|d| d.id != Some(id));
+            Ok(len_before - deliveries.len())
+        }
+
+        fn count_failed_deliveries(&self) -> crate::error::Result<HashMap<WebhookId, i64>> {
+            let mut counts = HashMap::new();
+            for delivery in self.deliveries.borrow().iter() {
+                if delivery.attempts >= WebhookDelivery::MAX_ATTEMPTS {
+                    *counts.entry(delivery.webhook_id).or_default() += 1;
+                }
+            }
+            Ok(counts)
+        }
+
+        fn retry_failed_deliveries(
+            &self,
+            id: WebhookId,
+            now: Timestamp,
+        ) -> crate::error::Result<usize> {
+            let mut retried = 0;
+            for delivery in self.deliveries.borrow_mut().iter_mut() {
+                if delivery.webhook_id == id && delivery.attempts >= WebhookDelivery::MAX_ATTEMPTS {
+                    delivery.attempts = 0;
+                    delivery.next_attempt_at = now;
+                    retried += 1;
+                }
+            }
+            Ok(retried)
         }
 
         fn get_config(&self) -> crate::error::Result<Config> {
@@ -1431,4 +4845,1487 @@ mod tests {
         app.handle_key_event(KeyEvent::from(KeyCode::Char('x')));
         assert!(app.notification.is_none());
     }
+
+    #[test]
+    fn test_pause_and_resume_record_segments() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+
+        app.start_timer();
+        clock.set(1300);
+        app.pause_timer();
+        clock.set(1400);
+        app.resume_timer();
+        clock.set(1600);
+        app.complete_session();
+
+        let session = app.current_session().unwrap();
+        assert_eq!(
+            session.segments,
+            vec![
+                SessionSegment {
+                    started_at: Timestamp::new(1000),
+                    ended_at: Timestamp::new(1300),
+                },
+                SessionSegment {
+                    started_at: Timestamp::new(1400),
+                    ended_at: Timestamp::new(1600),
+                },
+            ]
+        );
+        assert_eq!(session.pause_count(), 1);
+        assert_eq!(session.paused_secs(), DurationSecs::new(100));
+    }
+
+    #[test]
+    fn test_restarting_session_clears_segments() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+
+        app.start_timer();
+        clock.set(1100);
+        app.pause_timer();
+        app.start_timer();
+        clock.set(1200);
+        app.complete_session();
+
+        let session = app.current_session().unwrap();
+        assert_eq!(session.segments.len(), 1);
+        assert_eq!(session.segments[0].started_at, Timestamp::new(1100));
+    }
+
+    #[test]
+    fn test_interruption_logged_to_active_session() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.start_timer();
+
+        clock.set(1200);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('e')));
+        assert_eq!(app.modal, ModalState::Interruption);
+
+        // Typing the note takes a while; the timestamp is from the key press
+        clock.set(1230);
+        for c in "call".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c)));
+        }
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(app.modal, ModalState::None);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('i')));
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+        let session = app.current_session().unwrap();
+        assert_eq!(
+            session.interruptions[0],
+            Interruption {
+                kind: InterruptionKind::External,
+                note: Some("call".to_string()),
+                occurred_at: Timestamp::new(1200),
+            }
+        );
+        assert_eq!(session.interruptions[1].kind, InterruptionKind::Internal);
+        assert_eq!(session.interruptions[1].note, None);
+    }
+
+    #[test]
+    fn test_interruption_keys_ignored_without_active_work() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+
+        // Session is ready but the timer has not started
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('i')));
+        assert_eq!(app.modal, ModalState::None);
+    }
+
+    #[test]
+    fn test_finished_break_is_recorded() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.timer.work_duration = std::time::Duration::ZERO;
+        app.timer.short_break = std::time::Duration::ZERO;
+        app.start_timer();
+
+        // Work finishes, break auto-starts
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::ShortBreak);
+
+        clock.set(1300);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+
+        app.refresh_data();
+        assert_eq!(
+            app.data.breaks,
+            vec![BreakPeriod {
+                kind: BreakKind::Short,
+                started_at: Timestamp::new(1000),
+                ended_at: Timestamp::new(1300),
+                skipped: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_skipped_break_is_recorded() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.timer.work_duration = std::time::Duration::ZERO;
+        app.start_timer();
+        app.handle_tick();
+
+        clock.set(1060);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('s')));
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+
+        app.refresh_data();
+        assert_eq!(app.data.breaks.len(), 1);
+        assert!(app.data.breaks[0].skipped);
+        assert_eq!(app.data.breaks[0].duration(), DurationSecs::new(60));
+        assert_eq!(app.data.break_stats.skipped, 1);
+    }
+
+    #[test]
+    fn test_break_skipped_before_it_ran_is_not_recorded() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.data.config.auto_start_breaks = false;
+        app.timer.work_duration = std::time::Duration::ZERO;
+        app.start_timer();
+        app.handle_tick();
+        assert!(app.timer.is_awaiting_ack());
+
+        clock.set(1060);
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+
+        app.refresh_data();
+        assert!(app.data.breaks.is_empty());
+        assert_eq!(app.data.break_stats.skipped, 0);
+    }
+
+    #[test]
+    fn test_history_entries_interleave_breaks_when_shown() {
+        let mut app = App::default();
+        let session = Session::builder()
+            .name("Work")
+            .category("coding")
+            .started_at(Timestamp::new(1000))
+            .ended_at(Timestamp::new(2500))
+            .duration_secs(DurationSecs::new(1500))
+            .build()
+            .unwrap();
+        app.data.sessions = vec![session.clone(), session];
+        app.data.sessions[0].started_at = Timestamp::new(3000);
+        app.data.breaks = vec![BreakPeriod {
+            kind: BreakKind::Short,
+            started_at: Timestamp::new(2500),
+            ended_at: Timestamp::new(2800),
+            skipped: false,
+        }];
+
+        assert_eq!(app.data.history_entries().len(), 2);
+
+        app.data.show_breaks = true;
+        let entries = app.data.history_entries();
+        assert!(matches!(entries[0], HistoryEntry::Session { index: 0, .. }));
+        assert!(matches!(entries[1], HistoryEntry::Break(_)));
+        assert!(matches!(entries[2], HistoryEntry::Session { index: 1, .. }));
+
+        // Breaks are not selectable as sessions (index 0 is the date header)
+        assert_eq!(App::list_index_to_session_index(&entries, 2), None);
+        assert_eq!(App::list_index_to_session_index(&entries, 3), Some(1));
+    }
+
+    #[test]
+    fn test_quit_saves_running_session_as_abandoned() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.start_timer();
+        clock.set(1120);
+        press(&mut app, KeyCode::Char('p'));
+
+        clock.set(1200);
+        app.quit();
+
+        app.refresh_data();
+        assert_eq!(app.data.sessions.len(), 1);
+        let session = &app.data.sessions[0];
+        assert_eq!(session.outcome, SessionOutcome::Abandoned);
+        assert_eq!(session.duration_secs, DurationSecs::new(120));
+        assert_eq!(session.planned_secs, DurationSecs::new(25 * 60));
+    }
+
+    #[test]
+    fn test_stop_records_outcome_and_clears_checkpoint() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.start_timer();
+
+        // Starting the timer checkpoints the session for crash recovery
+        let checkpoint = app.db.as_ref().unwrap().load_checkpoint().unwrap();
+        assert_eq!(checkpoint.unwrap().outcome, SessionOutcome::Recovered);
+
+        // Later checkpoints carry the running segment so far
+        clock.set(1100);
+        app.checkpoint_session();
+        let checkpoint = app.db.as_ref().unwrap().load_checkpoint().unwrap().unwrap();
+        assert_eq!(
+            checkpoint.segments,
+            vec![SessionSegment {
+                started_at: Timestamp::new(1000),
+                ended_at: Timestamp::new(1100),
+            }]
+        );
+
+        clock.set(1300);
+        app.stop_session();
+
+        let db = app.db.as_ref().unwrap();
+        assert!(db.load_checkpoint().unwrap().is_none());
+        let sessions = db.get_sessions_in_range(0, i64::MAX).unwrap();
+        assert_eq!(sessions[0].outcome, SessionOutcome::Stopped);
+        assert_eq!(sessions[0].duration_secs, DurationSecs::new(300));
+    }
+
+    #[test]
+    fn test_failed_recovery_keeps_the_checkpoint() {
+        let clock = MockClock::default();
+        let db = MockDatabase::new();
+        let session = Session::builder()
+            .name("Crashed")
+            .category("coding")
+            .started_at(Timestamp::new(1000))
+            .ended_at(Timestamp::new(1300))
+            .duration_secs(DurationSecs::new(300))
+            .build()
+            .unwrap();
+        db.save_checkpoint(&session).unwrap();
+        db.fail_saves.set(true);
+        let mut app = App {
+            db: Some(Box::new(db)),
+            ..app_with_clock(&clock)
+        };
+
+        app.recover_checkpoint();
+        let notification = app.notification.as_ref().unwrap();
+        assert_eq!(notification.level, NotificationLevel::Error);
+        let db = app.db.as_ref().unwrap();
+        assert!(db.get_sessions_in_range(0, i64::MAX).unwrap().is_empty());
+        assert_eq!(db.load_checkpoint().unwrap().unwrap().name, "Crashed");
+    }
+
+    /// Press a plain key
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_session_from_task_counts_pomodoros() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        if let Some(ref db) = app.db {
+            db.create_task("Write report", "coding", 2).unwrap();
+        }
+        app.refresh_data();
+
+        // Start the queued task from the tasks view
+        app.view = View::Tasks;
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.modal, ModalState::Input);
+        assert_eq!(app.input.name.as_ref(), "Write report");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_task().map(|t| t.pomodoros), Some(0));
+        app.timer.work_duration = std::time::Duration::ZERO;
+
+        // A completed work period counts towards the task
+        app.handle_tick();
+        let task = app.current_task().unwrap();
+        assert_eq!(task.pomodoros, 1);
+        assert!(task.is_open());
+
+        // Marking it done freezes the actual count
+        app.view = View::Tasks;
+        app.data.task_state.select(Some(0));
+        press(&mut app, KeyCode::Char('x'));
+        let task = &app.data.tasks[0];
+        assert!(!task.is_open());
+        assert_eq!((task.pomodoros, task.estimate), (1, 2));
+        let notification = app.notification.as_ref().unwrap();
+        assert_eq!(notification.level, NotificationLevel::Info);
+        assert_eq!(app.data.finished_tasks.len(), 1);
+        assert_eq!(app.data.estimate_stats[0].mean_error(), -1.0);
+    }
+
+    #[test]
+    fn test_deleting_the_task_being_worked_on_keeps_the_session() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = App {
+            db: Some(Box::new(Database::open_in_memory().unwrap())),
+            term_out: Box::new(std::io::sink()),
+            ..app_with_clock(&clock)
+        };
+        if let Some(ref db) = app.db {
+            db.create_task("Write report", "coding", 2).unwrap();
+        }
+        app.refresh_data();
+        app.view = View::Tasks;
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        app.start_timer();
+        assert!(app.current_session().unwrap().task_id.is_some());
+
+        clock.set(1300);
+        app.view = View::Tasks;
+        app.data.task_state.select(Some(0));
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.data.tasks.is_empty());
+        assert_eq!(app.current_session().unwrap().task_id, None);
+        let db = app.db.as_ref().unwrap();
+        assert_eq!(db.load_checkpoint().unwrap().unwrap().task_id, None);
+
+        clock.set(1600);
+        app.stop_session();
+        assert!(app.notification.is_none());
+        let db = app.db.as_ref().unwrap();
+        let sessions = db.get_sessions_in_range(0, i64::MAX).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].duration_secs, DurationSecs::new(600));
+        assert!(db.load_checkpoint().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_input_modal_cycles_open_tasks() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        if let Some(ref db) = app.db {
+            db.create_task("First", "coding", 1).unwrap();
+            db.create_task("Second", "coding", 3).unwrap();
+        }
+        app.refresh_data();
+
+        press(&mut app, KeyCode::Char('n'));
+        app.input.field = InputField::Task;
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.input.selected_task, Some(1));
+        assert_eq!(app.input.name.as_ref(), "Second");
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.input.selected_task, None);
+    }
+
+    #[test]
+    fn test_phase_awaits_ack_when_auto_start_disabled() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.data.config.auto_start_breaks = false;
+        app.timer.work_duration = std::time::Duration::ZERO;
+        app.start_timer();
+
+        // Work finishes but the break waits for acknowledgement
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::ShortBreak);
+        assert!(app.timer.is_awaiting_ack());
+
+        // The bell keeps ringing while unacknowledged
+        clock.set(1000 + ACK_ALERT_INTERVAL_SECS);
+        app.handle_tick();
+        assert_eq!(
+            app.last_alert_at,
+            Timestamp::new(1000 + ACK_ALERT_INTERVAL_SECS)
+        );
+
+        clock.set(1100);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.timer.is_running());
+        assert_eq!(app.break_started_at, Some(Timestamp::new(1100)));
+    }
+
+    #[test]
+    fn test_auto_started_work_is_recorded() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.start_timer();
+
+        // Work, then break, finish; the next work period restarts the session
+        clock.set(25 * 60);
+        app.handle_tick();
+        clock.set(30 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        assert!(matches!(app.session_phase, SessionPhase::Active { .. }));
+    }
+
+    #[test]
+    fn test_flowtime_session_saves_real_duration_and_breaks_proportionally() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.timer.mode, TimerMode::Flowtime);
+
+        app.start_timer();
+        // Work never finishes on its own
+        clock.set(50 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.timer.phase, TimerPhase::ShortBreak);
+        assert!(app.timer.is_running());
+        assert_eq!(app.timer.flow_break.as_secs(), 10 * 60);
+
+        let sessions = app
+            .db
+            .as_ref()
+            .unwrap()
+            .get_sessions_in_range(0, 10_000)
+            .unwrap();
+        assert_eq!(sessions[0].mode, TimerMode::Flowtime);
+        assert_eq!(sessions[0].outcome, SessionOutcome::Completed);
+        assert_eq!(sessions[0].duration_secs.as_secs(), 50 * 60);
+        assert_eq!(sessions[0].planned_secs.as_secs(), 0);
+    }
+
+    #[test]
+    fn test_switching_profile_applies_durations_and_tags_sessions() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        if let Some(ref db) = app.db {
+            db.create_profile(&Profile::from_config("Pomodoro", &Config::default()))
+                .unwrap();
+            db.create_profile(&Profile {
+                id: None,
+                name: "Deep Work".to_string(),
+                work_duration_secs: 90 * 60,
+                short_break_secs: 20 * 60,
+                long_break_secs: 30 * 60,
+                sessions_until_long_break: 2,
+            })
+            .unwrap();
+        }
+        app.refresh_profiles();
+
+        press(&mut app, KeyCode::Char('P'));
+        assert_eq!(
+            app.active_profile().map(|p| p.name.as_str()),
+            Some("Pomodoro")
+        );
+        press(&mut app, KeyCode::Char('P'));
+        assert_eq!(app.timer.work_duration.as_secs(), 90 * 60);
+        assert_eq!(app.timer.sessions_until_long, 2);
+
+        // Profiles cannot change under a running timer
+        app.start_timer();
+        press(&mut app, KeyCode::Char('P'));
+        assert_eq!(
+            app.active_profile().map(|p| p.name.as_str()),
+            Some("Deep Work")
+        );
+
+        clock.set(60);
+        app.stop_session();
+        let sessions = app
+            .db
+            .as_ref()
+            .unwrap()
+            .get_sessions_in_range(0, 100)
+            .unwrap();
+        assert_eq!(sessions[0].profile.as_deref(), Some("Deep Work"));
+    }
+
+    #[test]
+    fn test_active_profile_cannot_be_deleted() {
+        let mut app = App {
+            db: Some(Box::new(MockDatabase::new())),
+            ..App::default()
+        };
+        if let Some(ref db) = app.db {
+            db.create_profile(&Profile::from_config("Pomodoro", &Config::default()))
+                .unwrap();
+        }
+        app.refresh_profiles();
+        app.switch_profile(0);
+
+        app.delete_selected_profile();
+        assert_eq!(app.data.profiles.len(), 1);
+        assert!(app.notification.is_some());
+    }
+
+    #[test]
+    fn test_custom_cycle_built_in_settings_drives_timer() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.modal = ModalState::Settings;
+        app.settings.mode = SettingsMode::Cycle;
+
+        // Warm-up (work, 5m), then a 10m short break
+        press(&mut app, KeyCode::Char('n'));
+        for c in "Warm-up".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Char('5'));
+        press(&mut app, KeyCode::Enter);
+
+        press(&mut app, KeyCode::Char('n'));
+        for c in "Break".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::Char('0'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.data.cycle_steps.len(), 2);
+        assert_eq!(app.data.cycle_steps[1].kind, TimerPhase::ShortBreak);
+
+        // Nothing changes until the custom cycle is switched on
+        assert_eq!(app.timer.current_step(), None);
+        press(&mut app, KeyCode::Char(' '));
+        assert!(app.data.config.custom_cycle);
+        assert_eq!(app.timer.phase_label(), "WARM-UP");
+        assert_eq!(app.timer.remaining().as_secs(), 5 * 60);
+
+        // Reordering puts the break first
+        press(&mut app, KeyCode::Char('K'));
+        assert_eq!(app.data.cycle_steps[0].label, "Break");
+        assert_eq!(app.settings.cycle_list_index, 0);
+        assert_eq!(app.timer.phase, TimerPhase::ShortBreak);
+
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.data.cycle_steps.len(), 1);
+        assert_eq!(app.timer.phase_label(), "WARM-UP");
+    }
+
+    /// Add a custom cycle of a 50m focus step and a 10m rest, switched on in settings
+    fn app_with_custom_cycle(clock: &MockClock) -> App {
+        let mut app = app_with_session(clock);
+        if let Some(ref db) = app.db {
+            for (label, kind, minutes) in [
+                ("Focus", TimerPhase::Work, 50),
+                ("Rest", TimerPhase::ShortBreak, 10),
+            ] {
+                db.create_cycle_step(&CycleStep {
+                    id: None,
+                    label: label.to_string(),
+                    kind,
+                    duration_secs: minutes * 60,
+                })
+                .unwrap();
+            }
+        }
+        app.refresh_cycle();
+        app.modal = ModalState::Settings;
+        app.settings.mode = SettingsMode::Cycle;
+        press(&mut app, KeyCode::Char(' '));
+        app.modal = ModalState::None;
+        assert_eq!(app.timer.phase_label(), "FOCUS");
+        app
+    }
+
+    #[test]
+    fn test_switching_cycles_starts_the_new_one_from_its_first_phase() {
+        let clock = MockClock::default();
+        let mut app = app_with_custom_cycle(&clock);
+
+        // Sit idle on the rest step after the focus step finishes
+        app.start_timer();
+        clock.set(50 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase_label(), "REST");
+        press(&mut app, KeyCode::Char('r'));
+        assert!(app.timer.is_idle());
+
+        app.modal = ModalState::Settings;
+        press(&mut app, KeyCode::Char(' '));
+        assert!(!app.data.config.custom_cycle);
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        assert_eq!(app.timer.remaining().as_secs(), 25 * 60);
+    }
+
+    #[test]
+    fn test_cycle_cannot_change_under_a_started_timer() {
+        let clock = MockClock::default();
+        let mut app = app_with_custom_cycle(&clock);
+        app.start_timer();
+        app.modal = ModalState::Settings;
+
+        press(&mut app, KeyCode::Char('J'));
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.data.cycle_steps.len(), 2);
+        assert_eq!(app.data.cycle_steps[0].label, "Focus");
+        assert_eq!(
+            app.notification.as_ref().map(|n| n.message.as_str()),
+            Some("Stop the timer before changing the cycle")
+        );
+
+        // An edited step is kept in its form until the timer stops
+        press(&mut app, KeyCode::Char('e'));
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.settings.step_form.is_some());
+        assert_eq!(app.data.cycle_steps[0].kind, TimerPhase::Work);
+        assert_eq!(app.timer.phase_label(), "FOCUS");
+        assert_eq!(app.timer.remaining().as_secs(), 50 * 60);
+    }
+
+    #[test]
+    fn test_category_durations_apply_when_session_created() {
+        let mut app = App {
+            db: Some(Box::new(MockDatabase::new())),
+            ..App::default()
+        };
+        app.refresh_categories();
+        app.modal = ModalState::Settings;
+        app.settings.mode = SettingsMode::Categories;
+
+        // New "reading" category with a 45 minute work override
+        press(&mut app, KeyCode::Char('n'));
+        for c in "reading".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('4'));
+        press(&mut app, KeyCode::Char('5'));
+        press(&mut app, KeyCode::Enter);
+        let reading = app
+            .data
+            .categories
+            .iter()
+            .position(|c| c.name == "reading")
+            .unwrap();
+        assert_eq!(
+            app.data.categories[reading].durations.work_duration_secs,
+            Some(45 * 60)
+        );
+
+        app.modal = ModalState::None;
+        app.input.name.push('x');
+        app.input.selected_category = reading;
+        app.create_session();
+        assert_eq!(app.timer.work_duration.as_secs(), 45 * 60);
+        assert_eq!(
+            app.timer.short_break.as_secs() as i64,
+            Config::DEFAULT_SHORT_BREAK_SECS
+        );
+
+        // Categories without overrides go back to the timer settings
+        app.input.selected_category = app
+            .data
+            .categories
+            .iter()
+            .position(|c| c.name == "Default")
+            .unwrap();
+        app.create_session();
+        assert_eq!(
+            app.timer.work_duration.as_secs() as i64,
+            Config::DEFAULT_WORK_SECS
+        );
+    }
+
+    #[test]
+    fn test_session_created_during_a_break_works_to_its_category() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.data.categories.push(Category {
+            id: None,
+            name: "reading".to_string(),
+            color: Color::Gray,
+            durations: DurationOverrides {
+                work_duration_secs: Some(45 * 60),
+                ..DurationOverrides::default()
+            },
+        });
+        app.start_timer();
+        clock.set(25 * 60);
+        app.handle_tick();
+        assert!(app.timer.phase.is_break());
+
+        // The running break keeps its length
+        app.input.name.push('x');
+        app.input.selected_category = 1;
+        app.create_session();
+        assert_eq!(app.timer.remaining().as_secs(), 5 * 60);
+
+        clock.set(30 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        assert!(app.timer.is_running());
+        assert_eq!(app.timer.remaining().as_secs(), 45 * 60);
+    }
+
+    #[test]
+    fn test_extending_phase_records_extra_time() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.data.config.extend_secs = 5 * 60;
+        app.start_timer();
+
+        // Extending twice keeps the work period going for ten minutes past its end
+        clock.set(24 * 60);
+        press(&mut app, KeyCode::Char('+'));
+        press(&mut app, KeyCode::Char('+'));
+        clock.set(25 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        assert_eq!(app.timer.remaining().as_secs(), 10 * 60);
+
+        clock.set(33 * 60);
+        app.stop_session();
+        let sessions = app
+            .db
+            .as_ref()
+            .unwrap()
+            .get_sessions_in_range(0, 10_000)
+            .unwrap();
+        assert_eq!(sessions[0].duration_secs.as_secs(), 33 * 60);
+        assert_eq!(sessions[0].planned_secs.as_secs(), 25 * 60);
+    }
+
+    #[test]
+    fn test_snoozing_an_ended_phase_records_it_once() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.data.config.extend_secs = 5 * 60;
+        app.data.config.auto_start_breaks = false;
+        app.data.config.auto_start_work = false;
+        app.start_timer();
+
+        // Work ends and the break waits; a minute later the work is snoozed
+        clock.set(25 * 60);
+        app.handle_tick();
+        assert!(app.timer.is_awaiting_ack());
+        clock.set(26 * 60);
+        press(&mut app, KeyCode::Char('+'));
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        assert!(app.timer.is_running());
+        assert_eq!(app.timer.remaining().as_secs(), 5 * 60);
+
+        clock.set(31 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::ShortBreak);
+        let db = app.db.as_ref().unwrap();
+        let sessions = db.get_sessions_in_range(0, 10_000).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].duration_secs.as_secs(), 30 * 60);
+        assert_eq!(sessions[0].planned_secs.as_secs(), 25 * 60);
+
+        // The break runs out, and snoozing it also leaves a single, longer break
+        press(&mut app, KeyCode::Enter);
+        clock.set(36 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.ended_phase(), Some(TimerPhase::ShortBreak));
+        press(&mut app, KeyCode::Char('+'));
+        assert_eq!(app.timer.phase, TimerPhase::ShortBreak);
+        clock.set(41 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        let breaks = app
+            .db
+            .as_ref()
+            .unwrap()
+            .get_breaks_in_range(0, 10_000)
+            .unwrap();
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].started_at, Timestamp::new(31 * 60));
+        assert_eq!(breaks[0].ended_at, Timestamp::new(41 * 60));
+    }
+
+    #[test]
+    fn test_overtime_waits_for_finish_and_records_overtime() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.data.config.overtime = true;
+        app.apply_timer_config();
+        app.start_timer();
+
+        // Reaching zero does not end the work period
+        clock.set(30 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        assert!(app.timer.in_overtime());
+
+        press(&mut app, KeyCode::Char('x'));
+        assert!(app.timer.phase.is_break());
+        let sessions = app
+            .db
+            .as_ref()
+            .unwrap()
+            .get_sessions_in_range(0, 10_000)
+            .unwrap();
+        assert_eq!(sessions[0].outcome, SessionOutcome::Completed);
+        assert_eq!(sessions[0].planned_secs.as_secs(), 25 * 60);
+        assert_eq!(sessions[0].overtime_secs.as_secs(), 5 * 60);
+    }
+
+    #[test]
+    fn test_warnings_fire_once_before_phase_end() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.start_timer();
+
+        clock.set(23 * 60 + 10);
+        app.handle_tick();
+        let message = app.notification.take().map(|n| n.message);
+        assert_eq!(message.as_deref(), Some("2m left in WORK SESSION"));
+        assert!(app.gauge_warning());
+
+        // The same warning is not repeated on later ticks
+        app.handle_tick();
+        assert!(app.notification.is_none());
+
+        clock.set(24 * 60 + 40);
+        app.handle_tick();
+        assert!(app.notification.take().is_some());
+    }
+
+    #[test]
+    fn test_phase_end_flashes_screen_when_pattern_asks() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.data.config.short_break_alert.flash = false;
+        app.start_timer();
+
+        let work_end = 1000 + 25 * 60;
+        clock.set(work_end);
+        app.handle_tick();
+        assert!(app.timer.phase.is_break());
+        assert!(app.screen_flash_on());
+
+        clock.set(work_end + ALERT_FLASH_SECS);
+        assert!(!app.screen_flash_on());
+
+        // Breaks end without a flash under their own pattern
+        clock.set(work_end + 5 * 60);
+        app.handle_tick();
+        assert_eq!(app.timer.phase, TimerPhase::Work);
+        assert!(!app.screen_flash_on());
+    }
+
+    #[test]
+    fn test_quiet_hours_follow_clock() {
+        let clock = MockClock::default();
+        let mut app = app_with_clock(&clock);
+        app.data.config.quiet_hours.enabled = true;
+
+        let late = Local.with_ymd_and_hms(2024, 3, 1, 23, 0, 0).unwrap();
+        clock.set(late.timestamp());
+        assert!(app.is_quiet_time());
+
+        let noon = Local.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        clock.set(noon.timestamp());
+        assert!(!app.is_quiet_time());
+    }
+
+    #[test]
+    fn test_alerts_tab_can_disable_a_warning() {
+        let mut app = App {
+            modal: ModalState::Settings,
+            ..App::default()
+        };
+        app.settings.editing_config = app.data.config.clone();
+
+        press(&mut app, KeyCode::Char('l'));
+        assert_eq!(app.settings.mode, SettingsMode::Alerts);
+        assert_eq!(app.settings.field, SettingsField::FirstWarning);
+
+        app.settings.editing_value.clear();
+        press(&mut app, KeyCode::Char('0'));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(
+            app.settings.field,
+            SettingsField::AlertFlash(TimerPhase::Work)
+        );
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.data.config.first_warning_secs, 0);
+        assert!(!app.data.config.work_alert.flash);
+    }
+
+    #[test]
+    fn test_hook_built_in_settings_reports_failure() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.modal = ModalState::Settings;
+        app.settings.mode = SettingsMode::Hooks;
+
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Tab);
+        "exit 4"
+            .chars()
+            .for_each(|c| press(&mut app, KeyCode::Char(c)));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.data.hooks.len(), 1);
+        assert_eq!(app.data.hooks[0].event, HookEvent::WorkStarted);
+        assert_eq!(app.data.hooks[0].timeout_secs, Hook::DEFAULT_TIMEOUT_SECS);
+        app.modal = ModalState::None;
+
+        // The hook runs in the background and its failure surfaces on a later tick
+        app.start_timer();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while app.notification.is_none() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            app.handle_tick();
+        }
+        let message = app.notification.map(|n| n.message).unwrap_or_default();
+        assert!(
+            message.starts_with("Hook `exit 4` exited with"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_webhook_is_retried_until_the_receiver_accepts_it() {
+        use crate::webhooks::tests::receiver;
+
+        let clock = MockClock::default();
+        clock.set(1_000);
+        let mut app = app_with_session(&clock);
+        let (down_url, down) = receiver(500);
+        let webhook = Webhook {
+            id: None,
+            url: down_url,
+            headers: String::new(),
+            events: vec![HookEvent::WorkStarted],
+        };
+        let db = app.db.as_ref().unwrap();
+        let id = db.create_webhook(&webhook).unwrap();
+        app.refresh_webhooks();
+
+        let queued = |app: &App| {
+            let db = app.db.as_ref().unwrap();
+            db.get_due_deliveries(Timestamp::new(i64::MAX)).unwrap()
+        };
+        let settle = |app: &mut App| {
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+            while !app.webhooks_in_flight.is_empty() && std::time::Instant::now() < deadline {
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                app.handle_tick();
+            }
+        };
+
+        app.start_timer();
+        down.join().unwrap();
+        settle(&mut app);
+        let pending = queued(&app);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].next_attempt_at, Timestamp::new(1_030));
+
+        // The receiver comes back, at a new address, before the retry is due
+        let (up_url, up) = receiver(200);
+        let db = app.db.as_ref().unwrap();
+        db.update_webhook(&Webhook {
+            id: Some(id),
+            url: up_url,
+            ..webhook
+        })
+        .unwrap();
+        app.refresh_webhooks();
+        clock.set(1_030);
+        app.handle_tick();
+        settle(&mut app);
+
+        assert!(queued(&app).is_empty());
+        let request = up.join().unwrap();
+        assert!(request.contains(r#""event":"work_started""#), "{}", request);
+        assert!(request.contains(r#""category":"coding""#), "{}", request);
+    }
+
+    #[test]
+    fn test_webhook_out_of_attempts_is_kept_until_retried() {
+        use crate::webhooks::tests::receiver;
+
+        let clock = MockClock::default();
+        clock.set(1_000);
+        let mut app = app_with_session(&clock);
+        let (down_url, down) = receiver(500);
+        let db = app.db.as_ref().unwrap();
+        let id = db
+            .create_webhook(&Webhook {
+                id: None,
+                url: down_url,
+                headers: String::new(),
+                events: Vec::new(),
+            })
+            .unwrap();
+        db.queue_delivery(&WebhookDelivery {
+            id: None,
+            webhook_id: id,
+            payload: "{}".to_string(),
+            attempts: WebhookDelivery::MAX_ATTEMPTS - 1,
+            next_attempt_at: Timestamp::new(1_000),
+            last_error: None,
+        })
+        .unwrap();
+        app.refresh_webhooks();
+
+        // The last attempt fails
+        app.send_due_webhooks();
+        down.join().unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !app.webhooks_in_flight.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            app.process_webhook_results();
+        }
+        let notification = app.notification.as_ref().unwrap();
+        assert_eq!(notification.level, NotificationLevel::Error);
+        assert_eq!(app.data.failed_deliveries.get(&id), Some(&1));
+        let db = app.db.as_ref().unwrap();
+        assert!(
+            db.get_due_deliveries(Timestamp::new(i64::MAX))
+                .unwrap()
+                .is_empty()
+        );
+
+        // Retrying from the settings queues it again
+        app.modal = ModalState::Settings;
+        app.settings.mode = SettingsMode::Webhooks;
+        press(&mut app, KeyCode::Char('r'));
+        assert!(app.data.failed_deliveries.is_empty());
+        assert_eq!(app.webhooks_in_flight.len(), 1);
+    }
+
+    #[test]
+    fn test_phase_end_notifies_desktop_and_title_follows_timer() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        let output = CapturedOutput::default();
+        app.term_out = Box::new(output.clone());
+        app.data.config.desktop_notification = DesktopNotification::Osc9;
+        app.data.config.window_title = true;
+        app.data.config.work_alert.bells = 0;
+
+        app.handle_tick();
+        assert_eq!(output.take(), "\x1b[22;2t\x1b]2;Grindstone\x07");
+
+        app.start_timer();
+        clock.set(60);
+        press(&mut app, KeyCode::Char('p'));
+        app.handle_tick();
+        assert_eq!(
+            output.take(),
+            "\x1b]2;24:00 WORK SESSION (paused) - Grindstone\x07"
+        );
+        // Nothing is rewritten while the title stays the same
+        app.handle_tick();
+        assert_eq!(output.take(), "");
+
+        press(&mut app, KeyCode::Char('s'));
+        clock.set(25 * 60);
+        app.handle_tick();
+        assert!(
+            output
+                .take()
+                .contains("\x1b]9;Grindstone: Time's up: WORK SESSION\x07")
+        );
+
+        // Turning the title off hands the terminal its own title back
+        app.data.config.window_title = false;
+        app.handle_tick();
+        assert_eq!(output.take(), "\x1b[23;2t");
+    }
+
+    #[test]
+    fn test_losing_focus_pauses_work_from_when_focus_was_lost() {
+        let clock = MockClock::default();
+        clock.set(1000);
+        let mut app = app_with_session(&clock);
+        app.data.config.focus_pause_secs = 60;
+        app.start_timer();
+
+        // Coming back before the threshold changes nothing
+        clock.set(1100);
+        app.handle_focus_change(false);
+        clock.set(1130);
+        app.handle_focus_change(true);
+        clock.set(1200);
+        app.handle_tick();
+        assert!(app.timer.is_running());
+
+        app.handle_focus_change(false);
+        clock.set(1259);
+        app.handle_tick();
+        assert!(app.timer.is_running());
+        clock.set(1260);
+        app.handle_tick();
+        assert!(app.timer.is_paused());
+        // The minute away is given back and recorded as paused
+        assert_eq!(app.timer.elapsed().as_secs(), 200);
+        let segments = &app.current_session().unwrap().segments;
+        assert_eq!(segments.last().unwrap().ended_at, Timestamp::new(1200));
+        assert!(app.notification.is_some());
+    }
+
+    #[test]
+    fn test_paste_types_into_focused_field_only() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.running = true;
+
+        // With no text field focused, pasted letters are not commands
+        app.handle_paste("q");
+        assert!(app.running);
+
+        app.modal = ModalState::TaskInput;
+        app.handle_paste("Write\treport\n");
+        assert_eq!(app.task_input.title.as_ref(), "Write report");
+
+        app.task_input.field = TaskField::Category;
+        app.handle_paste("xyz");
+        assert_eq!(app.task_input.title.as_ref(), "Write report");
+    }
+
+    #[test]
+    fn test_paste_into_number_field_keeps_only_digits() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        press(&mut app, KeyCode::Char('c'));
+        assert_eq!(app.modal, ModalState::Settings);
+        assert_eq!(app.settings.mode, SettingsMode::Timer);
+        app.settings.editing_value.clear();
+
+        // Letters, spaces and '?' would switch tabs, toggle or open help if typed
+        app.handle_paste("hello 5? ]2");
+        assert_eq!(app.settings.mode, SettingsMode::Timer);
+        assert!(!app.help_open);
+        assert_eq!(app.settings.editing_value, "52");
+    }
+
+    /// Draw the app on an off-screen terminal, as the event loop does before reading input
+    fn draw(app: &mut App) -> ratatui::buffer::Buffer {
+        draw_sized(app, 80, 24)
+    }
+
+    fn draw_sized(app: &mut App, width: u16, height: u16) -> ratatui::buffer::Buffer {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// The cell where some text was drawn
+    fn find_text(buffer: &ratatui::buffer::Buffer, text: &str) -> (u16, u16) {
+        let area = buffer.area;
+        (area.top()..area.bottom())
+            .find_map(|y| {
+                let row: String = (area.left()..area.right())
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                row.find(text).map(|x| (x as u16, y))
+            })
+            .unwrap_or_else(|| panic!("{:?} was not drawn", text))
+    }
+
+    fn mouse(app: &mut App, kind: MouseEventKind, (column, row): (u16, u16)) {
+        app.handle_mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        });
+    }
+
+    #[test]
+    fn test_clicking_history_rows_selects_and_double_click_opens_details() {
+        let mut app = App {
+            view: View::History,
+            ..App::default()
+        };
+        let session = Session::builder()
+            .name("Morning")
+            .category("coding")
+            .started_at(Timestamp::new(3000))
+            .ended_at(Timestamp::new(4500))
+            .duration_secs(DurationSecs::new(1500))
+            .build()
+            .unwrap();
+        let mut older = session.clone();
+        older.name = "Earlier".to_string();
+        older.started_at = Timestamp::new(1000);
+        app.data.sessions = vec![session, older];
+
+        let click = MouseEventKind::Down(MouseButton::Left);
+        let buffer = draw(&mut app);
+        let earlier = find_text(&buffer, "Earlier");
+        let morning = find_text(&buffer, "Morning");
+        mouse(&mut app, click, earlier);
+        assert_eq!(app.data.history_state.selected(), Some(2));
+        assert_eq!(app.modal, ModalState::None);
+
+        // The wheel moves the selection like j/k
+        mouse(&mut app, MouseEventKind::ScrollUp, earlier);
+        assert_eq!(app.data.history_state.selected(), Some(1));
+
+        draw(&mut app);
+        mouse(&mut app, click, morning);
+        mouse(&mut app, click, morning);
+        assert_eq!(app.modal, ModalState::Detail);
+        assert_eq!(app.detail.selected_session_index, 0);
+
+        // Rows under the modal no longer take clicks
+        draw(&mut app);
+        mouse(&mut app, click, earlier);
+        assert_eq!(app.data.history_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_clicking_labels_acts_like_their_keys() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        let click = MouseEventKind::Down(MouseButton::Left);
+
+        let buffer = draw(&mut app);
+        mouse(&mut app, click, find_text(&buffer, "[s] Start"));
+        assert!(app.timer.is_running());
+
+        app.view = View::Stats;
+        let buffer = draw(&mut app);
+        mouse(&mut app, click, find_text(&buffer, "Month"));
+        mouse(&mut app, click, find_text(&buffer, "Estimates"));
+        assert_eq!(app.data.stats_period, StatsPeriod::Month);
+        assert_eq!(app.data.chart_type, ChartType::Estimates);
+    }
+
+    #[test]
+    fn test_rebound_keys_drive_the_timer_and_its_controls() {
+        let clock = MockClock::default();
+        let mut app = App {
+            keymap: Keymap::parse("start = Space\npause = ctrl-o").unwrap(),
+            ..app_with_session(&clock)
+        };
+
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.timer.is_idle());
+        let buffer = draw(&mut app);
+        find_text(&buffer, "[Space] Start");
+
+        press(&mut app, KeyCode::Char(' '));
+        assert!(app.timer.is_running());
+        let buffer = draw(&mut app);
+        find_text(&buffer, "[Ctrl-o] Pause");
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+        assert!(app.timer.is_paused());
+    }
+
+    #[test]
+    fn test_timer_prompts_name_the_bound_keys() {
+        let clock = MockClock::default();
+        let keymap = Keymap::parse("confirm = ctrl-y\nnew = ctrl-n").unwrap();
+        let mut app = App {
+            keymap: keymap.clone(),
+            ..App::default()
+        };
+        let buffer = draw(&mut app);
+        find_text(
+            &buffer,
+            "No session - press [Ctrl-n] to start a new session",
+        );
+
+        let mut app = App {
+            keymap,
+            ..app_with_session(&clock)
+        };
+        app.data.config.auto_start_breaks = false;
+        app.timer.work_duration = std::time::Duration::ZERO;
+        app.start_timer();
+        app.handle_tick();
+        assert!(app.timer.is_awaiting_ack());
+        let buffer = draw(&mut app);
+        find_text(&buffer, "press [Ctrl-y] to start");
+    }
+
+    #[test]
+    fn test_help_overlay_lists_the_keys_of_the_current_screen() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.view = View::History;
+
+        press(&mut app, KeyCode::Char('?'));
+        assert!(app.help_open);
+        let buffer = draw(&mut app);
+        find_text(&buffer, "Keys: History");
+        find_text(&buffer, "Delete");
+
+        // Any key closes it without acting
+        press(&mut app, KeyCode::Char('t'));
+        assert!(!app.help_open);
+        assert_eq!(app.view, View::History);
+
+        // While typing, ? is text but F1 still opens help
+        app.open_input_modal(None);
+        press(&mut app, KeyCode::Char('?'));
+        assert!(!app.help_open);
+        assert!(app.input.name.as_ref().ends_with('?'));
+        press(&mut app, KeyCode::F(1));
+        assert!(app.help_open);
+        let buffer = draw(&mut app);
+        find_text(&buffer, "Keys: New Session");
+    }
+
+    #[test]
+    fn test_palette_filters_and_runs_commands_in_their_view() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        app.view = View::History;
+
+        press(&mut app, KeyCode::Char(':'));
+        assert_eq!(app.modal, ModalState::Palette);
+        for c in "new task".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        let buffer = draw(&mut app);
+        find_text(&buffer, "Tasks: New task");
+
+        // The command switches to its view before acting, like pressing its keys there
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.view, View::Tasks);
+        assert_eq!(app.modal, ModalState::TaskInput);
+    }
+
+    #[test]
+    fn test_palette_opens_with_ctrl_p_and_moves_the_selection() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.timer.is_running());
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert_eq!(app.modal, ModalState::Palette);
+        // Letters are typed rather than acting, so `p` doesn't pause
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app.timer.is_running());
+        assert_eq!(app.palette.matches()[0].action, Action::PauseTimer);
+
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.palette.selected, 1);
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.timer.is_paused());
+        assert_eq!(app.modal, ModalState::None);
+
+        // Esc closes without running anything
+        press(&mut app, KeyCode::Char(':'));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.modal, ModalState::None);
+        assert!(app.timer.is_paused());
+    }
+
+    #[test]
+    fn test_theme_setting_recolours_the_interface() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        let title_fg = |app: &mut App| {
+            let buffer = draw(app);
+            let (x, y) = find_text(&buffer, "Grindstone");
+            buffer[(x, y)].fg
+        };
+        assert_eq!(title_fg(&mut app), Theme::dark().title);
+
+        // The theme is the last field of the Alerts tab, just above the first
+        press(&mut app, KeyCode::Char('c'));
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.settings.field, SettingsField::Theme);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.data.config.theme, "light");
+        assert_eq!(title_fg(&mut app), Theme::light().title);
+
+        // NO_COLOR wins over the setting
+        app.no_color = true;
+        app.apply_theme();
+        assert_eq!(app.theme, Theme::monochrome());
+        assert_eq!(title_fg(&mut app), Color::Reset);
+    }
+
+    #[test]
+    fn test_big_clock_falls_back_to_plain_text_when_cramped() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        let time = app.timer.display_time();
+        find_text(&draw(&mut app), &time);
+
+        press(&mut app, KeyCode::Char('b'));
+        assert!(app.data.config.big_clock);
+        let buffer = draw(&mut app);
+        find_text(&buffer, "█");
+
+        // Too narrow for even the smallest digits
+        let buffer = draw_sized(&mut app, 16, 24);
+        find_text(&buffer, &time);
+
+        press(&mut app, KeyCode::Char('b'));
+        assert!(!app.data.config.big_clock);
+    }
+
+    #[test]
+    fn test_short_terminal_gets_the_compact_timer() {
+        let clock = MockClock::default();
+        let mut app = app_with_session(&clock);
+        let name = app.current_session().unwrap().name.clone();
+        press(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.view, View::History);
+
+        // A three-line pane shows the timer whatever the view, without leaving it
+        let buffer = draw_sized(&mut app, 80, 3);
+        assert_eq!(app.view, View::History);
+        let (_, time_row) = find_text(&buffer, &app.timer.display_time());
+        assert_eq!(find_text(&buffer, &name).1, time_row);
+        find_text(&buffer, "[s] Start  [n] New  [q] Quit");
+
+        // Keys act on the timer, and other views stay out of reach
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.timer.is_running());
+        find_text(&draw_sized(&mut app, 80, 3), "[p] Pause  [x] Stop");
+        press(&mut app, KeyCode::Char('t'));
+        assert_eq!(app.view, View::History);
+        find_text(&draw_sized(&mut app, 80, 3), COMPACT_TIMER_ONLY);
+
+        // Tall enough for the view again, unless compact is asked for
+        find_text(&draw(&mut app), "Session History");
+        app.compact = true;
+        let buffer = draw(&mut app);
+        assert_eq!(find_text(&buffer, &name).1, 0);
+    }
+
+    #[test]
+    fn test_compact_keys_leave_out_what_cannot_act() {
+        let mut app = App {
+            compact: true,
+            ..App::default()
+        };
+        let buffer = draw(&mut app);
+        let (_, y) = find_text(&buffer, "[q] Quit");
+        let row: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        assert_eq!(row.trim(), "[n] New  [q] Quit");
+    }
 }
@@ -1,8 +1,9 @@
 use chrono::{DateTime, Local};
+use std::fmt::Debug;
 use std::time::Instant;
 
 /// Trait for abstracting time operations, enabling testability
-pub trait Clock: Send + Sync {
+pub trait Clock: Send + Sync + Debug {
     /// Get the current Unix timestamp in seconds
     fn now_timestamp(&self) -> i64;
 
@@ -14,7 +15,7 @@ pub trait Clock: Send + Sync {
 }
 
 /// System clock implementation using real time
-#[derive(Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct SystemClock;
 
 impl Clock for SystemClock {
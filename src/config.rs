@@ -35,3 +35,25 @@ pub fn get_log_path() -> Result<PathBuf> {
 
     Ok(data_dir.join("grindstone.log"))
 }
+
+/// Get the path to the key bindings file.
+///
+/// Returns the path to `keys.conf` in the configuration directory, e.g.
+/// `~/.config/grindstone/keys.conf` on Linux. The file is optional.
+pub fn get_keymap_path() -> Result<PathBuf> {
+    let proj_dirs =
+        ProjectDirs::from("", "", "grindstone").ok_or(GrindstoneError::NoDataDirectory)?;
+
+    Ok(proj_dirs.config_dir().join("keys.conf"))
+}
+
+/// Get the path to the custom themes file.
+///
+/// Returns the path to `themes.conf` in the configuration directory, e.g.
+/// `~/.config/grindstone/themes.conf` on Linux. The file is optional.
+pub fn get_themes_path() -> Result<PathBuf> {
+    let proj_dirs =
+        ProjectDirs::from("", "", "grindstone").ok_or(GrindstoneError::NoDataDirectory)?;
+
+    Ok(proj_dirs.config_dir().join("themes.conf"))
+}
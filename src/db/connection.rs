@@ -1,9 +1,16 @@
+use std::collections::HashMap;
+
 use ratatui::style::Color;
 use rusqlite::Connection;
 
 use crate::config::get_db_path;
 use crate::error::Result;
-use crate::models::{Category, CategoryId, CategoryStat, Config, Session, SessionId};
+use crate::models::{
+    BreakId, BreakPeriod, BreakStat, Category, CategoryId, CategoryStat, Config, CycleStep,
+    CycleStepId, DailyInterruptions, DeliveryId, DurationOverrides, EstimateStat, Hook, HookId,
+    PauseStat, Profile, ProfileId, ProfileStat, Session, SessionId, Task, TaskId, Timestamp,
+    Webhook, WebhookDelivery, WebhookId,
+};
 
 use super::schema::init_schema;
 use super::{DatabaseOps, queries};
@@ -48,6 +55,34 @@ impl DatabaseOps for Database {
         queries::get_time_by_category(&self.conn, start, end).map_err(Into::into)
     }
 
+    fn get_pause_stats(&self, start: i64, end: i64) -> Result<PauseStat> {
+        queries::get_pause_stats(&self.conn, start, end).map_err(Into::into)
+    }
+
+    fn get_time_by_profile(&self, start: i64, end: i64) -> Result<Vec<ProfileStat>> {
+        queries::get_time_by_profile(&self.conn, start, end).map_err(Into::into)
+    }
+
+    fn get_interruptions_by_day(&self, start: i64, end: i64) -> Result<Vec<DailyInterruptions>> {
+        queries::get_interruptions_by_day(&self.conn, start, end).map_err(Into::into)
+    }
+
+    fn save_break(&self, period: &BreakPeriod) -> Result<BreakId> {
+        queries::save_break(&self.conn, period).map_err(Into::into)
+    }
+
+    fn delete_break(&self, id: BreakId) -> Result<usize> {
+        queries::delete_break(&self.conn, id).map_err(Into::into)
+    }
+
+    fn get_breaks_in_range(&self, start: i64, end: i64) -> Result<Vec<BreakPeriod>> {
+        queries::get_breaks_in_range(&self.conn, start, end).map_err(Into::into)
+    }
+
+    fn get_break_stats(&self, start: i64, end: i64) -> Result<BreakStat> {
+        queries::get_break_stats(&self.conn, start, end).map_err(Into::into)
+    }
+
     fn get_categories(&self) -> Result<Vec<Category>> {
         queries::get_categories(&self.conn).map_err(Into::into)
     }
@@ -64,10 +99,151 @@ impl DatabaseOps for Database {
         queries::update_category(&self.conn, id, name, color).map_err(Into::into)
     }
 
+    fn set_category_durations(
+        &self,
+        id: CategoryId,
+        durations: &DurationOverrides,
+    ) -> Result<usize> {
+        queries::set_category_durations(&self.conn, id, durations).map_err(Into::into)
+    }
+
     fn is_category_in_use(&self, name: &str) -> Result<bool> {
         queries::is_category_in_use(&self.conn, name).map_err(Into::into)
     }
 
+    fn save_checkpoint(&self, session: &Session) -> Result<()> {
+        queries::save_checkpoint(&self.conn, session).map_err(Into::into)
+    }
+
+    #[cfg(test)]
+    fn load_checkpoint(&self) -> Result<Option<Session>> {
+        queries::load_checkpoint(&self.conn).map_err(Into::into)
+    }
+
+    fn recover_checkpoint(&self) -> Result<Option<Session>> {
+        queries::recover_checkpoint(&self.conn).map_err(Into::into)
+    }
+
+    fn clear_checkpoint(&self) -> Result<()> {
+        queries::clear_checkpoint(&self.conn).map_err(Into::into)
+    }
+
+    fn get_tasks(&self, done_since: i64) -> Result<Vec<Task>> {
+        queries::get_tasks(&self.conn, done_since).map_err(Into::into)
+    }
+
+    fn get_finished_tasks(&self, start: i64, end: i64) -> Result<Vec<Task>> {
+        queries::get_finished_tasks(&self.conn, start, end).map_err(Into::into)
+    }
+
+    fn get_estimates_by_category(&self, start: i64, end: i64) -> Result<Vec<EstimateStat>> {
+        queries::get_estimates_by_category(&self.conn, start, end).map_err(Into::into)
+    }
+
+    fn create_task(&self, title: &str, category: &str, estimate: i64) -> Result<TaskId> {
+        queries::create_task(&self.conn, title, category, estimate).map_err(Into::into)
+    }
+
+    fn complete_task(&self, id: TaskId, completed_at: Timestamp) -> Result<usize> {
+        queries::complete_task(&self.conn, id, completed_at).map_err(Into::into)
+    }
+
+    fn delete_task(&self, id: TaskId) -> Result<usize> {
+        queries::delete_task(&self.conn, id).map_err(Into::into)
+    }
+
+    fn swap_task_positions(&self, a: TaskId, b: TaskId) -> Result<()> {
+        queries::swap_task_positions(&self.conn, a, b).map_err(Into::into)
+    }
+
+    fn get_profiles(&self) -> Result<Vec<Profile>> {
+        queries::get_profiles(&self.conn).map_err(Into::into)
+    }
+
+    fn create_profile(&self, profile: &Profile) -> Result<ProfileId> {
+        queries::create_profile(&self.conn, profile).map_err(Into::into)
+    }
+
+    fn delete_profile(&self, id: ProfileId) -> Result<usize> {
+        queries::delete_profile(&self.conn, id).map_err(Into::into)
+    }
+
+    fn get_cycle_steps(&self) -> Result<Vec<CycleStep>> {
+        queries::get_cycle_steps(&self.conn).map_err(Into::into)
+    }
+
+    fn create_cycle_step(&self, step: &CycleStep) -> Result<CycleStepId> {
+        queries::create_cycle_step(&self.conn, step).map_err(Into::into)
+    }
+
+    fn update_cycle_step(&self, step: &CycleStep) -> Result<usize> {
+        queries::update_cycle_step(&self.conn, step).map_err(Into::into)
+    }
+
+    fn delete_cycle_step(&self, id: CycleStepId) -> Result<usize> {
+        queries::delete_cycle_step(&self.conn, id).map_err(Into::into)
+    }
+
+    fn swap_cycle_step_positions(&self, a: CycleStepId, b: CycleStepId) -> Result<()> {
+        queries::swap_cycle_step_positions(&self.conn, a, b).map_err(Into::into)
+    }
+
+    fn get_hooks(&self) -> Result<Vec<Hook>> {
+        queries::get_hooks(&self.conn).map_err(Into::into)
+    }
+
+    fn create_hook(&self, hook: &Hook) -> Result<HookId> {
+        queries::create_hook(&self.conn, hook).map_err(Into::into)
+    }
+
+    fn update_hook(&self, hook: &Hook) -> Result<usize> {
+        queries::update_hook(&self.conn, hook).map_err(Into::into)
+    }
+
+    fn delete_hook(&self, id: HookId) -> Result<usize> {
+        queries::delete_hook(&self.conn, id).map_err(Into::into)
+    }
+
+    fn get_webhooks(&self) -> Result<Vec<Webhook>> {
+        queries::get_webhooks(&self.conn).map_err(Into::into)
+    }
+
+    fn create_webhook(&self, webhook: &Webhook) -> Result<WebhookId> {
+        queries::create_webhook(&self.conn, webhook).map_err(Into::into)
+    }
+
+    fn update_webhook(&self, webhook: &Webhook) -> Result<usize> {
+        queries::update_webhook(&self.conn, webhook).map_err(Into::into)
+    }
+
+    fn delete_webhook(&self, id: WebhookId) -> Result<usize> {
+        queries::delete_webhook(&self.conn, id).map_err(Into::into)
+    }
+
+    fn queue_delivery(&self, delivery: &WebhookDelivery) -> Result<DeliveryId> {
+        queries::queue_delivery(&self.conn, delivery).map_err(Into::into)
+    }
+
+    fn get_due_deliveries(&self, now: Timestamp) -> Result<Vec<WebhookDelivery>> {
+        queries::get_due_deliveries(&self.conn, now).map_err(Into::into)
+    }
+
+    fn reschedule_delivery(&self, delivery: &WebhookDelivery) -> Result<usize> {
+        queries::reschedule_delivery(&self.conn, delivery).map_err(Into::into)
+    }
+
+    fn delete_delivery(&self, id: DeliveryId) -> Result<usize> {
+        queries::delete_delivery(&self.conn, id).map_err(Into::into)
+    }
+
+    fn count_failed_deliveries(&self) -> Result<HashMap<WebhookId, i64>> {
+        queries::count_failed_deliveries(&self.conn).map_err(Into::into)
+    }
+
+    fn retry_failed_deliveries(&self, id: WebhookId, now: Timestamp) -> Result<usize> {
+        queries::retry_failed_deliveries(&self.conn, id, now).map_err(Into::into)
+    }
+
     fn get_config(&self) -> Result<Config> {
         queries::get_config(&self.conn).map_err(Into::into)
     }
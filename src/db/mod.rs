@@ -2,10 +2,17 @@ mod connection;
 pub mod queries;
 mod schema;
 
+use std::collections::HashMap;
+
 use ratatui::style::Color;
 
 use crate::error::Result;
-use crate::models::{Category, CategoryId, CategoryStat, Config, Session, SessionId};
+use crate::models::{
+    BreakId, BreakPeriod, BreakStat, Category, CategoryId, CategoryStat, Config, CycleStep,
+    CycleStepId, DailyInterruptions, DeliveryId, DurationOverrides, EstimateStat, Hook, HookId,
+    PauseStat, Profile, ProfileId, ProfileStat, Session, SessionId, Task, TaskId, Timestamp,
+    Webhook, WebhookDelivery, WebhookId,
+};
 
 pub use connection::Database;
 
@@ -15,11 +22,57 @@ pub trait DatabaseOps {
     fn delete_session(&self, id: SessionId) -> Result<usize>;
     fn get_sessions_in_range(&self, start: i64, end: i64) -> Result<Vec<Session>>;
     fn get_time_by_category(&self, start: i64, end: i64) -> Result<Vec<CategoryStat>>;
+    fn get_pause_stats(&self, start: i64, end: i64) -> Result<PauseStat>;
+    fn get_time_by_profile(&self, start: i64, end: i64) -> Result<Vec<ProfileStat>>;
+    fn get_interruptions_by_day(&self, start: i64, end: i64) -> Result<Vec<DailyInterruptions>>;
+    fn save_break(&self, period: &BreakPeriod) -> Result<BreakId>;
+    fn delete_break(&self, id: BreakId) -> Result<usize>;
+    fn get_breaks_in_range(&self, start: i64, end: i64) -> Result<Vec<BreakPeriod>>;
+    fn get_break_stats(&self, start: i64, end: i64) -> Result<BreakStat>;
     fn get_categories(&self) -> Result<Vec<Category>>;
     fn create_category(&self, name: &str, color: Color) -> Result<CategoryId>;
     fn delete_category(&self, id: CategoryId) -> Result<usize>;
     fn update_category(&self, id: CategoryId, name: &str, color: Color) -> Result<usize>;
+    fn set_category_durations(
+        &self,
+        id: CategoryId,
+        durations: &DurationOverrides,
+    ) -> Result<usize>;
     fn is_category_in_use(&self, name: &str) -> Result<bool>;
+    fn save_checkpoint(&self, session: &Session) -> Result<()>;
+    #[cfg(test)]
+    fn load_checkpoint(&self) -> Result<Option<Session>>;
+    fn recover_checkpoint(&self) -> Result<Option<Session>>;
+    fn clear_checkpoint(&self) -> Result<()>;
+    fn get_tasks(&self, done_since: i64) -> Result<Vec<Task>>;
+    fn get_finished_tasks(&self, start: i64, end: i64) -> Result<Vec<Task>>;
+    fn get_estimates_by_category(&self, start: i64, end: i64) -> Result<Vec<EstimateStat>>;
+    fn create_task(&self, title: &str, category: &str, estimate: i64) -> Result<TaskId>;
+    fn complete_task(&self, id: TaskId, completed_at: Timestamp) -> Result<usize>;
+    fn delete_task(&self, id: TaskId) -> Result<usize>;
+    fn swap_task_positions(&self, a: TaskId, b: TaskId) -> Result<()>;
+    fn get_profiles(&self) -> Result<Vec<Profile>>;
+    fn create_profile(&self, profile: &Profile) -> Result<ProfileId>;
+    fn delete_profile(&self, id: ProfileId) -> Result<usize>;
+    fn get_cycle_steps(&self) -> Result<Vec<CycleStep>>;
+    fn create_cycle_step(&self, step: &CycleStep) -> Result<CycleStepId>;
+    fn update_cycle_step(&self, step: &CycleStep) -> Result<usize>;
+    fn delete_cycle_step(&self, id: CycleStepId) -> Result<usize>;
+    fn swap_cycle_step_positions(&self, a: CycleStepId, b: CycleStepId) -> Result<()>;
+    fn get_hooks(&self) -> Result<Vec<Hook>>;
+    fn create_hook(&self, hook: &Hook) -> Result<HookId>;
+    fn update_hook(&self, hook: &Hook) -> Result<usize>;
+    fn delete_hook(&self, id: HookId) -> Result<usize>;
+    fn get_webhooks(&self) -> Result<Vec<Webhook>>;
+    fn create_webhook(&self, webhook: &Webhook) -> Result<WebhookId>;
+    fn update_webhook(&self, webhook: &Webhook) -> Result<usize>;
+    fn delete_webhook(&self, id: WebhookId) -> Result<usize>;
+    fn queue_delivery(&self, delivery: &WebhookDelivery) -> Result<DeliveryId>;
+    fn get_due_deliveries(&self, now: Timestamp) -> Result<Vec<WebhookDelivery>>;
+    fn reschedule_delivery(&self, delivery: &WebhookDelivery) -> Result<usize>;
+    fn delete_delivery(&self, id: DeliveryId) -> Result<usize>;
+    fn count_failed_deliveries(&self) -> Result<HashMap<WebhookId, i64>>;
+    fn retry_failed_deliveries(&self, id: WebhookId, now: Timestamp) -> Result<usize>;
     fn get_config(&self) -> Result<Config>;
     fn save_config(&self, config: &Config) -> Result<()>;
 }
@@ -1,16 +1,31 @@
+use std::collections::HashMap;
+
 use ratatui::style::Color;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 
 use crate::models::{
-    Category, CategoryId, CategoryStat, Config, Session, SessionId, format_hex_color,
-    parse_hex_color,
+    BreakId, BreakPeriod, BreakStat, Category, CategoryId, CategoryStat, Config, CycleStep,
+    CycleStepId, DailyInterruptions, DeliveryId, DesktopNotification, DurationOverrides,
+    EstimateStat, Hook, HookId, Interruption, PauseStat, PhaseKind, Profile, ProfileId,
+    ProfileStat, Session, SessionId, SessionOutcome, SessionSegment, Task, TaskId, TimerMode,
+    Timestamp, Webhook, WebhookDelivery, WebhookId, format_event_filter, format_hex_color,
+    parse_event_filter, parse_hex_color,
 };
 
-/// Save a session to the database
+/// Save a session with its running segments and interruptions to the database
 pub fn save_session(conn: &Connection, session: &Session) -> rusqlite::Result<SessionId> {
-    conn.execute(
-        "INSERT INTO sessions (name, description, category, started_at, ended_at, duration_secs)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    let tx = conn.unchecked_transaction()?;
+    let id = insert_session(&tx, session)?;
+    tx.commit()?;
+    Ok(id)
+}
+
+/// Insert a session and its children, within a transaction the caller holds
+fn insert_session(tx: &Connection, session: &Session) -> rusqlite::Result<SessionId> {
+    tx.execute(
+        "INSERT INTO sessions (name, description, category, started_at, ended_at, duration_secs,
+                               planned_secs, outcome, task_id, profile, mode, overtime_secs)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         params![
             session.name,
             session.description,
@@ -18,9 +33,110 @@ pub fn save_session(conn: &Connection, session: &Session) -> rusqlite::Result<Se
             session.started_at,
             session.ended_at,
             session.duration_secs,
+            session.planned_secs,
+            session.outcome,
+            session.task_id,
+            session.profile,
+            session.mode,
+            session.overtime_secs,
         ],
     )?;
-    Ok(SessionId::new(conn.last_insert_rowid()))
+    let id = SessionId::new(tx.last_insert_rowid());
+
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO session_segments (session_id, started_at, ended_at)
+             VALUES (?1, ?2, ?3)",
+        )?;
+        for segment in &session.segments {
+            stmt.execute(params![id, segment.started_at, segment.ended_at])?;
+        }
+
+        let mut stmt = tx.prepare(
+            "INSERT INTO interruptions (session_id, kind, note, occurred_at)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for interruption in &session.interruptions {
+            stmt.execute(params![
+                id,
+                interruption.kind,
+                interruption.note,
+                interruption.occurred_at
+            ])?;
+        }
+    }
+
+    Ok(id)
+}
+
+/// Load running segments for all sessions started within a time range, keyed by session
+fn get_segments_in_range(
+    conn: &Connection,
+    start: i64,
+    end: i64,
+) -> rusqlite::Result<HashMap<SessionId, Vec<SessionSegment>>> {
+    let mut stmt = conn.prepare(
+        "SELECT g.session_id, g.started_at, g.ended_at
+         FROM session_segments g
+         JOIN sessions s ON s.id = g.session_id
+         WHERE s.started_at >= ?1 AND s.started_at < ?2
+         ORDER BY g.session_id, g.started_at",
+    )?;
+
+    let rows = stmt.query_map(params![start, end], |row| {
+        let session_id: SessionId = row.get(0)?;
+        Ok((
+            session_id,
+            SessionSegment {
+                started_at: row.get(1)?,
+                ended_at: row.get(2)?,
+            },
+        ))
+    })?;
+
+    let mut segments: HashMap<SessionId, Vec<SessionSegment>> = HashMap::new();
+    for row in rows {
+        let (session_id, segment) = row?;
+        segments.entry(session_id).or_default().push(segment);
+    }
+    Ok(segments)
+}
+
+/// Load interruptions for all sessions started within a time range, keyed by session
+fn get_interruptions_in_range(
+    conn: &Connection,
+    start: i64,
+    end: i64,
+) -> rusqlite::Result<HashMap<SessionId, Vec<Interruption>>> {
+    let mut stmt = conn.prepare(
+        "SELECT i.session_id, i.kind, i.note, i.occurred_at
+         FROM interruptions i
+         JOIN sessions s ON s.id = i.session_id
+         WHERE s.started_at >= ?1 AND s.started_at < ?2
+         ORDER BY i.session_id, i.occurred_at",
+    )?;
+
+    let rows = stmt.query_map(params![start, end], |row| {
+        let session_id: SessionId = row.get(0)?;
+        Ok((
+            session_id,
+            Interruption {
+                kind: row.get(1)?,
+                note: row.get(2)?,
+                occurred_at: row.get(3)?,
+            },
+        ))
+    })?;
+
+    let mut interruptions: HashMap<SessionId, Vec<Interruption>> = HashMap::new();
+    for row in rows {
+        let (session_id, interruption) = row?;
+        interruptions
+            .entry(session_id)
+            .or_default()
+            .push(interruption);
+    }
+    Ok(interruptions)
 }
 
 /// Get sessions within a time range
@@ -30,7 +146,8 @@ pub fn get_sessions_in_range(
     end: i64,
 ) -> rusqlite::Result<Vec<Session>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, description, category, started_at, ended_at, duration_secs
+        "SELECT id, name, description, category, started_at, ended_at, duration_secs,
+                planned_secs, outcome, task_id, profile, mode, overtime_secs
          FROM sessions
          WHERE started_at >= ?1 AND started_at < ?2
          ORDER BY started_at DESC",
@@ -45,10 +162,57 @@ pub fn get_sessions_in_range(
             started_at: row.get(4)?,
             ended_at: row.get(5)?,
             duration_secs: row.get(6)?,
+            planned_secs: row.get(7)?,
+            outcome: row.get(8)?,
+            task_id: row.get(9)?,
+            profile: row.get(10)?,
+            mode: row.get(11)?,
+            overtime_secs: row.get(12)?,
+            segments: Vec::new(),
+            interruptions: Vec::new(),
         })
     })?;
+    let mut sessions = sessions.collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut segments = get_segments_in_range(conn, start, end)?;
+    let mut interruptions = get_interruptions_in_range(conn, start, end)?;
+    for session in &mut sessions {
+        let Some(id) = session.id else { continue };
+        if let Some(s) = segments.remove(&id) {
+            session.segments = s;
+        }
+        if let Some(i) = interruptions.remove(&id) {
+            session.interruptions = i;
+        }
+    }
+
+    Ok(sessions)
+}
 
-    sessions.collect()
+/// Get pause count and total paused time for sessions within a time range
+///
+/// Only sessions recorded with running segments contribute.
+pub fn get_pause_stats(conn: &Connection, start: i64, end: i64) -> rusqlite::Result<PauseStat> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(segment_count - 1), 0),
+                COALESCE(SUM(span - running), 0)
+         FROM (
+             SELECT COUNT(*) AS segment_count,
+                    MAX(g.ended_at) - MIN(g.started_at) AS span,
+                    SUM(g.ended_at - g.started_at) AS running
+             FROM sessions s
+             JOIN session_segments g ON g.session_id = s.id
+             WHERE s.started_at >= ?1 AND s.started_at < ?2
+             GROUP BY s.id
+         )",
+        params![start, end],
+        |row| {
+            Ok(PauseStat {
+                pause_count: row.get(0)?,
+                paused_secs: row.get(1)?,
+            })
+        },
+    )
 }
 
 /// Get total time by category within a time range
@@ -58,7 +222,8 @@ pub fn get_time_by_category(
     end: i64,
 ) -> rusqlite::Result<Vec<CategoryStat>> {
     let mut stmt = conn.prepare(
-        "SELECT category, SUM(duration_secs) as total
+        "SELECT category, SUM(duration_secs) as total, COUNT(*),
+                SUM(outcome = 'completed')
          FROM sessions
          WHERE started_at >= ?1 AND started_at < ?2
          GROUP BY category
@@ -69,15 +234,140 @@ pub fn get_time_by_category(
         Ok(CategoryStat {
             name: row.get(0)?,
             total_seconds: row.get(1)?,
+            session_count: row.get(2)?,
+            completed_count: row.get(3)?,
+        })
+    })?;
+
+    results.collect()
+}
+
+/// Get total time and completion per timer profile within a time range
+///
+/// Sessions recorded before profiles existed are grouped as "(none)".
+pub fn get_time_by_profile(
+    conn: &Connection,
+    start: i64,
+    end: i64,
+) -> rusqlite::Result<Vec<ProfileStat>> {
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(profile, '(none)'), COUNT(*), SUM(duration_secs) as total,
+                SUM(outcome = 'completed')
+         FROM sessions
+         WHERE started_at >= ?1 AND started_at < ?2
+         GROUP BY COALESCE(profile, '(none)')
+         ORDER BY total DESC",
+    )?;
+
+    let results = stmt.query_map(params![start, end], |row| {
+        Ok(ProfileStat {
+            name: row.get(0)?,
+            session_count: row.get(1)?,
+            total_seconds: row.get(2)?,
+            completed_count: row.get(3)?,
+        })
+    })?;
+
+    results.collect()
+}
+
+/// Get internal and external interruption counts per local day within a time range
+pub fn get_interruptions_by_day(
+    conn: &Connection,
+    start: i64,
+    end: i64,
+) -> rusqlite::Result<Vec<DailyInterruptions>> {
+    let mut stmt = conn.prepare(
+        "SELECT date(occurred_at, 'unixepoch', 'localtime') AS day,
+                SUM(kind = 'internal'),
+                SUM(kind = 'external')
+         FROM interruptions
+         WHERE occurred_at >= ?1 AND occurred_at < ?2
+         GROUP BY day
+         ORDER BY day",
+    )?;
+
+    let results = stmt.query_map(params![start, end], |row| {
+        Ok(DailyInterruptions {
+            day: row.get(0)?,
+            internal: row.get(1)?,
+            external: row.get(2)?,
         })
     })?;
 
     results.collect()
 }
 
+/// Save a break period to the database
+pub fn save_break(conn: &Connection, period: &BreakPeriod) -> rusqlite::Result<BreakId> {
+    conn.execute(
+        "INSERT INTO breaks (kind, started_at, ended_at, skipped) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            period.kind,
+            period.started_at,
+            period.ended_at,
+            period.skipped
+        ],
+    )?;
+    Ok(BreakId::new(conn.last_insert_rowid()))
+}
+
+/// Delete a break period
+pub fn delete_break(conn: &Connection, id: BreakId) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM breaks WHERE id = ?1", params![id])
+}
+
+/// Get break periods within a time range
+pub fn get_breaks_in_range(
+    conn: &Connection,
+    start: i64,
+    end: i64,
+) -> rusqlite::Result<Vec<BreakPeriod>> {
+    let mut stmt = conn.prepare(
+        "SELECT kind, started_at, ended_at, skipped
+         FROM breaks
+         WHERE started_at >= ?1 AND started_at < ?2
+         ORDER BY started_at DESC",
+    )?;
+
+    let breaks = stmt.query_map(params![start, end], |row| {
+        Ok(BreakPeriod {
+            kind: row.get(0)?,
+            started_at: row.get(1)?,
+            ended_at: row.get(2)?,
+            skipped: row.get(3)?,
+        })
+    })?;
+
+    breaks.collect()
+}
+
+/// Get break count, skipped count and total break time within a time range
+pub fn get_break_stats(conn: &Connection, start: i64, end: i64) -> rusqlite::Result<BreakStat> {
+    conn.query_row(
+        "SELECT COUNT(*),
+                COALESCE(SUM(skipped), 0),
+                COALESCE(SUM(ended_at - started_at), 0)
+         FROM breaks
+         WHERE started_at >= ?1 AND started_at < ?2",
+        params![start, end],
+        |row| {
+            Ok(BreakStat {
+                count: row.get(0)?,
+                skipped: row.get(1)?,
+                total_seconds: row.get(2)?,
+            })
+        },
+    )
+}
+
 /// Get all categories
 pub fn get_categories(conn: &Connection) -> rusqlite::Result<Vec<Category>> {
-    let mut stmt = conn.prepare("SELECT id, name, color FROM categories ORDER BY name")?;
+    let mut stmt = conn.prepare(
+        "SELECT id, name, color, work_duration_secs, short_break_secs, long_break_secs
+         FROM categories
+         ORDER BY name",
+    )?;
 
     let categories = stmt.query_map([], |row| {
         let color_hex: String = row.get(2)?;
@@ -85,13 +375,18 @@ pub fn get_categories(conn: &Connection) -> rusqlite::Result<Vec<Category>> {
             id: Some(row.get(0)?),
             name: row.get(1)?,
             color: parse_hex_color(&color_hex),
+            durations: DurationOverrides {
+                work_duration_secs: row.get(3)?,
+                short_break_secs: row.get(4)?,
+                long_break_secs: row.get(5)?,
+            },
         })
     })?;
 
     categories.collect()
 }
 
-/// Delete a session by ID
+/// Delete a session by ID; its segments and interruptions go with it
 pub fn delete_session(conn: &Connection, id: SessionId) -> rusqlite::Result<usize> {
     conn.execute("DELETE FROM sessions WHERE id = ?1", params![id])
 }
@@ -129,6 +424,25 @@ pub fn update_category(
     )
 }
 
+/// Set the durations a category overrides, clearing any left as `None`
+pub fn set_category_durations(
+    conn: &Connection,
+    id: CategoryId,
+    durations: &DurationOverrides,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE categories
+         SET work_duration_secs = ?1, short_break_secs = ?2, long_break_secs = ?3
+         WHERE id = ?4",
+        params![
+            durations.work_duration_secs,
+            durations.short_break_secs,
+            durations.long_break_secs,
+            id,
+        ],
+    )
+}
+
 /// Check if a category is in use by any session
 pub fn is_category_in_use(conn: &Connection, name: &str) -> rusqlite::Result<bool> {
     let count: i64 = conn.query_row(
@@ -139,144 +453,958 @@ pub fn is_category_in_use(conn: &Connection, name: &str) -> rusqlite::Result<boo
     Ok(count > 0)
 }
 
-/// Get timer configuration from database
-pub fn get_config(conn: &Connection) -> rusqlite::Result<Config> {
-    let mut config = Config::default();
+/// Record the in-progress session so it can be recovered after a crash
+///
+/// `ended_at` is stored as the time of the checkpoint, along with the segments and
+/// interruptions so far.
+pub fn save_checkpoint(conn: &Connection, session: &Session) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT OR REPLACE INTO active_session
+             (id, name, description, category, started_at, updated_at, duration_secs,
+              planned_secs, task_id, profile, mode, overtime_secs)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            session.name,
+            session.description,
+            session.category,
+            session.started_at,
+            session.ended_at,
+            session.duration_secs,
+            session.planned_secs,
+            session.task_id,
+            session.profile,
+            session.mode,
+            session.overtime_secs,
+        ],
+    )?;
+    tx.execute("DELETE FROM active_session_segments", [])?;
+    tx.execute("DELETE FROM active_session_interruptions", [])?;
 
-    let mut stmt = conn.prepare("SELECT key, value FROM config")?;
-    let rows = stmt.query_map([], |row| {
-        let key: String = row.get(0)?;
-        let value: i64 = row.get(1)?;
-        Ok((key, value))
-    })?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO active_session_segments (started_at, ended_at) VALUES (?1, ?2)",
+        )?;
+        for segment in &session.segments {
+            stmt.execute(params![segment.started_at, segment.ended_at])?;
+        }
 
-    for row in rows {
-        let (key, value) = row?;
-        match key.as_str() {
-            "work_duration_secs" => config.work_duration_secs = value,
-            "short_break_secs" => config.short_break_secs = value,
-            "long_break_secs" => config.long_break_secs = value,
-            "sessions_until_long_break" => config.sessions_until_long_break = value,
-            _ => {}
+        let mut stmt = tx.prepare(
+            "INSERT INTO active_session_interruptions (kind, note, occurred_at)
+             VALUES (?1, ?2, ?3)",
+        )?;
+        for interruption in &session.interruptions {
+            stmt.execute(params![
+                interruption.kind,
+                interruption.note,
+                interruption.occurred_at
+            ])?;
         }
     }
 
-    Ok(config)
+    tx.commit()
 }
 
-/// Save timer configuration to database
-pub fn save_config(conn: &Connection, config: &Config) -> rusqlite::Result<()> {
-    let mut stmt = conn.prepare("INSERT OR REPLACE INTO config (key, value) VALUES (?1, ?2)")?;
+/// Get the checkpointed session, if any, leaving the checkpoint in place
+pub fn load_checkpoint(conn: &Connection) -> rusqlite::Result<Option<Session>> {
+    let session = conn
+        .query_row(
+            "SELECT name, description, category, started_at, updated_at, duration_secs,
+                    planned_secs, task_id, profile, mode, overtime_secs
+             FROM active_session WHERE id = 1",
+            [],
+            |row| {
+                Ok(Session {
+                    id: None,
+                    name: row.get(0)?,
+                    description: row.get(1)?,
+                    category: row.get(2)?,
+                    started_at: row.get(3)?,
+                    ended_at: row.get(4)?,
+                    duration_secs: row.get(5)?,
+                    planned_secs: row.get(6)?,
+                    outcome: SessionOutcome::Recovered,
+                    task_id: row.get(7)?,
+                    profile: row.get(8)?,
+                    mode: row.get(9)?,
+                    overtime_secs: row.get(10)?,
+                    segments: Vec::new(),
+                    interruptions: Vec::new(),
+                })
+            },
+        )
+        .optional()?;
+    let Some(mut session) = session else {
+        return Ok(None);
+    };
 
-    stmt.execute(params!["work_duration_secs", config.work_duration_secs])?;
-    stmt.execute(params!["short_break_secs", config.short_break_secs])?;
-    stmt.execute(params!["long_break_secs", config.long_break_secs])?;
-    stmt.execute(params![
-        "sessions_until_long_break",
-        config.sessions_until_long_break
-    ])?;
+    let mut stmt = conn
+        .prepare("SELECT started_at, ended_at FROM active_session_segments ORDER BY started_at")?;
+    session.segments = stmt
+        .query_map([], |row| {
+            Ok(SessionSegment {
+                started_at: row.get(0)?,
+                ended_at: row.get(1)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
 
-    Ok(())
-}
+    let mut stmt = conn.prepare(
+        "SELECT kind, note, occurred_at FROM active_session_interruptions
+         ORDER BY occurred_at",
+    )?;
+    session.interruptions = stmt
+        .query_map([], |row| {
+            Ok(Interruption {
+                kind: row.get(0)?,
+                note: row.get(1)?,
+                occurred_at: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::db::Database;
-    use crate::models::{DurationSecs, Timestamp};
+    Ok(Some(session))
+}
 
-    #[test]
-    fn test_save_and_load_session() {
-        let db = Database::open_in_memory().unwrap();
-        let session = Session {
-            id: None,
-            name: "Test session".to_string(),
-            description: Some("Description".to_string()),
-            category: "coding".to_string(),
-            started_at: Timestamp::new(1000),
-            ended_at: Timestamp::new(2500),
-            duration_secs: DurationSecs::new(1500),
-        };
+/// Save the checkpointed session, if any, and clear the checkpoint
+///
+/// Both happen in one transaction, so a failed save leaves the checkpoint to try again.
+pub fn recover_checkpoint(conn: &Connection) -> rusqlite::Result<Option<Session>> {
+    let tx = conn.unchecked_transaction()?;
+    let Some(mut session) = load_checkpoint(&tx)? else {
+        return Ok(None);
+    };
+    session.id = Some(insert_session(&tx, &session)?);
+    clear_checkpoint(&tx)?;
+    tx.commit()?;
+    Ok(Some(session))
+}
 
-        let id = save_session(&db.conn, &session).unwrap();
-        assert!(i64::from(id) > 0);
+/// Remove the checkpointed session
+pub fn clear_checkpoint(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "DELETE FROM active_session;
+         DELETE FROM active_session_segments;
+         DELETE FROM active_session_interruptions;",
+    )
+}
 
-        let sessions = get_sessions_in_range(&db.conn, 0, 3000).unwrap();
-        assert_eq!(sessions.len(), 1);
-        assert_eq!(sessions[0].name, "Test session");
-        assert_eq!(sessions[0].duration_secs, DurationSecs::new(1500));
-    }
+/// Get open tasks in queue order, followed by tasks finished since `done_since`
+///
+/// `pomodoros` counts completed sessions linked to the task, or the recorded
+/// actual for finished tasks.
+pub fn get_tasks(conn: &Connection, done_since: i64) -> rusqlite::Result<Vec<Task>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.title, t.category, t.estimate, t.status, t.position,
+                COALESCE(t.actual, (SELECT COUNT(*) FROM sessions s
+                                    WHERE s.task_id = t.id AND s.outcome = 'completed')),
+                t.completed_at
+         FROM tasks t
+         WHERE t.status = 'open' OR t.completed_at >= ?1
+         ORDER BY t.status = 'done', t.position",
+    )?;
 
-    #[test]
-    fn test_time_by_category() {
-        let db = Database::open_in_memory().unwrap();
+    let tasks = stmt.query_map(params![done_since], |row| {
+        Ok(Task {
+            id: Some(row.get(0)?),
+            title: row.get(1)?,
+            category: row.get(2)?,
+            estimate: row.get(3)?,
+            status: row.get(4)?,
+            position: row.get(5)?,
+            pomodoros: row.get(6)?,
+            completed_at: row.get(7)?,
+        })
+    })?;
 
-        let s1 = Session {
-            id: None,
-            name: "Work 1".to_string(),
-            description: None,
-            category: "coding".to_string(),
-            started_at: Timestamp::new(1000),
-            ended_at: Timestamp::new(2000),
-            duration_secs: DurationSecs::new(1000),
-        };
-        let s2 = Session {
-            id: None,
-            name: "Work 2".to_string(),
-            description: None,
-            category: "coding".to_string(),
-            started_at: Timestamp::new(2000),
-            ended_at: Timestamp::new(3000),
-            duration_secs: DurationSecs::new(1000),
-        };
-        let s3 = Session {
-            id: None,
-            name: "Meeting".to_string(),
-            description: None,
-            category: "work".to_string(),
-            started_at: Timestamp::new(3000),
-            ended_at: Timestamp::new(4000),
-            duration_secs: DurationSecs::new(1000),
-        };
+    tasks.collect()
+}
 
-        save_session(&db.conn, &s1).unwrap();
-        save_session(&db.conn, &s2).unwrap();
-        save_session(&db.conn, &s3).unwrap();
+/// Get tasks finished within a time range, worst estimates first
+pub fn get_finished_tasks(conn: &Connection, start: i64, end: i64) -> rusqlite::Result<Vec<Task>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, category, estimate, status, position, COALESCE(actual, 0), completed_at
+         FROM tasks
+         WHERE status = 'done' AND completed_at >= ?1 AND completed_at < ?2
+         ORDER BY ABS(COALESCE(actual, 0) - estimate) DESC, completed_at DESC",
+    )?;
 
-        let totals = get_time_by_category(&db.conn, 0, 5000).unwrap();
-        assert_eq!(totals.len(), 2);
-        // coding: 2000 seconds, work: 1000 seconds
-        assert_eq!(totals[0].name, "coding");
-        assert_eq!(totals[0].total_seconds, 2000);
-        assert_eq!(totals[1].name, "work");
-        assert_eq!(totals[1].total_seconds, 1000);
-    }
+    let tasks = stmt.query_map(params![start, end], |row| {
+        Ok(Task {
+            id: Some(row.get(0)?),
+            title: row.get(1)?,
+            category: row.get(2)?,
+            estimate: row.get(3)?,
+            status: row.get(4)?,
+            position: row.get(5)?,
+            pomodoros: row.get(6)?,
+            completed_at: row.get(7)?,
+        })
+    })?;
 
-    #[test]
-    fn test_default_categories_seeded() {
-        let db = Database::open_in_memory().unwrap();
-        let categories = get_categories(&db.conn).unwrap();
-        assert!(!categories.is_empty());
-        assert!(categories.iter().any(|c| c.name == "coding"));
-    }
+    tasks.collect()
+}
 
-    #[test]
-    fn test_config_save_and_load() {
-        let db = Database::open_in_memory().unwrap();
+/// Get estimated vs actual pomodoros per category for tasks finished within a time range
+pub fn get_estimates_by_category(
+    conn: &Connection,
+    start: i64,
+    end: i64,
+) -> rusqlite::Result<Vec<EstimateStat>> {
+    let mut stmt = conn.prepare(
+        "SELECT category, COUNT(*), SUM(estimate), SUM(COALESCE(actual, 0))
+         FROM tasks
+         WHERE status = 'done' AND completed_at >= ?1 AND completed_at < ?2
+         GROUP BY category
+         ORDER BY category",
+    )?;
 
-        // Default config should be seeded
-        let config = get_config(&db.conn).unwrap();
-        assert_eq!(config.work_duration_secs, 25 * 60);
-        assert_eq!(config.short_break_secs, 5 * 60);
+    let stats = stmt.query_map(params![start, end], |row| {
+        Ok(EstimateStat {
+            name: row.get(0)?,
+            task_count: row.get(1)?,
+            estimated: row.get(2)?,
+            actual: row.get(3)?,
+        })
+    })?;
 
-        // Modify and save
-        let mut new_config = config;
-        new_config.work_duration_secs = 30 * 60;
-        save_config(&db.conn, &new_config).unwrap();
+    stats.collect()
+}
+
+/// Add a task to the end of the queue
+pub fn create_task(
+    conn: &Connection,
+    title: &str,
+    category: &str,
+    estimate: i64,
+) -> rusqlite::Result<TaskId> {
+    conn.execute(
+        "INSERT INTO tasks (title, category, estimate, position)
+         VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position), 0) + 1 FROM tasks))",
+        params![title, category, estimate],
+    )?;
+    Ok(TaskId::from(conn.last_insert_rowid()))
+}
+
+/// Mark a task as done, recording its completed pomodoros as the actual
+pub fn complete_task(
+    conn: &Connection,
+    id: TaskId,
+    completed_at: Timestamp,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE tasks
+         SET status = 'done',
+             completed_at = ?1,
+             actual = (SELECT COUNT(*) FROM sessions
+                       WHERE task_id = ?2 AND outcome = 'completed')
+         WHERE id = ?2",
+        params![completed_at, id],
+    )
+}
+
+/// Delete a task; sessions that worked on it are unlinked by the schema
+pub fn delete_task(conn: &Connection, id: TaskId) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM tasks WHERE id = ?1", params![id])
+}
+
+/// Swap the queue positions of two tasks
+pub fn swap_task_positions(conn: &Connection, a: TaskId, b: TaskId) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    let position = |id: TaskId| -> rusqlite::Result<i64> {
+        tx.query_row(
+            "SELECT position FROM tasks WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+    };
+    let (pos_a, pos_b) = (position(a)?, position(b)?);
+
+    tx.execute(
+        "UPDATE tasks SET position = ?1 WHERE id = ?2",
+        params![pos_b, a],
+    )?;
+    tx.execute(
+        "UPDATE tasks SET position = ?1 WHERE id = ?2",
+        params![pos_a, b],
+    )?;
+
+    tx.commit()
+}
+
+/// Get all timer profiles ordered by name
+pub fn get_profiles(conn: &Connection) -> rusqlite::Result<Vec<Profile>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, work_duration_secs, short_break_secs, long_break_secs,
+                sessions_until_long_break
+         FROM profiles
+         ORDER BY name",
+    )?;
+
+    let profiles = stmt.query_map([], |row| {
+        Ok(Profile {
+            id: Some(row.get(0)?),
+            name: row.get(1)?,
+            work_duration_secs: row.get(2)?,
+            short_break_secs: row.get(3)?,
+            long_break_secs: row.get(4)?,
+            sessions_until_long_break: row.get(5)?,
+        })
+    })?;
+
+    profiles.collect()
+}
+
+/// Create a new timer profile
+pub fn create_profile(conn: &Connection, profile: &Profile) -> rusqlite::Result<ProfileId> {
+    conn.execute(
+        "INSERT INTO profiles (name, work_duration_secs, short_break_secs, long_break_secs,
+                               sessions_until_long_break)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            profile.name,
+            profile.work_duration_secs,
+            profile.short_break_secs,
+            profile.long_break_secs,
+            profile.sessions_until_long_break,
+        ],
+    )?;
+    Ok(ProfileId::from(conn.last_insert_rowid()))
+}
+
+/// Delete a timer profile
+pub fn delete_profile(conn: &Connection, id: ProfileId) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM profiles WHERE id = ?1", params![id])
+}
+
+/// Get all hooks in creation order
+pub fn get_hooks(conn: &Connection) -> rusqlite::Result<Vec<Hook>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, event, command, timeout_secs
+         FROM hooks
+         ORDER BY id",
+    )?;
+
+    let hooks = stmt.query_map([], |row| {
+        Ok(Hook {
+            id: Some(row.get(0)?),
+            event: row.get(1)?,
+            command: row.get(2)?,
+            timeout_secs: row.get(3)?,
+        })
+    })?;
+
+    hooks.collect()
+}
+
+/// Create a hook
+pub fn create_hook(conn: &Connection, hook: &Hook) -> rusqlite::Result<HookId> {
+    conn.execute(
+        "INSERT INTO hooks (event, command, timeout_secs) VALUES (?1, ?2, ?3)",
+        params![hook.event, hook.command, hook.timeout_secs],
+    )?;
+    Ok(HookId::from(conn.last_insert_rowid()))
+}
+
+/// Update the event, command and timeout of a hook
+pub fn update_hook(conn: &Connection, hook: &Hook) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE hooks SET event = ?1, command = ?2, timeout_secs = ?3 WHERE id = ?4",
+        params![hook.event, hook.command, hook.timeout_secs, hook.id],
+    )
+}
+
+/// Delete a hook
+pub fn delete_hook(conn: &Connection, id: HookId) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM hooks WHERE id = ?1", params![id])
+}
+
+/// Get all webhooks in creation order
+pub fn get_webhooks(conn: &Connection) -> rusqlite::Result<Vec<Webhook>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, url, headers, events
+         FROM webhooks
+         ORDER BY id",
+    )?;
+
+    let webhooks = stmt.query_map([], |row| {
+        let events: String = row.get(3)?;
+        Ok(Webhook {
+            id: Some(row.get(0)?),
+            url: row.get(1)?,
+            headers: row.get(2)?,
+            events: parse_event_filter(&events),
+        })
+    })?;
+
+    webhooks.collect()
+}
+
+/// Create a webhook
+pub fn create_webhook(conn: &Connection, webhook: &Webhook) -> rusqlite::Result<WebhookId> {
+    conn.execute(
+        "INSERT INTO webhooks (url, headers, events) VALUES (?1, ?2, ?3)",
+        params![
+            webhook.url,
+            webhook.headers,
+            format_event_filter(&webhook.events)
+        ],
+    )?;
+    Ok(WebhookId::from(conn.last_insert_rowid()))
+}
+
+/// Update the URL, headers and event filter of a webhook
+pub fn update_webhook(conn: &Connection, webhook: &Webhook) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE webhooks SET url = ?1, headers = ?2, events = ?3 WHERE id = ?4",
+        params![
+            webhook.url,
+            webhook.headers,
+            format_event_filter(&webhook.events),
+            webhook.id
+        ],
+    )
+}
+
+/// Delete a webhook along with its queued deliveries
+pub fn delete_webhook(conn: &Connection, id: WebhookId) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM webhooks WHERE id = ?1", params![id])
+}
+
+/// Add a delivery to the webhook queue
+pub fn queue_delivery(
+    conn: &Connection,
+    delivery: &WebhookDelivery,
+) -> rusqlite::Result<DeliveryId> {
+    conn.execute(
+        "INSERT INTO webhook_queue (webhook_id, payload, attempts, next_attempt_at, last_error)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            delivery.webhook_id,
+            delivery.payload,
+            delivery.attempts,
+            delivery.next_attempt_at,
+            delivery.last_error
+        ],
+    )?;
+    Ok(DeliveryId::from(conn.last_insert_rowid()))
+}
+
+/// Get queued deliveries due for an attempt at the given time, oldest first
+///
+/// Failed deliveries, out of attempts, are left for the user to retry.
+pub fn get_due_deliveries(
+    conn: &Connection,
+    now: Timestamp,
+) -> rusqlite::Result<Vec<WebhookDelivery>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, webhook_id, payload, attempts, next_attempt_at, last_error
+         FROM webhook_queue
+         WHERE next_attempt_at <= ?1 AND attempts < ?2
+         ORDER BY id",
+    )?;
+
+    let deliveries = stmt.query_map(params![now, WebhookDelivery::MAX_ATTEMPTS], |row| {
+        Ok(WebhookDelivery {
+            id: Some(row.get(0)?),
+            webhook_id: row.get(1)?,
+            payload: row.get(2)?,
+            attempts: row.get(3)?,
+            next_attempt_at: row.get(4)?,
+            last_error: row.get(5)?,
+        })
+    })?;
+
+    deliveries.collect()
+}
+
+/// Record a failed attempt and when to try again
+pub fn reschedule_delivery(
+    conn: &Connection,
+    delivery: &WebhookDelivery,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE webhook_queue SET attempts = ?1, next_attempt_at = ?2, last_error = ?3
+         WHERE id = ?4",
+        params![
+            delivery.attempts,
+            delivery.next_attempt_at,
+            delivery.last_error,
+            delivery.id
+        ],
+    )
+}
+
+/// Remove a delivery from the queue once sent
+pub fn delete_delivery(conn: &Connection, id: DeliveryId) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM webhook_queue WHERE id = ?1", params![id])
+}
+
+/// Count the failed deliveries of each webhook that has any
+pub fn count_failed_deliveries(conn: &Connection) -> rusqlite::Result<HashMap<WebhookId, i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT webhook_id, COUNT(*) FROM webhook_queue
+         WHERE attempts >= ?1
+         GROUP BY webhook_id",
+    )?;
+    let counts = stmt.query_map(params![WebhookDelivery::MAX_ATTEMPTS], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })?;
+    counts.collect()
+}
+
+/// Give a webhook's failed deliveries a fresh set of attempts, starting now
+pub fn retry_failed_deliveries(
+    conn: &Connection,
+    id: WebhookId,
+    now: Timestamp,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE webhook_queue SET attempts = 0, next_attempt_at = ?2
+         WHERE webhook_id = ?1 AND attempts >= ?3",
+        params![id, now, WebhookDelivery::MAX_ATTEMPTS],
+    )
+}
+
+/// Get the steps of the custom phase cycle in order
+pub fn get_cycle_steps(conn: &Connection) -> rusqlite::Result<Vec<CycleStep>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, label, kind, duration_secs
+         FROM cycle_steps
+         ORDER BY position",
+    )?;
+
+    let steps = stmt.query_map([], |row| {
+        Ok(CycleStep {
+            id: Some(row.get(0)?),
+            label: row.get(1)?,
+            kind: row.get(2)?,
+            duration_secs: row.get(3)?,
+        })
+    })?;
+
+    steps.collect()
+}
+
+/// Append a step to the end of the custom phase cycle
+pub fn create_cycle_step(conn: &Connection, step: &CycleStep) -> rusqlite::Result<CycleStepId> {
+    conn.execute(
+        "INSERT INTO cycle_steps (label, kind, duration_secs, position)
+         VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position), 0) + 1 FROM cycle_steps))",
+        params![step.label, step.kind, step.duration_secs],
+    )?;
+    Ok(CycleStepId::from(conn.last_insert_rowid()))
+}
+
+/// Update the label, kind and duration of a cycle step
+pub fn update_cycle_step(conn: &Connection, step: &CycleStep) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE cycle_steps SET label = ?1, kind = ?2, duration_secs = ?3 WHERE id = ?4",
+        params![step.label, step.kind, step.duration_secs, step.id],
+    )
+}
+
+/// Remove a step from the custom phase cycle
+pub fn delete_cycle_step(conn: &Connection, id: CycleStepId) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM cycle_steps WHERE id = ?1", params![id])
+}
+
+/// Swap the positions of two cycle steps
+pub fn swap_cycle_step_positions(
+    conn: &Connection,
+    a: CycleStepId,
+    b: CycleStepId,
+) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    let position = |id: CycleStepId| -> rusqlite::Result<i64> {
+        tx.query_row(
+            "SELECT position FROM cycle_steps WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+    };
+    let (pos_a, pos_b) = (position(a)?, position(b)?);
+
+    tx.execute(
+        "UPDATE cycle_steps SET position = ?1 WHERE id = ?2",
+        params![pos_b, a],
+    )?;
+    tx.execute(
+        "UPDATE cycle_steps SET position = ?1 WHERE id = ?2",
+        params![pos_a, b],
+    )?;
+
+    tx.commit()
+}
+
+/// Get timer configuration from database
+pub fn get_config(conn: &Connection) -> rusqlite::Result<Config> {
+    let mut config = Config::default();
+
+    let mut stmt = conn.prepare("SELECT key, value FROM config")?;
+    let rows = stmt.query_map([], |row| {
+        let key: String = row.get(0)?;
+        let value: i64 = row.get(1)?;
+        Ok((key, value))
+    })?;
+
+    for row in rows {
+        let (key, value) = row?;
+        match key.as_str() {
+            "work_duration_secs" => config.work_duration_secs = value,
+            "short_break_secs" => config.short_break_secs = value,
+            "long_break_secs" => config.long_break_secs = value,
+            "sessions_until_long_break" => config.sessions_until_long_break = value,
+            "auto_start_breaks" => config.auto_start_breaks = value != 0,
+            "auto_start_work" => config.auto_start_work = value != 0,
+            "overtime" => config.overtime = value != 0,
+            "focus_pause_secs" => config.focus_pause_secs = value,
+            "active_profile_id" => config.active_profile = Some(ProfileId::from(value)),
+            "flowtime_enabled" if value != 0 => config.timer_mode = TimerMode::Flowtime,
+            "flowtime_break_ratio" => config.flowtime_break_ratio = value,
+            "flowtime_min_break_secs" => config.flowtime_min_break_secs = value,
+            "flowtime_max_break_secs" => config.flowtime_max_break_secs = value,
+            "custom_cycle" => config.custom_cycle = value != 0,
+            "extend_secs" => config.extend_secs = value,
+            "first_warning_secs" => config.first_warning_secs = value,
+            "second_warning_secs" => config.second_warning_secs = value,
+            "work_alert_bells" => config.work_alert.bells = value,
+            "work_alert_flash" => config.work_alert.flash = value != 0,
+            "work_alert_gauge" => config.work_alert.gauge = value != 0,
+            "short_break_alert_bells" => config.short_break_alert.bells = value,
+            "short_break_alert_flash" => config.short_break_alert.flash = value != 0,
+            "short_break_alert_gauge" => config.short_break_alert.gauge = value != 0,
+            "long_break_alert_bells" => config.long_break_alert.bells = value,
+            "long_break_alert_flash" => config.long_break_alert.flash = value != 0,
+            "long_break_alert_gauge" => config.long_break_alert.gauge = value != 0,
+            "quiet_hours" => config.quiet_hours.enabled = value != 0,
+            "quiet_start_hour" => config.quiet_hours.start_hour = value,
+            "quiet_end_hour" => config.quiet_hours.end_hour = value,
+            "desktop_notification" => {
+                config.desktop_notification = DesktopNotification::from_code(value);
+            }
+            "window_title" => config.window_title = value != 0,
+            "big_clock" => config.big_clock = value != 0,
+            _ => {}
+        }
+    }
+
+    // Settings that are not numbers
+    let mut stmt = conn.prepare("SELECT key, value FROM preferences")?;
+    let rows = stmt.query_map([], |row| {
+        let key: String = row.get(0)?;
+        let value: String = row.get(1)?;
+        Ok((key, value))
+    })?;
+    for row in rows {
+        let (key, value) = row?;
+        if key == "theme" {
+            config.theme = value;
+        }
+    }
+
+    // Durations come from the active profile when it still exists
+    if let Some(id) = config.active_profile {
+        let profile = get_profiles(conn)?.into_iter().find(|p| p.id == Some(id));
+        match profile {
+            Some(profile) => profile.apply_to(&mut config),
+            None => config.active_profile = None,
+        }
+    }
+
+    Ok(config)
+}
+
+/// Save timer configuration to database
+pub fn save_config(conn: &Connection, config: &Config) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("INSERT OR REPLACE INTO config (key, value) VALUES (?1, ?2)")?;
+
+    stmt.execute(params!["work_duration_secs", config.work_duration_secs])?;
+    stmt.execute(params!["short_break_secs", config.short_break_secs])?;
+    stmt.execute(params!["long_break_secs", config.long_break_secs])?;
+    stmt.execute(params![
+        "sessions_until_long_break",
+        config.sessions_until_long_break
+    ])?;
+    stmt.execute(params![
+        "auto_start_breaks",
+        i64::from(config.auto_start_breaks)
+    ])?;
+    stmt.execute(params![
+        "auto_start_work",
+        i64::from(config.auto_start_work)
+    ])?;
+    stmt.execute(params!["overtime", i64::from(config.overtime)])?;
+    stmt.execute(params!["focus_pause_secs", config.focus_pause_secs])?;
+    stmt.execute(params![
+        "flowtime_enabled",
+        i64::from(config.timer_mode == TimerMode::Flowtime)
+    ])?;
+    stmt.execute(params!["flowtime_break_ratio", config.flowtime_break_ratio])?;
+    stmt.execute(params![
+        "flowtime_min_break_secs",
+        config.flowtime_min_break_secs
+    ])?;
+    stmt.execute(params![
+        "flowtime_max_break_secs",
+        config.flowtime_max_break_secs
+    ])?;
+    stmt.execute(params!["custom_cycle", i64::from(config.custom_cycle)])?;
+    stmt.execute(params!["extend_secs", config.extend_secs])?;
+    stmt.execute(params!["first_warning_secs", config.first_warning_secs])?;
+    stmt.execute(params!["second_warning_secs", config.second_warning_secs])?;
+    for phase in [PhaseKind::Work, PhaseKind::ShortBreak, PhaseKind::LongBreak] {
+        let alert = config.alert_for(phase);
+        let key = |setting: &str| format!("{}_alert_{}", phase.as_str(), setting);
+        stmt.execute(params![key("bells"), alert.bells])?;
+        stmt.execute(params![key("flash"), i64::from(alert.flash)])?;
+        stmt.execute(params![key("gauge"), i64::from(alert.gauge)])?;
+    }
+    stmt.execute(params![
+        "quiet_hours",
+        i64::from(config.quiet_hours.enabled)
+    ])?;
+    stmt.execute(params!["quiet_start_hour", config.quiet_hours.start_hour])?;
+    stmt.execute(params!["quiet_end_hour", config.quiet_hours.end_hour])?;
+    stmt.execute(params![
+        "desktop_notification",
+        config.desktop_notification.code()
+    ])?;
+    stmt.execute(params!["window_title", i64::from(config.window_title)])?;
+    stmt.execute(params!["big_clock", i64::from(config.big_clock)])?;
+    conn.execute(
+        "INSERT OR REPLACE INTO preferences (key, value) VALUES ('theme', ?1)",
+        params![config.theme],
+    )?;
+
+    // Durations are edited on the active profile
+    if let Some(id) = config.active_profile {
+        stmt.execute(params!["active_profile_id", id])?;
+        conn.execute(
+            "UPDATE profiles
+             SET work_duration_secs = ?1, short_break_secs = ?2, long_break_secs = ?3,
+                 sessions_until_long_break = ?4
+             WHERE id = ?5",
+            params![
+                config.work_duration_secs,
+                config.short_break_secs,
+                config.long_break_secs,
+                config.sessions_until_long_break,
+                id,
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::{DurationSecs, HookEvent, TaskStatus};
+    use chrono::{Local, TimeZone};
+
+    fn segment(start: i64, end: i64) -> SessionSegment {
+        SessionSegment {
+            started_at: Timestamp::new(start),
+            ended_at: Timestamp::new(end),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_session() {
+        let db = Database::open_in_memory().unwrap();
+        let session = Session {
+            id: None,
+            name: "Test session".to_string(),
+            description: Some("Description".to_string()),
+            category: "coding".to_string(),
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(2500),
+            duration_secs: DurationSecs::new(1500),
+            planned_secs: DurationSecs::new(1500),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: Vec::new(),
+        };
+
+        let id = save_session(&db.conn, &session).unwrap();
+        assert!(i64::from(id) > 0);
+
+        let sessions = get_sessions_in_range(&db.conn, 0, 3000).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].name, "Test session");
+        assert_eq!(sessions[0].duration_secs, DurationSecs::new(1500));
+    }
+
+    #[test]
+    fn test_time_by_category() {
+        let db = Database::open_in_memory().unwrap();
+
+        let s1 = Session {
+            id: None,
+            name: "Work 1".to_string(),
+            description: None,
+            category: "coding".to_string(),
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(2000),
+            duration_secs: DurationSecs::new(1000),
+            planned_secs: DurationSecs::new(1000),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: Vec::new(),
+        };
+        let s2 = Session {
+            id: None,
+            name: "Work 2".to_string(),
+            description: None,
+            category: "coding".to_string(),
+            started_at: Timestamp::new(2000),
+            ended_at: Timestamp::new(3000),
+            duration_secs: DurationSecs::new(1000),
+            planned_secs: DurationSecs::new(1000),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: Vec::new(),
+        };
+        let s3 = Session {
+            id: None,
+            name: "Meeting".to_string(),
+            description: None,
+            category: "work".to_string(),
+            started_at: Timestamp::new(3000),
+            ended_at: Timestamp::new(4000),
+            duration_secs: DurationSecs::new(1000),
+            planned_secs: DurationSecs::new(1000),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: Vec::new(),
+        };
+
+        save_session(&db.conn, &s1).unwrap();
+        save_session(&db.conn, &s2).unwrap();
+        save_session(&db.conn, &s3).unwrap();
+
+        let totals = get_time_by_category(&db.conn, 0, 5000).unwrap();
+        assert_eq!(totals.len(), 2);
+        // coding: 2000 seconds, work: 1000 seconds
+        assert_eq!(totals[0].name, "coding");
+        assert_eq!(totals[0].total_seconds, 2000);
+        assert_eq!(totals[1].name, "work");
+        assert_eq!(totals[1].total_seconds, 1000);
+    }
+
+    #[test]
+    fn test_default_categories_seeded() {
+        let db = Database::open_in_memory().unwrap();
+        let categories = get_categories(&db.conn).unwrap();
+        assert!(!categories.is_empty());
+        assert!(categories.iter().any(|c| c.name == "coding"));
+    }
+
+    #[test]
+    fn test_config_save_and_load() {
+        let db = Database::open_in_memory().unwrap();
+
+        // Default config should be seeded
+        let config = get_config(&db.conn).unwrap();
+        assert_eq!(config.work_duration_secs, 25 * 60);
+        assert_eq!(config.short_break_secs, 5 * 60);
+
+        // Modify and save
+        let mut new_config = config;
+        new_config.work_duration_secs = 30 * 60;
+        save_config(&db.conn, &new_config).unwrap();
 
         // Reload and verify
         let loaded = get_config(&db.conn).unwrap();
-        assert_eq!(loaded.work_duration_secs, 30 * 60);
+        assert_eq!(loaded.work_duration_secs, 30 * 60);
+    }
+
+    #[test]
+    fn test_auto_start_config_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+
+        // Auto-start is on until configured otherwise
+        let config = get_config(&db.conn).unwrap();
+        assert!(config.auto_start_breaks && config.auto_start_work);
+
+        let new_config = Config {
+            auto_start_work: false,
+            ..config
+        };
+        save_config(&db.conn, &new_config).unwrap();
+
+        let loaded = get_config(&db.conn).unwrap();
+        assert!(loaded.auto_start_breaks);
+        assert!(!loaded.auto_start_work);
+    }
+
+    #[test]
+    fn test_flowtime_config_and_session_mode_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+
+        let config = Config {
+            timer_mode: TimerMode::Flowtime,
+            flowtime_break_ratio: 4,
+            ..get_config(&db.conn).unwrap()
+        };
+        save_config(&db.conn, &config).unwrap();
+        let loaded = get_config(&db.conn).unwrap();
+        assert_eq!(loaded.timer_mode, TimerMode::Flowtime);
+        assert_eq!(loaded.flowtime_break_ratio, 4);
+
+        let mut session = Session::builder()
+            .name("Deep dive")
+            .category("coding")
+            .started_at(Timestamp::new(1000))
+            .ended_at(Timestamp::new(4000))
+            .duration_secs(DurationSecs::new(3000))
+            .build()
+            .unwrap();
+        session.mode = TimerMode::Flowtime;
+        save_session(&db.conn, &session).unwrap();
+
+        let sessions = get_sessions_in_range(&db.conn, 0, 5000).unwrap();
+        assert_eq!(sessions[0].mode, TimerMode::Flowtime);
+        assert_eq!(sessions[0].duration_secs.as_secs(), 3000);
+    }
+
+    #[test]
+    fn test_category_duration_overrides_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+        let id = create_category(&db.conn, "piano", Color::Rgb(1, 2, 3)).unwrap();
+        let overrides = DurationOverrides {
+            work_duration_secs: Some(45 * 60),
+            short_break_secs: Some(10 * 60),
+            long_break_secs: None,
+        };
+        set_category_durations(&db.conn, id, &overrides).unwrap();
+
+        let categories = get_categories(&db.conn).unwrap();
+        let piano = categories.iter().find(|c| c.name == "piano").unwrap();
+        assert_eq!(piano.durations, overrides);
+        let work = categories.iter().find(|c| c.name == "work").unwrap();
+        assert!(work.durations.is_empty());
     }
 
     #[test]
@@ -323,6 +1451,14 @@ mod tests {
             started_at: Timestamp::new(1000),
             ended_at: Timestamp::new(2000),
             duration_secs: DurationSecs::new(1000),
+            planned_secs: DurationSecs::new(1000),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: Vec::new(),
         };
         save_session(&db.conn, &session).unwrap();
 
@@ -347,6 +1483,14 @@ mod tests {
                 started_at: Timestamp::new(1000 + i * 2000),
                 ended_at: Timestamp::new(1000 + i * 2000 + 1500),
                 duration_secs: DurationSecs::new(1500),
+                planned_secs: DurationSecs::new(1500),
+                overtime_secs: DurationSecs::default(),
+                outcome: SessionOutcome::Completed,
+                task_id: None,
+                profile: None,
+                mode: TimerMode::Pomodoro,
+                segments: Vec::new(),
+                interruptions: Vec::new(),
             };
             save_session(&db.conn, &session).unwrap();
         }
@@ -356,4 +1500,814 @@ mod tests {
         assert_eq!(totals[0].name, "coding");
         assert_eq!(totals[0].total_seconds, 15000);
     }
+
+    #[test]
+    fn test_save_and_load_session_segments() {
+        let db = Database::open_in_memory().unwrap();
+        let session = Session {
+            id: None,
+            name: "Interrupted".to_string(),
+            description: None,
+            category: "coding".to_string(),
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(3000),
+            duration_secs: DurationSecs::new(1500),
+            planned_secs: DurationSecs::new(1500),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: vec![segment(1000, 2000), segment(2500, 3000)],
+            interruptions: Vec::new(),
+        };
+        save_session(&db.conn, &session).unwrap();
+
+        let sessions = get_sessions_in_range(&db.conn, 0, 5000).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(
+            sessions[0].segments,
+            vec![segment(1000, 2000), segment(2500, 3000)]
+        );
+    }
+
+    #[test]
+    fn test_pause_stats() {
+        let db = Database::open_in_memory().unwrap();
+        let paused = Session {
+            id: None,
+            name: "Paused twice".to_string(),
+            description: None,
+            category: "coding".to_string(),
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(2000),
+            duration_secs: DurationSecs::new(700),
+            planned_secs: DurationSecs::new(700),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: vec![
+                segment(1000, 1300),
+                segment(1400, 1600),
+                segment(1800, 2000),
+            ],
+            interruptions: Vec::new(),
+        };
+        let uninterrupted = Session {
+            id: None,
+            name: "Straight through".to_string(),
+            description: None,
+            category: "coding".to_string(),
+            started_at: Timestamp::new(3000),
+            ended_at: Timestamp::new(4500),
+            duration_secs: DurationSecs::new(1500),
+            planned_secs: DurationSecs::new(1500),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: vec![segment(3000, 4500)],
+            interruptions: Vec::new(),
+        };
+        save_session(&db.conn, &paused).unwrap();
+        save_session(&db.conn, &uninterrupted).unwrap();
+
+        let stats = get_pause_stats(&db.conn, 0, 5000).unwrap();
+        assert_eq!(stats.pause_count, 2);
+        assert_eq!(stats.paused_secs, 300);
+
+        // Empty range yields zeroes rather than NULL
+        let empty = get_pause_stats(&db.conn, 10_000, 20_000).unwrap();
+        assert_eq!(empty, PauseStat::default());
+    }
+
+    #[test]
+    fn test_delete_session_removes_segments() {
+        let db = Database::open_in_memory().unwrap();
+        let session = Session {
+            id: None,
+            name: "Doomed".to_string(),
+            description: None,
+            category: "coding".to_string(),
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(2000),
+            duration_secs: DurationSecs::new(1000),
+            planned_secs: DurationSecs::new(1000),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: vec![segment(1000, 2000)],
+            interruptions: Vec::new(),
+        };
+        let id = save_session(&db.conn, &session).unwrap();
+        delete_session(&db.conn, id).unwrap();
+
+        let remaining: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM session_segments", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn test_save_and_load_interruptions() {
+        use crate::models::InterruptionKind;
+
+        let db = Database::open_in_memory().unwrap();
+        let session = Session {
+            id: None,
+            name: "Noisy".to_string(),
+            description: None,
+            category: "work".to_string(),
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(2500),
+            duration_secs: DurationSecs::new(1500),
+            planned_secs: DurationSecs::new(1500),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: vec![
+                Interruption {
+                    kind: InterruptionKind::External,
+                    note: Some("phone".to_string()),
+                    occurred_at: Timestamp::new(1200),
+                },
+                Interruption {
+                    kind: InterruptionKind::Internal,
+                    note: None,
+                    occurred_at: Timestamp::new(1100),
+                },
+            ],
+        };
+        let id = save_session(&db.conn, &session).unwrap();
+
+        let sessions = get_sessions_in_range(&db.conn, 0, 3000).unwrap();
+        let loaded = &sessions[0].interruptions;
+        assert_eq!(loaded.len(), 2);
+        // Loaded in chronological order
+        assert_eq!(loaded[0].kind, InterruptionKind::Internal);
+        assert_eq!(loaded[1].note.as_deref(), Some("phone"));
+
+        delete_session(&db.conn, id).unwrap();
+        let remaining: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM interruptions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn test_interruptions_by_day() {
+        use crate::models::InterruptionKind;
+
+        let db = Database::open_in_memory().unwrap();
+        let day_one = Local
+            .with_ymd_and_hms(2024, 3, 4, 10, 0, 0)
+            .unwrap()
+            .timestamp();
+        let day_two = Local
+            .with_ymd_and_hms(2024, 3, 5, 10, 0, 0)
+            .unwrap()
+            .timestamp();
+        let interruption = |kind, at| Interruption {
+            kind,
+            note: None,
+            occurred_at: Timestamp::new(at),
+        };
+        let session = Session {
+            id: None,
+            name: "Spanning".to_string(),
+            description: None,
+            category: "work".to_string(),
+            started_at: Timestamp::new(day_one),
+            ended_at: Timestamp::new(day_one + 1500),
+            duration_secs: DurationSecs::new(1500),
+            planned_secs: DurationSecs::new(1500),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Completed,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: vec![
+                interruption(InterruptionKind::Internal, day_one + 10),
+                interruption(InterruptionKind::Internal, day_one + 20),
+                interruption(InterruptionKind::External, day_one + 30),
+                interruption(InterruptionKind::External, day_two),
+            ],
+        };
+        save_session(&db.conn, &session).unwrap();
+
+        let days = get_interruptions_by_day(&db.conn, 0, i64::MAX).unwrap();
+        assert_eq!(
+            days,
+            vec![
+                DailyInterruptions {
+                    day: "2024-03-04".to_string(),
+                    internal: 2,
+                    external: 1,
+                },
+                DailyInterruptions {
+                    day: "2024-03-05".to_string(),
+                    internal: 0,
+                    external: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_breaks() {
+        use crate::models::BreakKind;
+
+        let db = Database::open_in_memory().unwrap();
+        let taken = BreakPeriod {
+            kind: BreakKind::Short,
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(1300),
+            skipped: false,
+        };
+        let skipped = BreakPeriod {
+            kind: BreakKind::Long,
+            started_at: Timestamp::new(3000),
+            ended_at: Timestamp::new(3060),
+            skipped: true,
+        };
+        save_break(&db.conn, &taken).unwrap();
+        save_break(&db.conn, &skipped).unwrap();
+
+        // Most recent first, like sessions
+        let breaks = get_breaks_in_range(&db.conn, 0, 5000).unwrap();
+        assert_eq!(breaks, vec![skipped, taken]);
+
+        let stats = get_break_stats(&db.conn, 0, 5000).unwrap();
+        assert_eq!(
+            stats,
+            BreakStat {
+                count: 2,
+                skipped: 1,
+                total_seconds: 360,
+            }
+        );
+
+        let empty = get_break_stats(&db.conn, 10_000, 20_000).unwrap();
+        assert_eq!(empty, BreakStat::default());
+    }
+
+    #[test]
+    fn test_outcome_and_planned_duration_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+        let session = Session {
+            id: None,
+            name: "Cut short".to_string(),
+            description: None,
+            category: "coding".to_string(),
+            started_at: Timestamp::new(1000),
+            ended_at: Timestamp::new(1600),
+            duration_secs: DurationSecs::new(600),
+            planned_secs: DurationSecs::new(1500),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::Stopped,
+            task_id: None,
+            profile: None,
+            mode: TimerMode::Pomodoro,
+            segments: Vec::new(),
+            interruptions: Vec::new(),
+        };
+        save_session(&db.conn, &session).unwrap();
+
+        let loaded = &get_sessions_in_range(&db.conn, 0, 3000).unwrap()[0];
+        assert_eq!(loaded.outcome, SessionOutcome::Stopped);
+        assert_eq!(loaded.planned_secs, DurationSecs::new(1500));
+        assert_eq!(loaded.duration_secs, DurationSecs::new(600));
+    }
+
+    #[test]
+    fn test_alert_config_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+        let mut config = Config {
+            first_warning_secs: 0,
+            second_warning_secs: 45,
+            ..Config::default()
+        };
+        config.short_break_alert.bells = 4;
+        config.long_break_alert.flash = true;
+        config.quiet_hours.enabled = true;
+        config.quiet_hours.start_hour = 21;
+        config.desktop_notification = DesktopNotification::Osc777;
+        config.window_title = true;
+        config.theme = "solarized".to_string();
+        config.big_clock = true;
+        save_config(&db.conn, &config).unwrap();
+
+        let loaded = get_config(&db.conn).unwrap();
+        assert_eq!(loaded.first_warning_secs, 0);
+        assert_eq!(loaded.second_warning_secs, 45);
+        assert_eq!(loaded.short_break_alert, config.short_break_alert);
+        assert_eq!(loaded.long_break_alert, config.long_break_alert);
+        assert_eq!(loaded.work_alert, config.work_alert);
+        assert_eq!(loaded.quiet_hours, config.quiet_hours);
+        assert_eq!(loaded.desktop_notification, DesktopNotification::Osc777);
+        assert!(loaded.window_title);
+        assert_eq!(loaded.theme, "solarized");
+        assert!(loaded.big_clock);
+    }
+
+    #[test]
+    fn test_focus_pause_config_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(get_config(&db.conn).unwrap().focus_pause_secs, 0);
+
+        let config = Config {
+            focus_pause_secs: 120,
+            ..Config::default()
+        };
+        save_config(&db.conn, &config).unwrap();
+        assert_eq!(get_config(&db.conn).unwrap().focus_pause_secs, 120);
+    }
+
+    #[test]
+    fn test_overtime_config_and_session_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+        let config = Config {
+            overtime: true,
+            ..Config::default()
+        };
+        save_config(&db.conn, &config).unwrap();
+        assert!(get_config(&db.conn).unwrap().overtime);
+
+        let mut session = Session::builder()
+            .name("Ran over")
+            .category("coding")
+            .started_at(Timestamp::new(1000))
+            .ended_at(Timestamp::new(2800))
+            .duration_secs(DurationSecs::new(1800))
+            .build()
+            .unwrap();
+        session.planned_secs = DurationSecs::new(1500);
+        session.overtime_secs = DurationSecs::new(300);
+        save_session(&db.conn, &session).unwrap();
+
+        let loaded = &get_sessions_in_range(&db.conn, 0, 3000).unwrap()[0];
+        assert_eq!(loaded.planned_secs, DurationSecs::new(1500));
+        assert_eq!(loaded.overtime_secs, DurationSecs::new(300));
+    }
+
+    #[test]
+    fn test_time_by_category_counts_completions() {
+        let db = Database::open_in_memory().unwrap();
+        for (i, outcome) in [
+            SessionOutcome::Completed,
+            SessionOutcome::Stopped,
+            SessionOutcome::Completed,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let session = Session {
+                id: None,
+                name: format!("Session {}", i),
+                description: None,
+                category: "coding".to_string(),
+                started_at: Timestamp::new(1000 + i as i64 * 2000),
+                ended_at: Timestamp::new(2500 + i as i64 * 2000),
+                duration_secs: DurationSecs::new(1500),
+                planned_secs: DurationSecs::new(1500),
+                overtime_secs: DurationSecs::default(),
+                outcome,
+                task_id: None,
+                profile: None,
+                mode: TimerMode::Pomodoro,
+                segments: Vec::new(),
+                interruptions: Vec::new(),
+            };
+            save_session(&db.conn, &session).unwrap();
+        }
+
+        let totals = get_time_by_category(&db.conn, 0, i64::MAX).unwrap();
+        assert_eq!(totals[0].session_count, 3);
+        assert_eq!(totals[0].completed_count, 2);
+    }
+
+    #[test]
+    fn test_checkpoint_recover_and_clear() {
+        use crate::models::InterruptionKind;
+
+        let db = Database::open_in_memory().unwrap();
+        assert!(recover_checkpoint(&db.conn).unwrap().is_none());
+
+        let mut session = Session::builder()
+            .name("In flight")
+            .category("coding")
+            .started_at(Timestamp::new(1000))
+            .ended_at(Timestamp::new(1300))
+            .duration_secs(DurationSecs::new(300))
+            .build()
+            .unwrap();
+        session.planned_secs = DurationSecs::new(1500);
+        save_checkpoint(&db.conn, &session).unwrap();
+
+        // Later checkpoints replace earlier ones
+        session.ended_at = Timestamp::new(1400);
+        session.duration_secs = DurationSecs::new(400);
+        session.segments = vec![segment(1000, 1100), segment(1200, 1400)];
+        session.interruptions = vec![Interruption {
+            kind: InterruptionKind::External,
+            note: Some("door".to_string()),
+            occurred_at: Timestamp::new(1100),
+        }];
+        save_checkpoint(&db.conn, &session).unwrap();
+
+        let recovered = recover_checkpoint(&db.conn).unwrap().unwrap();
+        assert!(recovered.id.is_some());
+        assert_eq!(recovered.name, "In flight");
+        assert_eq!(recovered.ended_at, Timestamp::new(1400));
+        assert_eq!(recovered.duration_secs, DurationSecs::new(400));
+        assert_eq!(recovered.planned_secs, DurationSecs::new(1500));
+        assert_eq!(recovered.outcome, SessionOutcome::Recovered);
+
+        // The saved session keeps its pauses and interruptions
+        let saved = &get_sessions_in_range(&db.conn, 0, i64::MAX).unwrap()[0];
+        assert_eq!(saved.outcome, SessionOutcome::Recovered);
+        assert_eq!(saved.segments, session.segments);
+        assert_eq!(saved.interruptions[0].note.as_deref(), Some("door"));
+
+        // Recovering consumes the checkpoint
+        assert!(load_checkpoint(&db.conn).unwrap().is_none());
+        assert!(recover_checkpoint(&db.conn).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_recovery_keeps_the_checkpoint() {
+        let db = Database::open_in_memory().unwrap();
+        let mut session = Session::builder()
+            .name("In flight")
+            .category("coding")
+            .started_at(Timestamp::new(1000))
+            .ended_at(Timestamp::new(1300))
+            .duration_secs(DurationSecs::new(300))
+            .build()
+            .unwrap();
+        session.segments = vec![segment(1000, 1300)];
+        save_checkpoint(&db.conn, &session).unwrap();
+
+        // Make the save into the history fail
+        db.conn
+            .execute_batch(
+                "CREATE TEMP TRIGGER refuse BEFORE INSERT ON session_segments
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+            )
+            .unwrap();
+        assert!(recover_checkpoint(&db.conn).is_err());
+
+        let sessions: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(sessions, 0);
+        let kept = load_checkpoint(&db.conn).unwrap().unwrap();
+        assert_eq!(kept.segments, session.segments);
+    }
+
+    #[test]
+    fn test_task_queue_order_and_swap() {
+        let db = Database::open_in_memory().unwrap();
+        let first = create_task(&db.conn, "First", "work", 2).unwrap();
+        let second = create_task(&db.conn, "Second", "coding", 4).unwrap();
+
+        let tasks = get_tasks(&db.conn, 0).unwrap();
+        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["First", "Second"]);
+
+        swap_task_positions(&db.conn, first, second).unwrap();
+        let tasks = get_tasks(&db.conn, 0).unwrap();
+        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Second", "First"]);
+    }
+
+    #[test]
+    fn test_cycle_steps_crud_and_order() {
+        let db = Database::open_in_memory().unwrap();
+        let step = |label: &str, kind: PhaseKind, minutes: i64| CycleStep {
+            id: None,
+            label: label.to_string(),
+            kind,
+            duration_secs: minutes * 60,
+        };
+        let warm_up = create_cycle_step(&db.conn, &step("Warm-up", PhaseKind::Work, 5)).unwrap();
+        let work = create_cycle_step(&db.conn, &step("Work", PhaseKind::Work, 50)).unwrap();
+        create_cycle_step(&db.conn, &step("Break", PhaseKind::LongBreak, 30)).unwrap();
+
+        swap_cycle_step_positions(&db.conn, warm_up, work).unwrap();
+        let steps = get_cycle_steps(&db.conn).unwrap();
+        let labels: Vec<&str> = steps.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["Work", "Warm-up", "Break"]);
+        assert_eq!(steps[2].kind, PhaseKind::LongBreak);
+
+        let edited = CycleStep {
+            kind: PhaseKind::ShortBreak,
+            duration_secs: 10 * 60,
+            ..steps[2].clone()
+        };
+        update_cycle_step(&db.conn, &edited).unwrap();
+        delete_cycle_step(&db.conn, warm_up).unwrap();
+
+        let steps = get_cycle_steps(&db.conn).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1], edited);
+    }
+
+    #[test]
+    fn test_hooks_crud() {
+        let db = Database::open_in_memory().unwrap();
+        let hook = |event: HookEvent, command: &str| Hook {
+            id: None,
+            event,
+            command: command.to_string(),
+            timeout_secs: 5,
+        };
+        let dnd = create_hook(&db.conn, &hook(HookEvent::WorkStarted, "dnd on")).unwrap();
+        create_hook(&db.conn, &hook(HookEvent::BreakStarted, "dnd off")).unwrap();
+
+        let hooks = get_hooks(&db.conn).unwrap();
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].event, HookEvent::WorkStarted);
+        assert_eq!(hooks[0].command, "dnd on");
+
+        let edited = Hook {
+            event: HookEvent::Resumed,
+            timeout_secs: 30,
+            ..hooks[0].clone()
+        };
+        update_hook(&db.conn, &edited).unwrap();
+        assert!(get_hooks(&db.conn).unwrap().contains(&edited));
+
+        delete_hook(&db.conn, dnd).unwrap();
+        let hooks = get_hooks(&db.conn).unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].command, "dnd off");
+    }
+
+    #[test]
+    fn test_webhook_queue_survives_until_delivered() {
+        let db = Database::open_in_memory().unwrap();
+        let webhook = Webhook {
+            id: None,
+            url: "http://127.0.0.1:8080/events".to_string(),
+            headers: "X-Team: core".to_string(),
+            events: vec![HookEvent::WorkCompleted],
+        };
+        let id = create_webhook(&db.conn, &webhook).unwrap();
+        let stored = get_webhooks(&db.conn).unwrap();
+        assert_eq!(
+            stored,
+            vec![Webhook {
+                id: Some(id),
+                ..webhook
+            }]
+        );
+
+        let delivery = |at: i64| WebhookDelivery {
+            id: None,
+            webhook_id: id,
+            payload: "{}".to_string(),
+            attempts: 0,
+            next_attempt_at: Timestamp::new(at),
+            last_error: None,
+        };
+        let first = queue_delivery(&db.conn, &delivery(100)).unwrap();
+        queue_delivery(&db.conn, &delivery(500)).unwrap();
+
+        let due = get_due_deliveries(&db.conn, Timestamp::new(200)).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, Some(first));
+
+        let failed = WebhookDelivery {
+            attempts: 1,
+            next_attempt_at: Timestamp::new(300),
+            last_error: Some("refused".to_string()),
+            ..due[0].clone()
+        };
+        reschedule_delivery(&db.conn, &failed).unwrap();
+        assert!(
+            get_due_deliveries(&db.conn, Timestamp::new(200))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            get_due_deliveries(&db.conn, Timestamp::new(300)).unwrap(),
+            vec![failed]
+        );
+
+        delete_delivery(&db.conn, first).unwrap();
+        assert_eq!(
+            get_due_deliveries(&db.conn, Timestamp::new(1000))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        delete_webhook(&db.conn, id).unwrap();
+        assert!(get_webhooks(&db.conn).unwrap().is_empty());
+        assert!(
+            get_due_deliveries(&db.conn, Timestamp::new(1000))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_failed_deliveries_wait_to_be_retried() {
+        let db = Database::open_in_memory().unwrap();
+        let webhook = Webhook {
+            id: None,
+            url: "https://example.com/events".to_string(),
+            headers: String::new(),
+            events: Vec::new(),
+        };
+        let id = create_webhook(&db.conn, &webhook).unwrap();
+        let failed = WebhookDelivery {
+            id: None,
+            webhook_id: id,
+            payload: "{}".to_string(),
+            attempts: WebhookDelivery::MAX_ATTEMPTS,
+            next_attempt_at: Timestamp::new(100),
+            last_error: Some("refused".to_string()),
+        };
+        queue_delivery(&db.conn, &failed).unwrap();
+
+        // Kept and counted, but no longer attempted
+        let now = Timestamp::new(1000);
+        assert!(get_due_deliveries(&db.conn, now).unwrap().is_empty());
+        assert_eq!(
+            count_failed_deliveries(&db.conn).unwrap().get(&id),
+            Some(&1)
+        );
+
+        assert_eq!(retry_failed_deliveries(&db.conn, id, now).unwrap(), 1);
+        let due = get_due_deliveries(&db.conn, now).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].attempts, 0);
+        assert!(count_failed_deliveries(&db.conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_task_pomodoros_and_completion() {
+        let db = Database::open_in_memory().unwrap();
+        let id = create_task(&db.conn, "Report", "work", 2).unwrap();
+
+        for (i, outcome) in [
+            SessionOutcome::Completed,
+            SessionOutcome::Stopped,
+            SessionOutcome::Completed,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut session = Session::builder()
+                .name("Report")
+                .category("work")
+                .started_at(Timestamp::new(1000 + i as i64 * 2000))
+                .ended_at(Timestamp::new(2500 + i as i64 * 2000))
+                .duration_secs(DurationSecs::new(1500))
+                .task_id(Some(id))
+                .build()
+                .unwrap();
+            session.outcome = outcome;
+            save_session(&db.conn, &session).unwrap();
+        }
+
+        // Only completed sessions count as pomodoros
+        let task = &get_tasks(&db.conn, 0).unwrap()[0];
+        assert_eq!(task.pomodoros, 2);
+        assert!(task.is_open());
+
+        complete_task(&db.conn, id, Timestamp::new(9000)).unwrap();
+        let task = &get_tasks(&db.conn, 0).unwrap()[0];
+        assert_eq!(task.status, TaskStatus::Done);
+        assert_eq!(task.pomodoros, 2);
+        assert_eq!(task.completed_at, Some(Timestamp::new(9000)));
+
+        // Finished tasks older than the cutoff are hidden
+        assert!(get_tasks(&db.conn, 10_000).unwrap().is_empty());
+
+        // Deleting unlinks sessions rather than deleting them
+        delete_task(&db.conn, id).unwrap();
+        let sessions = get_sessions_in_range(&db.conn, 0, i64::MAX).unwrap();
+        assert_eq!(sessions.len(), 3);
+        assert!(sessions.iter().all(|s| s.task_id.is_none()));
+    }
+
+    #[test]
+    fn test_estimates_by_category_and_worst_offenders() {
+        let db = Database::open_in_memory().unwrap();
+        let close = create_task(&db.conn, "Close", "work", 2).unwrap();
+        let far = create_task(&db.conn, "Far", "work", 1).unwrap();
+        let unfinished = create_task(&db.conn, "Unfinished", "work", 1).unwrap();
+
+        for (i, task) in [close, close, far, far, far, far, unfinished]
+            .into_iter()
+            .enumerate()
+        {
+            let session = Session::builder()
+                .name("Pomodoro")
+                .category("work")
+                .started_at(Timestamp::new(1000 + i as i64 * 2000))
+                .ended_at(Timestamp::new(2500 + i as i64 * 2000))
+                .duration_secs(DurationSecs::new(1500))
+                .task_id(Some(task))
+                .build()
+                .unwrap();
+            save_session(&db.conn, &session).unwrap();
+        }
+        complete_task(&db.conn, close, Timestamp::new(20_000)).unwrap();
+        complete_task(&db.conn, far, Timestamp::new(21_000)).unwrap();
+
+        let stats = get_estimates_by_category(&db.conn, 0, i64::MAX).unwrap();
+        assert_eq!(
+            stats,
+            vec![EstimateStat {
+                name: "work".to_string(),
+                task_count: 2,
+                estimated: 3,
+                actual: 6,
+            }]
+        );
+
+        let tasks = get_finished_tasks(&db.conn, 0, i64::MAX).unwrap();
+        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Far", "Close"]);
+        assert_eq!(tasks[0].estimate_error(), 3);
+
+        // Outside the period nothing is reported
+        assert!(get_finished_tasks(&db.conn, 0, 20_000).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_profiles_seeded_and_drive_config() {
+        let db = Database::open_in_memory().unwrap();
+
+        // First run keeps the configured durations as the active profile
+        let profiles = get_profiles(&db.conn).unwrap();
+        assert_eq!(profiles.len(), 1 + Profile::PRESETS.len());
+        let config = get_config(&db.conn).unwrap();
+        let active = profiles
+            .iter()
+            .find(|p| p.id == config.active_profile)
+            .unwrap();
+        assert_eq!(active.name, "Pomodoro");
+
+        // Switching profile loads its durations
+        let deep = profiles.iter().find(|p| p.name == "Deep Work").unwrap();
+        let mut config = config;
+        deep.apply_to(&mut config);
+        save_config(&db.conn, &config).unwrap();
+        let loaded = get_config(&db.conn).unwrap();
+        assert_eq!(loaded.work_duration_secs, 90 * 60);
+        assert_eq!(loaded.sessions_until_long_break, 2);
+
+        // Editing durations updates the active profile
+        let edited = Config {
+            work_duration_secs: 80 * 60,
+            ..loaded
+        };
+        save_config(&db.conn, &edited).unwrap();
+        let profiles = get_profiles(&db.conn).unwrap();
+        let deep = profiles.iter().find(|p| p.name == "Deep Work").unwrap();
+        assert_eq!(deep.work_duration_secs, 80 * 60);
+    }
+
+    #[test]
+    fn test_time_by_profile() {
+        let db = Database::open_in_memory().unwrap();
+        for (i, profile) in [Some("52/17"), Some("52/17"), None].into_iter().enumerate() {
+            let mut session = Session::builder()
+                .name("Work")
+                .category("work")
+                .started_at(Timestamp::new(1000 + i as i64 * 4000))
+                .ended_at(Timestamp::new(4120 + i as i64 * 4000))
+                .duration_secs(DurationSecs::new(3120))
+                .build()
+                .unwrap();
+            session.profile = profile.map(str::to_string);
+            save_session(&db.conn, &session).unwrap();
+        }
+
+        let stats = get_time_by_profile(&db.conn, 0, i64::MAX).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, "52/17");
+        assert_eq!(stats[0].session_count, 2);
+        assert_eq!(stats[0].mean_secs(), 3120);
+        assert_eq!(stats[1].name, "(none)");
+    }
 }
@@ -1,9 +1,14 @@
 use rusqlite::Connection;
 
-use crate::models::{Category, Config, format_hex_color};
+use crate::models::{Category, Config, Profile, format_hex_color};
+
+use super::queries;
 
 /// Initialize the database schema
 pub fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    // SQLite only honours the ON DELETE clauses below when asked to, per connection
+    conn.pragma_update(None, "foreign_keys", true)?;
+
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS sessions (
@@ -14,25 +19,157 @@ pub fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
             started_at INTEGER NOT NULL,
             ended_at INTEGER NOT NULL,
             duration_secs INTEGER NOT NULL,
-            created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+            created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+            planned_secs INTEGER NOT NULL DEFAULT 0,
+            outcome TEXT NOT NULL DEFAULT 'completed',
+            task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL,
+            profile TEXT,
+            mode TEXT NOT NULL DEFAULT 'pomodoro',
+            overtime_secs INTEGER NOT NULL DEFAULT 0
         );
 
         CREATE INDEX IF NOT EXISTS idx_sessions_started_at ON sessions(started_at);
         CREATE INDEX IF NOT EXISTS idx_sessions_category ON sessions(category);
 
+        CREATE TABLE IF NOT EXISTS session_segments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_session_segments_session_id
+            ON session_segments(session_id);
+
+        CREATE TABLE IF NOT EXISTS interruptions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            kind TEXT NOT NULL,
+            note TEXT,
+            occurred_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_interruptions_session_id ON interruptions(session_id);
+        CREATE INDEX IF NOT EXISTS idx_interruptions_occurred_at ON interruptions(occurred_at);
+
+        CREATE TABLE IF NOT EXISTS breaks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER NOT NULL,
+            skipped INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_breaks_started_at ON breaks(started_at);
+
+        CREATE TABLE IF NOT EXISTS active_session (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            name TEXT NOT NULL,
+            description TEXT,
+            category TEXT NOT NULL,
+            started_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            duration_secs INTEGER NOT NULL,
+            planned_secs INTEGER NOT NULL,
+            task_id INTEGER,
+            profile TEXT,
+            mode TEXT NOT NULL DEFAULT 'pomodoro',
+            overtime_secs INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS active_session_segments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS active_session_interruptions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            note TEXT,
+            occurred_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            work_duration_secs INTEGER NOT NULL,
+            short_break_secs INTEGER NOT NULL,
+            long_break_secs INTEGER NOT NULL,
+            sessions_until_long_break INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS cycle_steps (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            label TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            duration_secs INTEGER NOT NULL,
+            position INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS hooks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event TEXT NOT NULL,
+            command TEXT NOT NULL,
+            timeout_secs INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS webhooks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            url TEXT NOT NULL,
+            headers TEXT NOT NULL DEFAULT '',
+            events TEXT NOT NULL DEFAULT ''
+        );
+
+        CREATE TABLE IF NOT EXISTS webhook_queue (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            webhook_id INTEGER NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+            payload TEXT NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at INTEGER NOT NULL,
+            last_error TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_webhook_queue_next_attempt_at
+            ON webhook_queue(next_attempt_at);
+
+        CREATE TABLE IF NOT EXISTS tasks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            category TEXT NOT NULL,
+            estimate INTEGER NOT NULL,
+            status TEXT NOT NULL DEFAULT 'open',
+            position INTEGER NOT NULL,
+            actual INTEGER,
+            created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+            completed_at INTEGER
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status, position);
+
         CREATE TABLE IF NOT EXISTS categories (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             name TEXT NOT NULL UNIQUE,
-            color TEXT NOT NULL DEFAULT '#808080'
+            color TEXT NOT NULL DEFAULT '#808080',
+            work_duration_secs INTEGER,
+            short_break_secs INTEGER,
+            long_break_secs INTEGER
         );
 
         CREATE TABLE IF NOT EXISTS config (
             key TEXT PRIMARY KEY,
             value INTEGER NOT NULL
         );
+
+        CREATE TABLE IF NOT EXISTS preferences (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );
         ",
     )?;
 
+    migrate(conn)?;
+
     // Seed default categories if table is empty
     let count: i64 = conn.query_row("SELECT COUNT(*) FROM categories", [], |row| row.get(0))?;
 
@@ -63,5 +200,167 @@ pub fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
         ])?;
     }
 
+    // Seed profiles, starting with the durations configured so far
+    let profile_count: i64 =
+        conn.query_row("SELECT COUNT(*) FROM profiles", [], |row| row.get(0))?;
+
+    if profile_count == 0 {
+        let mut config = queries::get_config(conn)?;
+        let id = queries::create_profile(conn, &Profile::from_config("Pomodoro", &config))?;
+        for (name, work, short, long, sessions) in Profile::PRESETS {
+            queries::create_profile(
+                conn,
+                &Profile {
+                    id: None,
+                    name: name.to_string(),
+                    work_duration_secs: work,
+                    short_break_secs: short,
+                    long_break_secs: long,
+                    sessions_until_long_break: sessions,
+                },
+            )?;
+        }
+        config.active_profile = Some(id);
+        queries::save_config(conn, &config)?;
+    }
+
     Ok(())
 }
+
+/// Bring tables created by older versions up to date
+fn migrate(conn: &Connection) -> rusqlite::Result<()> {
+    if add_column_if_missing(
+        conn,
+        "sessions",
+        "planned_secs",
+        "INTEGER NOT NULL DEFAULT 0",
+    )? {
+        // Older versions did not distinguish planned from actual time
+        conn.execute("UPDATE sessions SET planned_secs = duration_secs", [])?;
+    }
+    add_column_if_missing(
+        conn,
+        "sessions",
+        "outcome",
+        "TEXT NOT NULL DEFAULT 'completed'",
+    )?;
+    add_column_if_missing(
+        conn,
+        "sessions",
+        "task_id",
+        "INTEGER REFERENCES tasks(id) ON DELETE SET NULL",
+    )?;
+    add_column_if_missing(conn, "active_session", "task_id", "INTEGER")?;
+    add_column_if_missing(conn, "sessions", "profile", "TEXT")?;
+    add_column_if_missing(conn, "active_session", "profile", "TEXT")?;
+    add_column_if_missing(conn, "sessions", "mode", "TEXT NOT NULL DEFAULT 'pomodoro'")?;
+    add_column_if_missing(conn, "categories", "work_duration_secs", "INTEGER")?;
+    add_column_if_missing(conn, "categories", "short_break_secs", "INTEGER")?;
+    add_column_if_missing(conn, "categories", "long_break_secs", "INTEGER")?;
+    add_column_if_missing(
+        conn,
+        "active_session",
+        "mode",
+        "TEXT NOT NULL DEFAULT 'pomodoro'",
+    )?;
+    add_column_if_missing(
+        conn,
+        "sessions",
+        "overtime_secs",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        conn,
+        "active_session",
+        "overtime_secs",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+
+    Ok(())
+}
+
+/// Add a column to an existing table, returning whether it was added
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> rusqlite::Result<bool> {
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+        [table, column],
+        |row| row.get(0),
+    )?;
+    if exists {
+        return Ok(false);
+    }
+
+    conn.execute(
+        &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+        [],
+    )?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrates_legacy_sessions_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                description TEXT,
+                category TEXT NOT NULL DEFAULT 'uncategorized',
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER NOT NULL,
+                duration_secs INTEGER NOT NULL,
+                created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+            );
+            INSERT INTO sessions (name, started_at, ended_at, duration_secs)
+                VALUES ('old', 0, 1500, 1500);",
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+        // Running again must be a no-op
+        init_schema(&conn).unwrap();
+
+        let (planned, outcome): (i64, String) = conn
+            .query_row(
+                "SELECT planned_secs, outcome FROM sessions WHERE name = 'old'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(planned, 1500);
+        assert_eq!(outcome, "completed");
+    }
+
+    #[test]
+    fn test_every_foreign_key_says_what_a_delete_does() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+
+        // With foreign keys enforced, a reference without an action would refuse the delete
+        let mut stmt = conn
+            .prepare(
+                "SELECT m.name, f.\"from\", f.on_delete
+                 FROM sqlite_master m, pragma_foreign_key_list(m.name) f
+                 WHERE m.type = 'table'",
+            )
+            .unwrap();
+        let keys: Vec<(String, String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(!keys.is_empty());
+        for (table, column, on_delete) in keys {
+            assert_ne!(on_delete, "NO ACTION", "{}.{}", table, column);
+        }
+    }
+}
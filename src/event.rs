@@ -1,4 +1,4 @@
-use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+use crossterm::event::{self, Event, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind};
 use std::time::Duration;
 
 /// Application events
@@ -7,6 +7,16 @@ pub enum AppEvent {
     Key(KeyEvent),
     /// A tick occurred (for updating the timer display)
     Tick,
+    /// The terminal window gained focus
+    FocusGained,
+    /// The terminal window lost focus
+    FocusLost,
+    /// The terminal was resized
+    Resize,
+    /// Text was pasted (bracketed paste)
+    Paste(String),
+    /// A mouse button was pressed or the wheel was scrolled
+    Mouse(MouseEvent),
 }
 
 /// Poll for events with a timeout.
@@ -17,6 +27,19 @@ pub fn poll_event(tick_rate: Duration) -> color_eyre::Result<Option<AppEvent>> {
     if event::poll(tick_rate)? {
         match event::read()? {
             Event::Key(key) if key.kind == KeyEventKind::Press => Ok(Some(AppEvent::Key(key))),
+            Event::FocusGained => Ok(Some(AppEvent::FocusGained)),
+            Event::FocusLost => Ok(Some(AppEvent::FocusLost)),
+            Event::Resize(..) => Ok(Some(AppEvent::Resize)),
+            Event::Paste(text) => Ok(Some(AppEvent::Paste(text))),
+            // Moves, drags and releases are not used, so don't wake the loop for them
+            Event::Mouse(mouse)
+                if matches!(
+                    mouse.kind,
+                    MouseEventKind::Down(_) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                ) =>
+            {
+                Ok(Some(AppEvent::Mouse(mouse)))
+            }
             _ => Ok(None),
         }
     } else {
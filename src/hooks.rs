@@ -0,0 +1,300 @@
+use std::io::Write;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::models::{Hook, HookEvent, Session, Timestamp};
+use crate::timer::TimerPhase;
+
+/// How often a running hook is checked for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Why a hook command did not succeed
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("could not be started: {0}")]
+    Spawn(std::io::Error),
+
+    #[error("could not be waited on: {0}")]
+    Wait(std::io::Error),
+
+    #[error("timed out after {0}s")]
+    Timeout(u64),
+
+    #[error("exited with {0}")]
+    Failed(ExitStatus),
+}
+
+/// A hook that failed, reported back to the event loop
+#[derive(Debug)]
+pub struct HookFailure {
+    pub command: String,
+    pub error: HookError,
+}
+
+/// Details of a timer event handed to hooks
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub event: HookEvent,
+    pub timestamp: Timestamp,
+    pub phase: TimerPhase,
+    /// Time run so far in the current phase
+    pub elapsed_secs: i64,
+    /// The session the event belongs to, if any
+    pub session: Option<Session>,
+}
+
+impl HookContext {
+    /// Environment variables describing the event, all prefixed with `GRINDSTONE_`
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("GRINDSTONE_EVENT", self.event.as_str().to_string()),
+            (
+                "GRINDSTONE_TIMESTAMP",
+                i64::from(self.timestamp).to_string(),
+            ),
+            ("GRINDSTONE_PHASE", self.phase.as_str().to_string()),
+            ("GRINDSTONE_ELAPSED_SECS", self.elapsed_secs.to_string()),
+        ];
+        if let Some(session) = &self.session {
+            vars.extend([
+                ("GRINDSTONE_SESSION_NAME", session.name.clone()),
+                ("GRINDSTONE_SESSION_CATEGORY", session.category.clone()),
+                (
+                    "GRINDSTONE_SESSION_DESCRIPTION",
+                    session.description.clone().unwrap_or_default(),
+                ),
+                (
+                    "GRINDSTONE_SESSION_PLANNED_SECS",
+                    session.planned_secs.as_secs().to_string(),
+                ),
+                (
+                    "GRINDSTONE_SESSION_DURATION_SECS",
+                    session.duration_secs.as_secs().to_string(),
+                ),
+                (
+                    "GRINDSTONE_SESSION_OUTCOME",
+                    session.outcome.as_str().to_string(),
+                ),
+            ]);
+        }
+        vars
+    }
+
+    /// The event as a single-line JSON object
+    pub fn to_json(&self) -> String {
+        let session = match &self.session {
+            Some(s) => format!(
+                "{{\"name\":{},\"category\":{},\"description\":{},\"started_at\":{},\
+                 \"planned_secs\":{},\"duration_secs\":{},\"overtime_secs\":{},\
+                 \"outcome\":{},\"mode\":{}}}",
+                json_string(&s.name),
+                json_string(&s.category),
+                s.description
+                    .as_deref()
+                    .map_or("null".to_string(), json_string),
+                i64::from(s.started_at),
+                s.planned_secs.as_secs(),
+                s.duration_secs.as_secs(),
+                s.overtime_secs.as_secs(),
+                json_string(s.outcome.as_str()),
+                json_string(s.mode.as_str()),
+            ),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"event\":{},\"timestamp\":{},\"phase\":{},\"elapsed_secs\":{},\"session\":{}}}",
+            json_string(self.event.as_str()),
+            i64::from(self.timestamp),
+            json_string(self.phase.as_str()),
+            self.elapsed_secs,
+            session,
+        )
+    }
+}
+
+/// Quote and escape a string as a JSON string literal
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Runs hook commands in the background and collects their failures
+pub struct HookRunner {
+    sender: Sender<HookFailure>,
+    receiver: Receiver<HookFailure>,
+}
+
+impl Default for HookRunner {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver }
+    }
+}
+
+impl HookRunner {
+    /// Start a hook on its own thread, without waiting for it to finish
+    pub fn run(&self, hook: &Hook, context: &HookContext) {
+        let command = hook.command.clone();
+        let timeout = Duration::from_secs(hook.timeout_secs.max(1) as u64);
+        let env = context.env_vars();
+        let input = context.to_json();
+        let sender = self.sender.clone();
+
+        std::thread::spawn(move || {
+            if let Err(error) = execute(&command, &env, &input, timeout) {
+                let _ = sender.send(HookFailure { command, error });
+            }
+        });
+    }
+
+    /// Failures reported since the last call
+    pub fn failures(&self) -> Vec<HookFailure> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+/// Run a command through the shell with the event on stdin, killing it after the timeout
+fn execute(
+    command: &str,
+    env: &[(&str, String)],
+    input: &str,
+    timeout: Duration,
+) -> Result<(), HookError> {
+    let mut child = shell(command)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::piped())
+        // Output would draw over the TUI
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(HookError::Spawn)?;
+
+    // Commands are free to ignore their input, so a closed pipe is not an error
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait().map_err(HookError::Wait)? {
+            Some(status) if status.success() => return Ok(()),
+            Some(status) => return Err(HookError::Failed(status)),
+            None if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(HookError::Timeout(timeout.as_secs()));
+            }
+            None => std::thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::models::DurationSecs;
+
+    fn context() -> HookContext {
+        let mut session = Session::builder()
+            .name("Write \"report\"")
+            .category("coding")
+            .started_at(Timestamp::new(1000))
+            .ended_at(Timestamp::new(2500))
+            .duration_secs(DurationSecs::new(1500))
+            .build()
+            .unwrap();
+        session.planned_secs = DurationSecs::new(1500);
+        HookContext {
+            event: HookEvent::WorkCompleted,
+            timestamp: Timestamp::new(2500),
+            phase: TimerPhase::Work,
+            elapsed_secs: 1500,
+            session: Some(session),
+        }
+    }
+
+    fn run(command: &str, timeout: Duration) -> Result<(), HookError> {
+        let context = context();
+        execute(command, &context.env_vars(), &context.to_json(), timeout)
+    }
+
+    #[test]
+    fn test_json_escapes_strings() {
+        assert_eq!(json_string("a \"b\"\n\\"), r#""a \"b\"\n\\""#);
+        let json = context().to_json();
+        assert!(json.starts_with(r#"{"event":"work_completed","timestamp":2500,"#));
+        assert!(json.contains(r#""name":"Write \"report\"""#));
+        assert!(json.contains(r#""description":null"#));
+    }
+
+    #[test]
+    fn test_command_receives_env_and_stdin() {
+        let command = r#"test "$GRINDSTONE_EVENT" = work_completed \
+            && test "$GRINDSTONE_SESSION_CATEGORY" = coding \
+            && grep -q '"planned_secs":1500'"#;
+        assert!(run(command, Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn test_failures_and_timeouts_are_reported() {
+        assert!(matches!(
+            run("exit 3", Duration::from_secs(5)),
+            Err(HookError::Failed(_))
+        ));
+        assert!(matches!(
+            run("sleep 5", Duration::from_millis(100)),
+            Err(HookError::Timeout(_))
+        ));
+    }
+
+    #[test]
+    fn test_runner_reports_failures_without_blocking() {
+        let runner = HookRunner::default();
+        let hook = Hook {
+            id: None,
+            event: HookEvent::WorkCompleted,
+            command: "exit 1".to_string(),
+            timeout_secs: 5,
+        };
+        runner.run(&hook, &context());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut failures = runner.failures();
+        while failures.is_empty() && Instant::now() < deadline {
+            std::thread::sleep(POLL_INTERVAL);
+            failures = runner.failures();
+        }
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].command, "exit 1");
+    }
+}
@@ -0,0 +1,974 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use thiserror::Error;
+
+use crate::app::{SettingsMode, View};
+
+/// Something a key does; what exactly depends on where it is pressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Help,
+    Palette,
+    ShowTimer,
+    ShowHistory,
+    ShowStats,
+    ShowTasks,
+    Confirm,
+    Cancel,
+    New,
+    Edit,
+    Delete,
+    Toggle,
+    SelectNext,
+    SelectPrev,
+    MoveDown,
+    MoveUp,
+    NextField,
+    PrevField,
+    Erase,
+    PrevChoice,
+    NextChoice,
+    PrevTab,
+    NextTab,
+    StartTimer,
+    PauseTimer,
+    ResetTimer,
+    StopTimer,
+    ToggleMode,
+    ToggleBigClock,
+    ExtendPhase,
+    InternalInterruption,
+    ExternalInterruption,
+    CycleProfile,
+    OpenSettings,
+    ToggleBreaks,
+    PrevPeriod,
+    NextPeriod,
+    NextChart,
+    CompleteTask,
+    Retry,
+}
+
+/// An action's name in the key bindings file and its default keys
+struct ActionInfo {
+    action: Action,
+    name: &'static str,
+    default_keys: &'static [&'static str],
+}
+
+const fn action(
+    action: Action,
+    name: &'static str,
+    default_keys: &'static [&'static str],
+) -> ActionInfo {
+    ActionInfo {
+        action,
+        name,
+        default_keys,
+    }
+}
+
+const ACTIONS: &[ActionInfo] = &[
+    action(Action::Quit, "quit", &["q", "Esc"]),
+    action(Action::Help, "help", &["?", "F1"]),
+    action(Action::Palette, "palette", &[":", "ctrl-p"]),
+    action(Action::ShowTimer, "show-timer", &["Tab"]),
+    action(Action::ShowHistory, "show-history", &["h"]),
+    action(Action::ShowStats, "show-stats", &["t"]),
+    action(Action::ShowTasks, "show-tasks", &["a"]),
+    action(Action::Confirm, "confirm", &["Enter"]),
+    action(Action::Cancel, "cancel", &["Esc"]),
+    action(Action::New, "new", &["n"]),
+    action(Action::Edit, "edit", &["e"]),
+    action(Action::Delete, "delete", &["d"]),
+    action(Action::Toggle, "toggle", &["Space"]),
+    action(Action::SelectNext, "next", &["j", "Down"]),
+    action(Action::SelectPrev, "prev", &["k", "Up"]),
+    action(Action::MoveDown, "move-down", &["J"]),
+    action(Action::MoveUp, "move-up", &["K"]),
+    action(Action::NextField, "next-field", &["Tab", "Down"]),
+    action(Action::PrevField, "prev-field", &["Up"]),
+    action(Action::Erase, "erase", &["Backspace"]),
+    action(Action::PrevChoice, "prev-choice", &["Left"]),
+    action(Action::NextChoice, "next-choice", &["Right"]),
+    action(Action::PrevTab, "prev-tab", &["Left", "h"]),
+    action(Action::NextTab, "next-tab", &["Right", "l"]),
+    action(Action::StartTimer, "start", &["s"]),
+    action(Action::PauseTimer, "pause", &["p"]),
+    action(Action::ResetTimer, "reset", &["r"]),
+    action(Action::StopTimer, "stop", &["x"]),
+    action(Action::ToggleMode, "toggle-mode", &["m"]),
+    action(Action::ToggleBigClock, "big-clock", &["b"]),
+    action(Action::ExtendPhase, "extend", &["+", "="]),
+    action(
+        Action::InternalInterruption,
+        "internal-interruption",
+        &["i"],
+    ),
+    action(
+        Action::ExternalInterruption,
+        "external-interruption",
+        &["e"],
+    ),
+    action(Action::CycleProfile, "cycle-profile", &["P"]),
+    action(Action::OpenSettings, "settings", &["c"]),
+    action(Action::ToggleBreaks, "toggle-breaks", &["b"]),
+    action(Action::PrevPeriod, "prev-period", &["Left"]),
+    action(Action::NextPeriod, "next-period", &["Right", "l"]),
+    action(Action::NextChart, "next-chart", &["v"]),
+    action(Action::CompleteTask, "complete-task", &["x"]),
+    action(Action::Retry, "retry", &["r"]),
+];
+
+impl Action {
+    fn info(self) -> &'static ActionInfo {
+        ACTIONS
+            .iter()
+            .find(|info| info.action == self)
+            .expect("every action is listed in ACTIONS")
+    }
+
+    /// The action's name in the key bindings file
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    /// Find an action by its name in the key bindings file
+    pub fn from_name(name: &str) -> Option<Self> {
+        ACTIONS
+            .iter()
+            .find(|info| info.name == name)
+            .map(|info| info.action)
+    }
+}
+
+/// Where a key is pressed: a view, or the dialog open over it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    Timer,
+    History,
+    Stats,
+    Tasks,
+    /// The new session dialog
+    Input,
+    /// The new task dialog
+    TaskInput,
+    /// The interruption note dialog
+    Interruption,
+    /// The session details dialog
+    Detail,
+    /// The command palette
+    Palette,
+    /// The fields of the Timer and Alerts settings tabs
+    SettingsFields,
+    /// The list shown on another settings tab
+    SettingsList(SettingsMode),
+    /// A form opened from a settings list
+    SettingsForm(SettingsMode),
+}
+
+impl From<View> for Context {
+    fn from(view: View) -> Self {
+        match view {
+            View::Timer => Context::Timer,
+            View::History => Context::History,
+            View::Stats => Context::Stats,
+            View::Tasks => Context::Tasks,
+        }
+    }
+}
+
+impl Context {
+    pub const ALL: &[Context] = &[
+        Context::Timer,
+        Context::History,
+        Context::Stats,
+        Context::Tasks,
+        Context::Input,
+        Context::TaskInput,
+        Context::Interruption,
+        Context::Detail,
+        Context::Palette,
+        Context::SettingsFields,
+        Context::SettingsList(SettingsMode::Profiles),
+        Context::SettingsList(SettingsMode::Cycle),
+        Context::SettingsList(SettingsMode::Hooks),
+        Context::SettingsList(SettingsMode::Webhooks),
+        Context::SettingsList(SettingsMode::Categories),
+        Context::SettingsForm(SettingsMode::Profiles),
+        Context::SettingsForm(SettingsMode::Cycle),
+        Context::SettingsForm(SettingsMode::Hooks),
+        Context::SettingsForm(SettingsMode::Webhooks),
+        Context::SettingsForm(SettingsMode::Categories),
+    ];
+
+    /// A heading for the help overlay
+    pub fn title(self) -> &'static str {
+        match self {
+            Context::Timer => "Timer",
+            Context::History => "History",
+            Context::Stats => "Stats",
+            Context::Tasks => "Tasks",
+            Context::Input => "New Session",
+            Context::TaskInput => "New Task",
+            Context::Interruption => "Interruption",
+            Context::Detail => "Session Details",
+            Context::Palette => "Command Palette",
+            Context::SettingsFields => "Settings",
+            Context::SettingsList(SettingsMode::Profiles) => "Settings: Profiles",
+            Context::SettingsList(SettingsMode::Cycle) => "Settings: Cycle",
+            Context::SettingsList(SettingsMode::Hooks) => "Settings: Hooks",
+            Context::SettingsList(SettingsMode::Webhooks) => "Settings: Webhooks",
+            Context::SettingsList(_) => "Settings: Categories",
+            Context::SettingsForm(SettingsMode::Profiles) => "New Profile",
+            Context::SettingsForm(SettingsMode::Cycle) => "Cycle Step",
+            Context::SettingsForm(SettingsMode::Hooks) => "Hook",
+            Context::SettingsForm(SettingsMode::Webhooks) => "Webhook",
+            Context::SettingsForm(_) => "Category",
+        }
+    }
+}
+
+/// What a group of actions does in some contexts, as listed in controls lines and help
+pub struct Binding {
+    pub contexts: &'static [Context],
+    pub actions: &'static [Action],
+    pub description: &'static str,
+    /// Each action's title in the command palette, or none if the palette leaves it out
+    pub palette: &'static [&'static str],
+}
+
+const fn bind(
+    contexts: &'static [Context],
+    actions: &'static [Action],
+    description: &'static str,
+) -> Binding {
+    Binding {
+        contexts,
+        actions,
+        description,
+        palette: &[],
+    }
+}
+
+impl Binding {
+    /// Offer the actions in the command palette under these titles, one per action
+    const fn in_palette(self, titles: &'static [&'static str]) -> Self {
+        Binding {
+            palette: titles,
+            ..self
+        }
+    }
+}
+
+const VIEWS: &[Context] = &[
+    Context::Timer,
+    Context::History,
+    Context::Stats,
+    Context::Tasks,
+];
+
+const SETTINGS_LISTS: &[Context] = &[
+    Context::SettingsList(SettingsMode::Profiles),
+    Context::SettingsList(SettingsMode::Cycle),
+    Context::SettingsList(SettingsMode::Hooks),
+    Context::SettingsList(SettingsMode::Webhooks),
+    Context::SettingsList(SettingsMode::Categories),
+];
+
+/// Settings contexts where the tabs can be switched, i.e. outside forms
+const SETTINGS_TABS: &[Context] = &[
+    Context::SettingsFields,
+    Context::SettingsList(SettingsMode::Profiles),
+    Context::SettingsList(SettingsMode::Cycle),
+    Context::SettingsList(SettingsMode::Hooks),
+    Context::SettingsList(SettingsMode::Webhooks),
+    Context::SettingsList(SettingsMode::Categories),
+];
+
+const SETTINGS_FORMS: &[Context] = &[
+    Context::SettingsForm(SettingsMode::Profiles),
+    Context::SettingsForm(SettingsMode::Cycle),
+    Context::SettingsForm(SettingsMode::Hooks),
+    Context::SettingsForm(SettingsMode::Webhooks),
+    Context::SettingsForm(SettingsMode::Categories),
+];
+
+/// Settings lists whose entries are edited in a form
+const EDITABLE_LISTS: &[Context] = &[
+    Context::SettingsList(SettingsMode::Cycle),
+    Context::SettingsList(SettingsMode::Hooks),
+    Context::SettingsList(SettingsMode::Webhooks),
+    Context::SettingsList(SettingsMode::Categories),
+];
+
+/// Forms with a field that Space steps through
+const TOGGLE_FORMS: &[Context] = &[
+    Context::SettingsForm(SettingsMode::Cycle),
+    Context::SettingsForm(SettingsMode::Hooks),
+    Context::SettingsForm(SettingsMode::Webhooks),
+];
+
+/// Forms with more than one field
+const MULTI_FIELD_FORMS: &[Context] = &[
+    Context::SettingsForm(SettingsMode::Cycle),
+    Context::SettingsForm(SettingsMode::Hooks),
+    Context::SettingsForm(SettingsMode::Webhooks),
+    Context::SettingsForm(SettingsMode::Categories),
+];
+
+/// Every key binding by context, in the order help lists them
+///
+/// The key handlers and the command palette look actions up here, so help, palette and
+/// behaviour cannot disagree. A view's own "show" action is left out of that view, as
+/// pressing it there does nothing.
+pub const BINDINGS: &[Binding] = &[
+    // Timer
+    bind(
+        &[Context::Timer],
+        &[Action::StartTimer],
+        "Start, resume or skip the break",
+    )
+    .in_palette(&["Start, resume or skip the break"]),
+    bind(
+        &[Context::Timer],
+        &[Action::Confirm],
+        "Start the next phase",
+    ),
+    bind(&[Context::Timer], &[Action::PauseTimer], "Pause").in_palette(&["Pause the timer"]),
+    bind(
+        &[Context::Timer],
+        &[Action::StopTimer],
+        "Stop, or finish and break",
+    )
+    .in_palette(&["Stop, or finish and break"]),
+    bind(&[Context::Timer], &[Action::ResetTimer], "Reset the phase")
+        .in_palette(&["Reset the phase"]),
+    bind(
+        &[Context::Timer],
+        &[Action::ExtendPhase],
+        "Extend the phase, or snooze the one that ended",
+    )
+    .in_palette(&["Extend the phase, or snooze the one that ended"]),
+    bind(&[Context::Timer], &[Action::New], "New session").in_palette(&["New session"]),
+    bind(
+        &[Context::Timer],
+        &[Action::InternalInterruption, Action::ExternalInterruption],
+        "Log an internal / external interruption",
+    )
+    .in_palette(&[
+        "Log an internal interruption",
+        "Log an external interruption",
+    ]),
+    bind(&[Context::Timer], &[Action::CycleProfile], "Next profile")
+        .in_palette(&["Switch to the next profile"]),
+    bind(
+        &[Context::Timer],
+        &[Action::ToggleMode],
+        "Switch between countdown and flow",
+    )
+    .in_palette(&["Switch between countdown and flow"]),
+    bind(
+        &[Context::Timer],
+        &[Action::ToggleBigClock],
+        "Big or small clock",
+    )
+    .in_palette(&["Big or small clock"]),
+    bind(&[Context::Timer], &[Action::OpenSettings], "Settings").in_palette(&["Open settings"]),
+    // Switching views, in every view
+    bind(
+        &[Context::History, Context::Stats, Context::Tasks],
+        &[Action::ShowTimer],
+        "Timer",
+    )
+    .in_palette(&["Go to Timer"]),
+    bind(
+        &[Context::Timer, Context::Stats, Context::Tasks],
+        &[Action::ShowHistory],
+        "History",
+    )
+    .in_palette(&["Go to History"]),
+    bind(
+        &[Context::Timer, Context::History, Context::Tasks],
+        &[Action::ShowStats],
+        "Stats",
+    )
+    .in_palette(&["Go to Stats"]),
+    bind(
+        &[Context::Timer, Context::History, Context::Stats],
+        &[Action::ShowTasks],
+        "Tasks",
+    )
+    .in_palette(&["Go to Tasks"]),
+    // History
+    bind(
+        &[Context::History, Context::Tasks],
+        &[Action::SelectNext, Action::SelectPrev],
+        "Navigate",
+    ),
+    bind(&[Context::History], &[Action::Confirm], "Session details")
+        .in_palette(&["Show session details"]),
+    bind(&[Context::History], &[Action::Delete], "Delete the session")
+        .in_palette(&["Delete the selected session"]),
+    bind(
+        &[Context::History],
+        &[Action::ToggleBreaks],
+        "Show or hide breaks",
+    )
+    .in_palette(&["Show or hide breaks"]),
+    // Stats
+    bind(
+        &[Context::Stats],
+        &[Action::PrevPeriod, Action::NextPeriod],
+        "Change period",
+    )
+    .in_palette(&["Previous stats period", "Next stats period"]),
+    bind(&[Context::Stats], &[Action::NextChart], "Next chart").in_palette(&["Next stats chart"]),
+    // Tasks
+    bind(
+        &[Context::Tasks],
+        &[Action::MoveDown, Action::MoveUp],
+        "Reorder",
+    )
+    .in_palette(&["Move the selected task down", "Move the selected task up"]),
+    bind(&[Context::Tasks], &[Action::New], "New task").in_palette(&["New task"]),
+    bind(
+        &[Context::Tasks],
+        &[Action::Confirm],
+        "Start a session on the task",
+    )
+    .in_palette(&["Start a session on the selected task"]),
+    bind(&[Context::Tasks], &[Action::CompleteTask], "Mark done")
+        .in_palette(&["Mark the selected task done"]),
+    bind(&[Context::Tasks], &[Action::Delete], "Delete the task")
+        .in_palette(&["Delete the selected task"]),
+    bind(VIEWS, &[Action::Palette], "Commands"),
+    bind(VIEWS, &[Action::Quit], "Quit").in_palette(&["Quit"]),
+    // Dialogs
+    bind(&[Context::Input], &[Action::Confirm], "Start the session"),
+    bind(&[Context::TaskInput], &[Action::Confirm], "Add the task"),
+    bind(
+        &[Context::Interruption],
+        &[Action::Confirm],
+        "Log the interruption",
+    ),
+    bind(
+        &[Context::Input, Context::TaskInput],
+        &[Action::NextField],
+        "Next field",
+    ),
+    bind(
+        &[Context::Input, Context::TaskInput],
+        &[Action::PrevChoice, Action::NextChoice],
+        "Change the category or task",
+    ),
+    bind(
+        &[Context::Input, Context::TaskInput, Context::Interruption],
+        &[Action::Erase],
+        "Delete a character",
+    ),
+    bind(
+        &[Context::Input, Context::TaskInput, Context::Interruption],
+        &[Action::Cancel],
+        "Cancel",
+    ),
+    bind(&[Context::Detail], &[Action::Quit], "Close"),
+    bind(
+        &[Context::Palette],
+        &[Action::NextField, Action::PrevField],
+        "Next / previous command",
+    ),
+    bind(&[Context::Palette], &[Action::Confirm], "Run the command"),
+    bind(&[Context::Palette], &[Action::Erase], "Delete a character"),
+    bind(&[Context::Palette], &[Action::Cancel], "Close"),
+    // Settings
+    bind(
+        SETTINGS_TABS,
+        &[Action::PrevTab, Action::NextTab],
+        "Switch tab",
+    ),
+    bind(
+        &[Context::SettingsFields],
+        &[Action::NextField, Action::PrevField],
+        "Next / previous field",
+    ),
+    bind(
+        &[Context::SettingsFields],
+        &[Action::Toggle],
+        "Change the option",
+    ),
+    bind(
+        &[Context::SettingsFields],
+        &[Action::Erase],
+        "Delete a digit",
+    ),
+    bind(
+        &[Context::SettingsFields],
+        &[Action::Confirm],
+        "Save and close",
+    ),
+    bind(
+        SETTINGS_LISTS,
+        &[Action::SelectNext, Action::SelectPrev],
+        "Navigate",
+    ),
+    bind(
+        &[Context::SettingsList(SettingsMode::Cycle)],
+        &[Action::MoveDown, Action::MoveUp],
+        "Reorder",
+    ),
+    bind(SETTINGS_LISTS, &[Action::New], "New"),
+    bind(
+        &[Context::SettingsList(SettingsMode::Profiles)],
+        &[Action::Confirm],
+        "Use the profile",
+    ),
+    bind(EDITABLE_LISTS, &[Action::Edit, Action::Confirm], "Edit"),
+    bind(SETTINGS_LISTS, &[Action::Delete], "Delete"),
+    bind(
+        &[Context::SettingsList(SettingsMode::Webhooks)],
+        &[Action::Retry],
+        "Retry failed deliveries",
+    ),
+    bind(
+        &[Context::SettingsList(SettingsMode::Cycle)],
+        &[Action::Toggle],
+        "Turn the cycle on or off",
+    ),
+    bind(
+        &[Context::SettingsFields],
+        &[Action::Cancel],
+        "Close without saving",
+    ),
+    bind(SETTINGS_LISTS, &[Action::Cancel], "Close"),
+    bind(MULTI_FIELD_FORMS, &[Action::NextField], "Next field"),
+    bind(TOGGLE_FORMS, &[Action::Toggle], "Change the option"),
+    bind(
+        &[Context::SettingsForm(SettingsMode::Webhooks)],
+        &[Action::PrevChoice, Action::NextChoice],
+        "Pick an event",
+    ),
+    bind(SETTINGS_FORMS, &[Action::Erase], "Delete a character"),
+    bind(SETTINGS_FORMS, &[Action::Confirm], "Save"),
+    bind(SETTINGS_FORMS, &[Action::Cancel], "Cancel"),
+    // Everywhere
+    bind(Context::ALL, &[Action::Help], "Show or hide this help")
+        .in_palette(&["Show key bindings"]),
+];
+
+/// A key press as bindings see it: Shift is part of the character, so only Ctrl and Alt count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Parse a key name such as `s`, `P`, `Enter`, `Left` or `ctrl-p`
+    ///
+    /// Arrow keys also accept the arrow characters that `Display` uses for them.
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = name.trim();
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if lower.len() > 5 && lower.starts_with("ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[5..];
+            } else if lower.len() > 4 && lower.starts_with("alt-") {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[4..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => match c {
+                '←' => KeyCode::Left,
+                '→' => KeyCode::Right,
+                '↑' => KeyCode::Up,
+                '↓' => KeyCode::Down,
+                _ => KeyCode::Char(c),
+            },
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').map(str::parse) {
+                    Some(Ok(n @ 1..=12)) => KeyCode::F(n),
+                    _ => return None,
+                },
+            },
+        };
+        Some(Self { code, modifiers })
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            modifiers: event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl From<Key> for KeyEvent {
+    fn from(key: Key) -> Self {
+        KeyEvent::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// Why the key bindings file could not be used
+#[derive(Debug, Error)]
+pub enum KeymapError {
+    #[error("could not read key bindings: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("key bindings line {line}: {message}")]
+    Syntax { line: usize, message: String },
+
+    #[error("{key} is bound to both {first} and {second} in {context}")]
+    Conflict {
+        key: Key,
+        first: &'static str,
+        second: &'static str,
+        context: &'static str,
+    },
+}
+
+/// The keys bound to each action
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    keys: HashMap<Action, Vec<Key>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let keys = ACTIONS
+            .iter()
+            .map(|info| {
+                let keys = info
+                    .default_keys
+                    .iter()
+                    .map(|name| Key::parse(name).expect("default keys parse"))
+                    .collect();
+                (info.action, keys)
+            })
+            .collect();
+        Self { keys }
+    }
+}
+
+impl Keymap {
+    /// Load bindings from a file, falling back to the defaults when there is none
+    pub fn load(path: &Path) -> Result<Self, KeymapError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parse `action = key, key` lines over the defaults
+    ///
+    /// A listed action gets exactly the keys given, so `action =` leaves it unbound.
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self, KeymapError> {
+        let mut keymap = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let syntax = |message: String| KeymapError::Syntax {
+                line: i + 1,
+                message,
+            };
+            let (name, keys) = line
+                .split_once('=')
+                .ok_or_else(|| syntax("expected `action = key`".to_string()))?;
+            let action = Action::from_name(name.trim())
+                .ok_or_else(|| syntax(format!("unknown action \"{}\"", name.trim())))?;
+            let keys = keys
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| {
+                    Key::parse(key).ok_or_else(|| syntax(format!("unknown key \"{}\"", key)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            keymap.keys.insert(action, keys);
+        }
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// Refuse a key bound to two actions that can be used in the same place
+    fn check_conflicts(&self) -> Result<(), KeymapError> {
+        for &context in Context::ALL {
+            let actions: Vec<Action> = bindings_in(context)
+                .flat_map(|binding| binding.actions.iter().copied())
+                .collect();
+            for (i, &first) in actions.iter().enumerate() {
+                for &second in &actions[i + 1..] {
+                    let shared_key = self
+                        .keys(first)
+                        .iter()
+                        .find(|key| self.keys(second).contains(key));
+                    if let Some(&key) = shared_key {
+                        return Err(KeymapError::Conflict {
+                            key,
+                            first: first.name(),
+                            second: second.name(),
+                            context: context.title(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The action a key press triggers in a context
+    pub fn action(&self, context: Context, key: Key) -> Option<Action> {
+        bindings_in(context)
+            .flat_map(|binding| binding.actions.iter().copied())
+            .find(|&action| self.keys(action).contains(&key))
+    }
+
+    /// Every binding of a context as (keys, description), for the help overlay
+    ///
+    /// All keys of an action are listed, with the actions of a group split by ` / `.
+    pub fn help(&self, context: Context) -> Vec<(String, &'static str)> {
+        bindings_in(context)
+            .map(|binding| {
+                let keys: Vec<String> = binding
+                    .actions
+                    .iter()
+                    .map(|&action| {
+                        let keys: Vec<String> =
+                            self.keys(action).iter().map(Key::to_string).collect();
+                        if keys.is_empty() {
+                            "(unbound)".to_string()
+                        } else {
+                            keys.join(" ")
+                        }
+                    })
+                    .collect();
+                (keys.join(" / "), binding.description)
+            })
+            .collect()
+    }
+
+    /// A controls line like `[s] Start  [j/k] Navigate`, from the first key of each action
+    ///
+    /// Entries whose actions are all unbound are left out.
+    pub fn hints(&self, entries: &[(&[Action], &str)]) -> String {
+        self.hint_controls(entries)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    /// The controls of a `hints` line, each as the key it stands for and its `[key] Label` text
+    pub fn hint_controls(&self, entries: &[(&[Action], &str)]) -> Vec<(Key, String)> {
+        entries
+            .iter()
+            .filter_map(|(actions, label)| {
+                let keys: Vec<Key> = actions
+                    .iter()
+                    .filter_map(|&action| self.keys(action).first().copied())
+                    .collect();
+                let text = keys
+                    .iter()
+                    .map(Key::to_string)
+                    .collect::<Vec<_>>()
+                    .join("/");
+                keys.first()
+                    .map(|&key| (key, format!("[{}] {}", text, label)))
+            })
+            .collect()
+    }
+
+    /// The footer line of view switches and quit, leaving out what does nothing in `view`
+    pub fn nav_hints(&self, view: View) -> String {
+        let nav = [
+            Action::ShowTimer,
+            Action::ShowHistory,
+            Action::ShowStats,
+            Action::ShowTasks,
+            Action::Palette,
+            Action::Quit,
+        ];
+        let entries: Vec<(&[Action], &str)> = bindings_in(Context::from(view))
+            .filter(|binding| binding.actions.iter().all(|action| nav.contains(action)))
+            .map(|binding| (binding.actions, binding.description))
+            .collect();
+        self.hints(&entries)
+    }
+}
+
+fn bindings_in(context: Context) -> impl Iterator<Item = &'static Binding> {
+    BINDINGS
+        .iter()
+        .filter(move |binding| binding.contexts.contains(&context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str) -> Key {
+        Key::parse(name).unwrap()
+    }
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        Keymap::default().check_conflicts().unwrap();
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        let shifted = KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT);
+        assert_eq!(Key::from(shifted), key("P"));
+        assert_eq!(
+            Key::from(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            key("Ctrl-p")
+        );
+        assert_eq!(key("left"), key("←"));
+        assert_eq!(key("Space").to_string(), "Space");
+        assert_eq!(key("ctrl-alt-x").to_string(), "Ctrl-Alt-x");
+        assert_eq!(key("Enter").to_string(), "Enter");
+        assert_eq!(key("f1").to_string(), "F1");
+        assert!(Key::parse("F13").is_none());
+        assert!(Key::parse("ctrl-").is_none());
+        assert!(Key::parse("Hyper").is_none());
+    }
+
+    #[test]
+    fn test_file_overrides_defaults() {
+        let keymap = Keymap::parse("# mine\n\npause = space, ctrl-o\nreset =\n").unwrap();
+        assert_eq!(
+            keymap.action(Context::Timer, key("Space")),
+            Some(Action::PauseTimer)
+        );
+        assert_eq!(keymap.action(Context::Timer, key("p")), None);
+        assert_eq!(keymap.action(Context::Timer, key("r")), None);
+        // Other actions keep their defaults
+        assert_eq!(
+            keymap.action(Context::Timer, key("s")),
+            Some(Action::StartTimer)
+        );
+        assert_eq!(
+            keymap.hints(&[
+                (&[Action::PauseTimer], "Pause"),
+                (&[Action::ResetTimer], "Reset")
+            ]),
+            "[Space] Pause"
+        );
+    }
+
+    #[test]
+    fn test_bad_files_are_refused() {
+        assert!(matches!(
+            Keymap::parse("pause = s"),
+            Err(KeymapError::Conflict {
+                first: "start",
+                second: "pause",
+                ..
+            })
+        ));
+        // `h` already switches to History from Stats
+        assert!(matches!(
+            Keymap::parse("prev-period = Left, h"),
+            Err(KeymapError::Conflict { .. })
+        ));
+        // The same key may do different things in different views
+        Keymap::parse("complete-task = v").unwrap();
+        assert!(matches!(
+            Keymap::parse("x\n"),
+            Err(KeymapError::Syntax { line: 1, .. })
+        ));
+        assert!(matches!(
+            Keymap::parse("\nwarp = w"),
+            Err(KeymapError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            Keymap::parse("pause = Hyper"),
+            Err(KeymapError::Syntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_help_lists_every_key_of_a_context() {
+        let keymap = Keymap::parse("quit = q").unwrap();
+        assert_eq!(
+            keymap.help(Context::Detail),
+            vec![
+                ("q".to_string(), "Close"),
+                ("? F1".to_string(), "Show or hide this help")
+            ]
+        );
+        let settings = keymap.help(Context::SettingsList(SettingsMode::Categories));
+        assert!(settings.contains(&("e / Enter".to_string(), "Edit")));
+        assert!(settings.contains(&("j ↓ / k ↑".to_string(), "Navigate")));
+    }
+
+    #[test]
+    fn test_nav_hints_leave_out_the_current_view() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.nav_hints(View::Timer),
+            "[h] History  [t] Stats  [a] Tasks  [:] Commands  [q] Quit"
+        );
+        assert_eq!(
+            keymap.nav_hints(View::History),
+            "[Tab] Timer  [t] Stats  [a] Tasks  [:] Commands  [q] Quit"
+        );
+    }
+}
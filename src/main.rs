@@ -4,12 +4,25 @@ mod config;
 mod db;
 mod error;
 mod event;
+mod hooks;
+mod keymap;
 mod models;
+mod mouse;
+mod palette;
+mod report;
+mod terminal;
+mod theme;
 mod timer;
 mod ui;
 mod validation;
+mod webhooks;
 
 use app::App;
+use crossterm::event::{
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture,
+};
+use crossterm::execute;
 use log::LevelFilter;
 use simplelog::{Config as LogConfig, WriteLogger};
 
@@ -26,8 +39,31 @@ fn main() -> color_eyre::Result<()> {
         let _ = WriteLogger::init(LevelFilter::Info, LogConfig::default(), file);
     }
 
+    // Subcommands print a report and exit without starting the TUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("report") {
+        return report::run_estimation_report(args.get(1).map(String::as_str));
+    }
+
+    // Set up before the terminal is taken over, so a failure leaves it as it was
+    let mut app = App::new()?;
+    app.compact = args.iter().any(|arg| arg == "--compact");
+
     let terminal = ratatui::init();
-    let result = App::new()?.run(terminal);
+    // Focus changes drive auto-pause; bracketed paste keeps pasted text out of key bindings
+    let _ = execute!(
+        std::io::stdout(),
+        EnableFocusChange,
+        EnableBracketedPaste,
+        EnableMouseCapture
+    );
+    let result = app.run(terminal);
+    let _ = execute!(
+        std::io::stdout(),
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        DisableFocusChange
+    );
     ratatui::restore();
     result
 }
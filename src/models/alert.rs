@@ -0,0 +1,134 @@
+/// How the end of a phase is announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlertPattern {
+    /// Number of terminal bells to ring
+    pub bells: i64,
+    /// Flash the screen in reverse video
+    pub flash: bool,
+    /// Change the gauge colour once a warning point is passed
+    pub gauge: bool,
+}
+
+impl AlertPattern {
+    pub const MAX_BELLS: i64 = 5;
+}
+
+/// Hours of the day during which audible alerts are suppressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub enabled: bool,
+    /// First quiet hour (0-23)
+    pub start_hour: i64,
+    /// First hour after the quiet period (0-23); may be earlier than the start to span midnight
+    pub end_hour: i64,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_hour: 22,
+            end_hour: 7,
+        }
+    }
+}
+
+impl QuietHours {
+    /// Check if the given hour of the day falls in the quiet period
+    pub fn contains(&self, hour: i64) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if self.start_hour <= self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (0..24).contains(&self.start_hour) && (0..24).contains(&self.end_hour)
+    }
+}
+
+/// Escape sequence used to raise a desktop notification through the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DesktopNotification {
+    #[default]
+    Off,
+    /// `OSC 9`, understood by iTerm2, Windows Terminal, kitty and others
+    Osc9,
+    /// `OSC 777`, understood by rxvt-unicode, foot, Ghostty and VTE-based terminals
+    Osc777,
+}
+
+impl DesktopNotification {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DesktopNotification::Off => "Off",
+            DesktopNotification::Osc9 => "OSC 9",
+            DesktopNotification::Osc777 => "OSC 777",
+        }
+    }
+
+    /// Cycle through the choices, for picking one in settings
+    pub fn next(&self) -> Self {
+        match self {
+            DesktopNotification::Off => DesktopNotification::Osc9,
+            DesktopNotification::Osc9 => DesktopNotification::Osc777,
+            DesktopNotification::Osc777 => DesktopNotification::Off,
+        }
+    }
+
+    /// Number stored in the config table
+    pub fn code(&self) -> i64 {
+        match self {
+            DesktopNotification::Off => 0,
+            DesktopNotification::Osc9 => 9,
+            DesktopNotification::Osc777 => 777,
+        }
+    }
+
+    pub fn from_code(code: i64) -> Self {
+        match code {
+            9 => DesktopNotification::Osc9,
+            777 => DesktopNotification::Osc777,
+            _ => DesktopNotification::Off,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hours_span_midnight() {
+        let quiet = QuietHours {
+            enabled: true,
+            start_hour: 22,
+            end_hour: 7,
+        };
+        assert!(quiet.contains(23));
+        assert!(quiet.contains(0));
+        assert!(!quiet.contains(7));
+        assert!(!quiet.contains(12));
+
+        let disabled = QuietHours {
+            enabled: false,
+            ..quiet
+        };
+        assert!(!disabled.contains(23));
+    }
+
+    #[test]
+    fn test_quiet_hours_within_a_day() {
+        let quiet = QuietHours {
+            enabled: true,
+            start_hour: 13,
+            end_hour: 14,
+        };
+        assert!(quiet.contains(13));
+        assert!(!quiet.contains(14));
+    }
+}
@@ -0,0 +1,119 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+/// Database row ID for a step of a custom phase cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CycleStepId(i64);
+
+impl From<i64> for CycleStepId {
+    fn from(val: i64) -> Self {
+        CycleStepId(val)
+    }
+}
+
+impl From<CycleStepId> for i64 {
+    fn from(id: CycleStepId) -> Self {
+        id.0
+    }
+}
+
+impl ToSql for CycleStepId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+impl FromSql for CycleStepId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(CycleStepId)
+    }
+}
+
+/// What a phase of the cycle counts as: work, a short break or a long break
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhaseKind {
+    #[default]
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PhaseKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PhaseKind::Work => "WORK SESSION",
+            PhaseKind::ShortBreak => "SHORT BREAK",
+            PhaseKind::LongBreak => "LONG BREAK",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PhaseKind::Work => "work",
+            PhaseKind::ShortBreak => "short_break",
+            PhaseKind::LongBreak => "long_break",
+        }
+    }
+
+    pub fn is_break(&self) -> bool {
+        matches!(self, PhaseKind::ShortBreak | PhaseKind::LongBreak)
+    }
+
+    /// Cycle through the phase kinds, for choosing one in a form
+    pub fn next(&self) -> Self {
+        match self {
+            PhaseKind::Work => PhaseKind::ShortBreak,
+            PhaseKind::ShortBreak => PhaseKind::LongBreak,
+            PhaseKind::LongBreak => PhaseKind::Work,
+        }
+    }
+}
+
+impl ToSql for PhaseKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for PhaseKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "work" => Ok(PhaseKind::Work),
+            "short_break" => Ok(PhaseKind::ShortBreak),
+            "long_break" => Ok(PhaseKind::LongBreak),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// One phase of a user-defined cycle, such as "Warm-up" for 5 minutes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleStep {
+    pub id: Option<CycleStepId>,
+    pub label: String,
+    /// Whether the step is tracked as work, a short break or a long break
+    pub kind: PhaseKind,
+    pub duration_secs: i64,
+}
+
+impl CycleStep {
+    /// Format as "Warm-up 5m"
+    pub fn summary(&self) -> String {
+        format!("{} {}m", self.label, self.duration_secs / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let step = CycleStep {
+            id: None,
+            label: "Warm-up".to_string(),
+            kind: PhaseKind::Work,
+            duration_secs: 5 * 60,
+        };
+        assert_eq!(step.summary(), "Warm-up 5m");
+    }
+}
@@ -0,0 +1,126 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+/// Database row ID for a hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookId(i64);
+
+impl From<i64> for HookId {
+    fn from(val: i64) -> Self {
+        HookId(val)
+    }
+}
+
+impl From<HookId> for i64 {
+    fn from(id: HookId) -> Self {
+        id.0
+    }
+}
+
+impl ToSql for HookId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+impl FromSql for HookId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(HookId)
+    }
+}
+
+/// A timer transition that hooks can run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HookEvent {
+    #[default]
+    WorkStarted,
+    Paused,
+    Resumed,
+    WorkCompleted,
+    WorkStopped,
+    BreakStarted,
+    BreakEnded,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 7] = [
+        HookEvent::WorkStarted,
+        HookEvent::Paused,
+        HookEvent::Resumed,
+        HookEvent::WorkCompleted,
+        HookEvent::WorkStopped,
+        HookEvent::BreakStarted,
+        HookEvent::BreakEnded,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::WorkStarted => "work_started",
+            HookEvent::Paused => "paused",
+            HookEvent::Resumed => "resumed",
+            HookEvent::WorkCompleted => "work_completed",
+            HookEvent::WorkStopped => "work_stopped",
+            HookEvent::BreakStarted => "break_started",
+            HookEvent::BreakEnded => "break_ended",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HookEvent::WorkStarted => "Work started",
+            HookEvent::Paused => "Paused",
+            HookEvent::Resumed => "Resumed",
+            HookEvent::WorkCompleted => "Work completed",
+            HookEvent::WorkStopped => "Work stopped",
+            HookEvent::BreakStarted => "Break started",
+            HookEvent::BreakEnded => "Break ended",
+        }
+    }
+
+    /// Look up an event by its stored name
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == s)
+    }
+
+    /// Cycle through the events, for choosing one in a form
+    pub fn next(&self) -> Self {
+        let i = Self::ALL.iter().position(|e| e == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl ToSql for HookEvent {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for HookEvent {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        HookEvent::parse(value.as_str()?).ok_or(FromSqlError::InvalidType)
+    }
+}
+
+/// A shell command run when a timer event happens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hook {
+    pub id: Option<HookId>,
+    pub event: HookEvent,
+    pub command: String,
+    /// The command is killed if it runs longer than this
+    pub timeout_secs: i64,
+}
+
+impl Hook {
+    pub const DEFAULT_TIMEOUT_SECS: i64 = 10;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_next_wraps_around() {
+        assert_eq!(HookEvent::WorkStarted.next(), HookEvent::Paused);
+        assert_eq!(HookEvent::BreakEnded.next(), HookEvent::WorkStarted);
+    }
+}
@@ -1,6 +1,22 @@
+mod alert;
+mod cycle;
+mod hook;
+mod profile;
 mod session;
+mod task;
+mod webhook;
 
+pub use alert::{AlertPattern, DesktopNotification, QuietHours};
+pub use cycle::{CycleStep, CycleStepId, PhaseKind};
+pub use hook::{Hook, HookEvent, HookId};
+pub use profile::{Profile, ProfileId, ProfileStat};
 pub use session::{
-    BoundedString, Category, CategoryId, CategoryStat, Config, DurationSecs, Session, SessionId,
-    Timestamp, format_hex_color, parse_hex_color,
+    BoundedString, BreakId, BreakKind, BreakPeriod, BreakStat, Category, CategoryId, CategoryStat,
+    Config, DailyInterruptions, DurationOverrides, DurationSecs, Interruption, InterruptionKind,
+    PauseStat, Session, SessionId, SessionOutcome, SessionSegment, TimerMode, Timestamp,
+    format_hex_color, parse_hex_color,
+};
+pub use task::{EstimateStat, Task, TaskId, TaskStatus};
+pub use webhook::{
+    DeliveryId, Webhook, WebhookDelivery, WebhookId, format_event_filter, parse_event_filter,
 };
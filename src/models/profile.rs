@@ -0,0 +1,149 @@
+use rusqlite::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use super::Config;
+
+/// Database row ID for a timer profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProfileId(i64);
+
+impl From<i64> for ProfileId {
+    fn from(val: i64) -> Self {
+        ProfileId(val)
+    }
+}
+
+impl From<ProfileId> for i64 {
+    fn from(id: ProfileId) -> Self {
+        id.0
+    }
+}
+
+impl ToSql for ProfileId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+impl FromSql for ProfileId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(ProfileId)
+    }
+}
+
+/// A named set of timer durations, such as 25/5 or 52/17
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub id: Option<ProfileId>,
+    pub name: String,
+    pub work_duration_secs: i64,
+    pub short_break_secs: i64,
+    pub long_break_secs: i64,
+    pub sessions_until_long_break: i64,
+}
+
+impl Profile {
+    /// Profiles offered alongside the user's original durations on first run:
+    /// (name, work, short break, long break, sessions until long break)
+    pub const PRESETS: [(&str, i64, i64, i64, i64); 2] = [
+        ("52/17", 52 * 60, 17 * 60, 17 * 60, 4),
+        ("Deep Work", 90 * 60, 20 * 60, 30 * 60, 2),
+    ];
+
+    /// Create a profile from the durations in a config
+    pub fn from_config(name: impl Into<String>, config: &Config) -> Self {
+        Self {
+            id: None,
+            name: name.into(),
+            work_duration_secs: config.work_duration_secs,
+            short_break_secs: config.short_break_secs,
+            long_break_secs: config.long_break_secs,
+            sessions_until_long_break: config.sessions_until_long_break,
+        }
+    }
+
+    /// Copy this profile's durations into a config and make it the active profile
+    pub fn apply_to(&self, config: &mut Config) {
+        config.active_profile = self.id;
+        config.work_duration_secs = self.work_duration_secs;
+        config.short_break_secs = self.short_break_secs;
+        config.long_break_secs = self.long_break_secs;
+        config.sessions_until_long_break = self.sessions_until_long_break;
+    }
+
+    /// Short summary of the durations in minutes, e.g. "25/5/15 x4"
+    pub fn summary(&self) -> String {
+        format!(
+            "{}/{}/{} x{}",
+            self.work_duration_secs / 60,
+            self.short_break_secs / 60,
+            self.long_break_secs / 60,
+            self.sessions_until_long_break
+        )
+    }
+}
+
+/// Aggregated work sessions recorded under one profile
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileStat {
+    pub name: String,
+    pub session_count: i64,
+    pub total_seconds: i64,
+    pub completed_count: i64,
+}
+
+impl ProfileStat {
+    /// Fraction of sessions that ran to completion
+    pub fn completion_rate(&self) -> f64 {
+        if self.session_count == 0 {
+            return 0.0;
+        }
+        self.completed_count as f64 / self.session_count as f64
+    }
+
+    /// Mean focused time per session in seconds
+    pub fn mean_secs(&self) -> i64 {
+        if self.session_count == 0 {
+            return 0;
+        }
+        self.total_seconds / self.session_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_round_trips_through_config() {
+        let mut profile = Profile::from_config("Classic", &Config::default());
+        profile.id = Some(ProfileId::from(3));
+        assert_eq!(profile.summary(), "25/5/15 x4");
+
+        let mut config = Config::default();
+        let deep = Profile {
+            id: Some(ProfileId::from(7)),
+            name: "Deep Work".to_string(),
+            work_duration_secs: 90 * 60,
+            short_break_secs: 20 * 60,
+            long_break_secs: 30 * 60,
+            sessions_until_long_break: 2,
+        };
+        deep.apply_to(&mut config);
+        assert_eq!(config.active_profile, Some(ProfileId::from(7)));
+        assert_eq!(config.work_duration_secs, 90 * 60);
+        assert_eq!(config.sessions_until_long_break, 2);
+    }
+
+    #[test]
+    fn test_profile_stat_rates() {
+        let stat = ProfileStat {
+            name: "52/17".to_string(),
+            session_count: 4,
+            total_seconds: 4 * 3000,
+            completed_count: 3,
+        };
+        assert_eq!(stat.completion_rate(), 0.75);
+        assert_eq!(stat.mean_secs(), 3000);
+        assert_eq!(ProfileStat::default().mean_secs(), 0);
+    }
+}
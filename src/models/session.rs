@@ -1,8 +1,10 @@
 use chrono::{DateTime, Local};
 use ratatui::style::Color;
-use rusqlite::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
 
+use super::{AlertPattern, DesktopNotification, PhaseKind, ProfileId, QuietHours, TaskId};
 use crate::clock::Clock;
+use crate::theme::Theme;
 
 /// A string with a maximum length enforced at runtime.
 /// Silently ignores characters that would exceed the limit.
@@ -88,6 +90,14 @@ impl std::ops::Sub for Timestamp {
     }
 }
 
+impl std::ops::Add<DurationSecs> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: DurationSecs) -> Timestamp {
+        Timestamp(self.0 + duration.0)
+    }
+}
+
 impl ToSql for Timestamp {
     fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
         self.0.to_sql()
@@ -112,6 +122,19 @@ impl DurationSecs {
     pub fn as_secs(self) -> i64 {
         self.0
     }
+
+    /// Format as "Xh Ym" or "Xm"
+    pub fn format(self) -> String {
+        let minutes = self.0 / 60;
+        let hours = minutes / 60;
+        let remaining_minutes = minutes % 60;
+
+        if hours > 0 {
+            format!("{}h {}m", hours, remaining_minutes)
+        } else {
+            format!("{}m", minutes)
+        }
+    }
 }
 
 impl From<i64> for DurationSecs {
@@ -200,6 +223,171 @@ impl FromSql for CategoryId {
     }
 }
 
+/// A stretch of time during which the timer was running within a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionSegment {
+    pub started_at: Timestamp,
+    pub ended_at: Timestamp,
+}
+
+impl SessionSegment {
+    pub fn duration(&self) -> DurationSecs {
+        self.ended_at - self.started_at
+    }
+}
+
+/// Where an interruption came from, per the Pomodoro Technique
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterruptionKind {
+    #[default]
+    /// An urge or thought that pulled attention away
+    Internal,
+    /// Someone or something else demanding attention
+    External,
+}
+
+impl InterruptionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterruptionKind::Internal => "internal",
+            InterruptionKind::External => "external",
+        }
+    }
+
+    /// Tally mark used by the technique: `'` for internal, `-` for external
+    pub fn mark(&self) -> char {
+        match self {
+            InterruptionKind::Internal => '\'',
+            InterruptionKind::External => '-',
+        }
+    }
+}
+
+impl ToSql for InterruptionKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for InterruptionKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "internal" => Ok(InterruptionKind::Internal),
+            "external" => Ok(InterruptionKind::External),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// An interruption logged during a work period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interruption {
+    pub kind: InterruptionKind,
+    pub note: Option<String>,
+    pub occurred_at: Timestamp,
+}
+
+/// How a work session ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionOutcome {
+    /// Ran until the timer finished
+    #[default]
+    Completed,
+    /// Stopped early by the user
+    Stopped,
+    /// Still running when the app was quit
+    Abandoned,
+    /// Found unfinished after a crash and saved on the next launch
+    Recovered,
+}
+
+impl SessionOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionOutcome::Completed => "completed",
+            SessionOutcome::Stopped => "stopped",
+            SessionOutcome::Abandoned => "abandoned",
+            SessionOutcome::Recovered => "recovered",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            SessionOutcome::Completed => "✓",
+            SessionOutcome::Stopped => "■",
+            SessionOutcome::Abandoned => "✗",
+            SessionOutcome::Recovered => "↺",
+        }
+    }
+}
+
+impl ToSql for SessionOutcome {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for SessionOutcome {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "completed" => Ok(SessionOutcome::Completed),
+            "stopped" => Ok(SessionOutcome::Stopped),
+            "abandoned" => Ok(SessionOutcome::Abandoned),
+            "recovered" => Ok(SessionOutcome::Recovered),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// How the work phase is timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimerMode {
+    /// Fixed-length work periods counting down
+    #[default]
+    Pomodoro,
+    /// Open-ended work periods counting up, followed by a proportional break
+    Flowtime,
+}
+
+impl TimerMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimerMode::Pomodoro => "pomodoro",
+            TimerMode::Flowtime => "flowtime",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimerMode::Pomodoro => "Pomodoro",
+            TimerMode::Flowtime => "Flowtime",
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            TimerMode::Pomodoro => TimerMode::Flowtime,
+            TimerMode::Flowtime => TimerMode::Pomodoro,
+        }
+    }
+}
+
+impl ToSql for TimerMode {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for TimerMode {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "pomodoro" => Ok(TimerMode::Pomodoro),
+            "flowtime" => Ok(TimerMode::Flowtime),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
 /// A completed pomodoro session
 #[derive(Debug, Clone)]
 pub struct Session {
@@ -209,7 +397,23 @@ pub struct Session {
     pub category: String,
     pub started_at: Timestamp,
     pub ended_at: Timestamp,
+    /// Actual time spent running
     pub duration_secs: DurationSecs,
+    /// Work duration the timer was set to
+    pub planned_secs: DurationSecs,
+    /// Time worked past the end of the (possibly extended) work period
+    pub overtime_secs: DurationSecs,
+    pub outcome: SessionOutcome,
+    /// The planned task this session worked on, if any
+    pub task_id: Option<TaskId>,
+    /// Name of the timer profile the work period ran under
+    pub profile: Option<String>,
+    /// Whether the work period counted down or up
+    pub mode: TimerMode,
+    /// Running segments in chronological order (empty for sessions recorded without them)
+    pub segments: Vec<SessionSegment>,
+    /// Interruptions logged during the session in chronological order
+    pub interruptions: Vec<Interruption>,
 }
 
 impl Session {
@@ -230,15 +434,27 @@ impl Session {
 
     /// Format duration as "Xh Ym" or "Xm"
     pub fn format_duration(&self) -> String {
-        let minutes = self.duration_secs.as_secs() / 60;
-        let hours = minutes / 60;
-        let remaining_minutes = minutes % 60;
+        self.duration_secs.format()
+    }
 
-        if hours > 0 {
-            format!("{}h {}m", hours, remaining_minutes)
-        } else {
-            format!("{}m", minutes)
-        }
+    /// Count interruptions of the given kind
+    pub fn interruption_count(&self, kind: InterruptionKind) -> usize {
+        self.interruptions.iter().filter(|i| i.kind == kind).count()
+    }
+
+    /// Number of times the session was paused
+    pub fn pause_count(&self) -> usize {
+        self.segments.len().saturating_sub(1)
+    }
+
+    /// Total wall-clock time spent paused between segments
+    pub fn paused_secs(&self) -> DurationSecs {
+        self.segments
+            .windows(2)
+            .map(|w| w[1].started_at - w[0].ended_at)
+            .fold(DurationSecs::default(), |acc, d| {
+                DurationSecs::new(acc.as_secs() + d.as_secs().max(0))
+            })
     }
 }
 
@@ -251,6 +467,7 @@ pub struct SessionBuilder {
     started_at: Option<Timestamp>,
     ended_at: Option<Timestamp>,
     duration_secs: Option<DurationSecs>,
+    task_id: Option<TaskId>,
 }
 
 impl SessionBuilder {
@@ -288,6 +505,11 @@ impl SessionBuilder {
         self
     }
 
+    pub fn task_id(mut self, task_id: Option<TaskId>) -> Self {
+        self.task_id = task_id;
+        self
+    }
+
     /// Build the Session, returning None if required fields are missing
     pub fn build(self) -> Option<Session> {
         Some(Session {
@@ -298,16 +520,65 @@ impl SessionBuilder {
             started_at: self.started_at?,
             ended_at: self.ended_at?,
             duration_secs: self.duration_secs?,
+            planned_secs: DurationSecs::default(),
+            overtime_secs: DurationSecs::default(),
+            outcome: SessionOutcome::default(),
+            task_id: self.task_id,
+            profile: None,
+            mode: TimerMode::default(),
+            segments: Vec::new(),
+            interruptions: Vec::new(),
         })
     }
 }
 
+/// Timer durations a category uses in place of the configured ones, where set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DurationOverrides {
+    pub work_duration_secs: Option<i64>,
+    pub short_break_secs: Option<i64>,
+    pub long_break_secs: Option<i64>,
+}
+
+impl DurationOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Replace the durations in a config with the overridden ones
+    pub fn apply_to(&self, config: &mut Config) {
+        if let Some(secs) = self.work_duration_secs {
+            config.work_duration_secs = secs;
+        }
+        if let Some(secs) = self.short_break_secs {
+            config.short_break_secs = secs;
+        }
+        if let Some(secs) = self.long_break_secs {
+            config.long_break_secs = secs;
+        }
+    }
+
+    /// Format as "45/10/-" in minutes, with "-" for durations left to the config
+    pub fn summary(&self) -> String {
+        [
+            self.work_duration_secs,
+            self.short_break_secs,
+            self.long_break_secs,
+        ]
+        .iter()
+        .map(|d| d.map_or("-".to_string(), |secs| (secs / 60).to_string()))
+        .collect::<Vec<_>>()
+        .join("/")
+    }
+}
+
 /// A category for sessions with an associated color
 #[derive(Debug, Clone)]
 pub struct Category {
     pub id: Option<CategoryId>,
     pub name: String,
     pub color: Color,
+    pub durations: DurationOverrides,
 }
 
 impl Category {
@@ -318,31 +589,37 @@ impl Category {
                 id: None,
                 name: "work".to_string(),
                 color: Color::Rgb(255, 107, 107), // #FF6B6B
+                durations: DurationOverrides::default(),
             },
             Self {
                 id: None,
                 name: "study".to_string(),
                 color: Color::Rgb(78, 205, 196), // #4ECDC4
+                durations: DurationOverrides::default(),
             },
             Self {
                 id: None,
                 name: "coding".to_string(),
                 color: Color::Rgb(69, 183, 209), // #45B7D1
+                durations: DurationOverrides::default(),
             },
             Self {
                 id: None,
                 name: "reading".to_string(),
                 color: Color::Rgb(150, 206, 180), // #96CEB4
+                durations: DurationOverrides::default(),
             },
             Self {
                 id: None,
                 name: "exercise".to_string(),
                 color: Color::Rgb(255, 234, 167), // #FFEAA7
+                durations: DurationOverrides::default(),
             },
             Self {
                 id: None,
                 name: "other".to_string(),
                 color: Color::Rgb(223, 230, 233), // #DFE6E9
+                durations: DurationOverrides::default(),
             },
         ]
     }
@@ -373,6 +650,132 @@ pub fn format_hex_color(color: Color) -> String {
 pub struct CategoryStat {
     pub name: String,
     pub total_seconds: i64,
+    pub session_count: i64,
+    /// Sessions that ran until the timer finished
+    pub completed_count: i64,
+}
+
+impl CategoryStat {
+    /// Fraction of sessions that were completed (0.0 to 1.0)
+    pub fn completion_rate(&self) -> f64 {
+        if self.session_count == 0 {
+            return 0.0;
+        }
+        self.completed_count as f64 / self.session_count as f64
+    }
+}
+
+/// Which kind of break was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakKind {
+    Short,
+    Long,
+}
+
+impl BreakKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BreakKind::Short => "short",
+            BreakKind::Long => "long",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BreakKind::Short => "Short break",
+            BreakKind::Long => "Long break",
+        }
+    }
+}
+
+impl ToSql for BreakKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for BreakKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "short" => Ok(BreakKind::Short),
+            "long" => Ok(BreakKind::Long),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// Database row ID for a break period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BreakId(i64);
+
+impl BreakId {
+    pub fn new(id: i64) -> Self {
+        BreakId(id)
+    }
+}
+
+impl From<i64> for BreakId {
+    fn from(val: i64) -> Self {
+        BreakId(val)
+    }
+}
+
+impl From<BreakId> for i64 {
+    fn from(id: BreakId) -> Self {
+        id.0
+    }
+}
+
+impl ToSql for BreakId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+impl FromSql for BreakId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(BreakId)
+    }
+}
+
+/// A break period between work sessions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakPeriod {
+    pub kind: BreakKind,
+    pub started_at: Timestamp,
+    pub ended_at: Timestamp,
+    /// Whether the break was cut short via skip
+    pub skipped: bool,
+}
+
+impl BreakPeriod {
+    pub fn duration(&self) -> DurationSecs {
+        self.ended_at - self.started_at
+    }
+}
+
+/// Aggregated break statistics over a time range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BreakStat {
+    pub count: i64,
+    pub skipped: i64,
+    pub total_seconds: i64,
+}
+
+/// Interruption counts for a single calendar day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyInterruptions {
+    /// Local date formatted as `YYYY-MM-DD`
+    pub day: String,
+    pub internal: i64,
+    pub external: i64,
+}
+
+/// Aggregated pause statistics over a time range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PauseStat {
+    pub pause_count: i64,
+    pub paused_secs: i64,
 }
 
 /// Timer configuration settings
@@ -382,6 +785,41 @@ pub struct Config {
     pub short_break_secs: i64,
     pub long_break_secs: i64,
     pub sessions_until_long_break: i64,
+    /// Start a break as soon as a work period finishes
+    pub auto_start_breaks: bool,
+    /// Start the next work period as soon as a break finishes
+    pub auto_start_work: bool,
+    /// Keep counting into overtime when a work period reaches zero
+    pub overtime: bool,
+    /// Pause work once the terminal has been unfocused this long, zero to disable
+    pub focus_pause_secs: i64,
+    /// Profile the durations above were loaded from
+    pub active_profile: Option<ProfileId>,
+    pub timer_mode: TimerMode,
+    /// Flowtime break length is the work time divided by this ratio
+    pub flowtime_break_ratio: i64,
+    pub flowtime_min_break_secs: i64,
+    pub flowtime_max_break_secs: i64,
+    /// Follow the user-defined phase cycle instead of the standard one
+    pub custom_cycle: bool,
+    /// Time added to the current phase each time it is extended
+    pub extend_secs: i64,
+    /// Warn this long before a phase ends, zero to disable
+    pub first_warning_secs: i64,
+    /// A second, usually later, warning before a phase ends, zero to disable
+    pub second_warning_secs: i64,
+    pub work_alert: AlertPattern,
+    pub short_break_alert: AlertPattern,
+    pub long_break_alert: AlertPattern,
+    pub quiet_hours: QuietHours,
+    /// How phase changes are announced to the desktop, if at all
+    pub desktop_notification: DesktopNotification,
+    /// Show the phase and time left in the terminal window title
+    pub window_title: bool,
+    /// Name of the colour theme, built in or from the themes file
+    pub theme: String,
+    /// Draw the timer in large block digits
+    pub big_clock: bool,
 }
 
 impl Config {
@@ -389,6 +827,12 @@ impl Config {
     pub const DEFAULT_SHORT_BREAK_SECS: i64 = 5 * 60;
     pub const DEFAULT_LONG_BREAK_SECS: i64 = 15 * 60;
     pub const DEFAULT_SESSIONS_UNTIL_LONG: i64 = 4;
+    pub const DEFAULT_EXTEND_SECS: i64 = 5 * 60;
+    pub const DEFAULT_FIRST_WARNING_SECS: i64 = 2 * 60;
+    pub const DEFAULT_SECOND_WARNING_SECS: i64 = 30;
+    pub const DEFAULT_FLOWTIME_RATIO: i64 = 5;
+    pub const DEFAULT_FLOWTIME_MIN_BREAK_SECS: i64 = 5 * 60;
+    pub const DEFAULT_FLOWTIME_MAX_BREAK_SECS: i64 = 30 * 60;
 
     /// Check if all config values are valid (positive durations)
     pub fn is_valid(&self) -> bool {
@@ -396,6 +840,38 @@ impl Config {
             && self.short_break_secs > 0
             && self.long_break_secs > 0
             && self.sessions_until_long_break > 0
+            && self.extend_secs > 0
+            && self.flowtime_break_ratio > 0
+            && self.flowtime_min_break_secs > 0
+            && self.flowtime_min_break_secs <= self.flowtime_max_break_secs
+            && self.focus_pause_secs >= 0
+            && self.first_warning_secs >= 0
+            && self.second_warning_secs >= 0
+            && [
+                self.work_alert,
+                self.short_break_alert,
+                self.long_break_alert,
+            ]
+            .iter()
+            .all(|a| (0..=AlertPattern::MAX_BELLS).contains(&a.bells))
+            && self.quiet_hours.is_valid()
+    }
+
+    /// How the end of the given phase is announced
+    pub fn alert_for(&self, phase: PhaseKind) -> AlertPattern {
+        match phase {
+            PhaseKind::Work => self.work_alert,
+            PhaseKind::ShortBreak => self.short_break_alert,
+            PhaseKind::LongBreak => self.long_break_alert,
+        }
+    }
+
+    pub fn alert_for_mut(&mut self, phase: PhaseKind) -> &mut AlertPattern {
+        match phase {
+            PhaseKind::Work => &mut self.work_alert,
+            PhaseKind::ShortBreak => &mut self.short_break_alert,
+            PhaseKind::LongBreak => &mut self.long_break_alert,
+        }
     }
 }
 
@@ -406,6 +882,39 @@ impl Default for Config {
             short_break_secs: Self::DEFAULT_SHORT_BREAK_SECS,
             long_break_secs: Self::DEFAULT_LONG_BREAK_SECS,
             sessions_until_long_break: Self::DEFAULT_SESSIONS_UNTIL_LONG,
+            auto_start_breaks: true,
+            auto_start_work: true,
+            overtime: false,
+            focus_pause_secs: 0,
+            active_profile: None,
+            timer_mode: TimerMode::default(),
+            flowtime_break_ratio: Self::DEFAULT_FLOWTIME_RATIO,
+            flowtime_min_break_secs: Self::DEFAULT_FLOWTIME_MIN_BREAK_SECS,
+            flowtime_max_break_secs: Self::DEFAULT_FLOWTIME_MAX_BREAK_SECS,
+            custom_cycle: false,
+            extend_secs: Self::DEFAULT_EXTEND_SECS,
+            first_warning_secs: Self::DEFAULT_FIRST_WARNING_SECS,
+            second_warning_secs: Self::DEFAULT_SECOND_WARNING_SECS,
+            work_alert: AlertPattern {
+                bells: 1,
+                flash: true,
+                gauge: true,
+            },
+            short_break_alert: AlertPattern {
+                bells: 2,
+                flash: false,
+                gauge: true,
+            },
+            long_break_alert: AlertPattern {
+                bells: 3,
+                flash: false,
+                gauge: true,
+            },
+            quiet_hours: QuietHours::default(),
+            desktop_notification: DesktopNotification::Off,
+            window_title: false,
+            theme: Theme::DEFAULT_NAME.to_string(),
+            big_clock: false,
         }
     }
 }
@@ -494,6 +1003,7 @@ mod tests {
             short_break_secs: 10 * 60,
             long_break_secs: 20 * 60,
             sessions_until_long_break: 3,
+            ..Config::default()
         };
         assert_eq!(config.work_duration_secs, 1800);
         assert_eq!(config.short_break_secs, 600);
@@ -511,6 +1021,7 @@ mod tests {
             short_break_secs: 1,
             long_break_secs: 1,
             sessions_until_long_break: 1,
+            ..Config::default()
         };
         assert!(valid.is_valid());
     }
@@ -549,10 +1060,132 @@ mod tests {
             short_break_secs: 5 * 60,
             long_break_secs: 15 * 60,
             sessions_until_long_break: 4,
+            ..Config::default()
         };
         assert!(!negative.is_valid());
     }
 
+    #[test]
+    fn test_duration_overrides_replace_only_set_durations() {
+        let overrides = DurationOverrides {
+            work_duration_secs: Some(45 * 60),
+            long_break_secs: Some(20 * 60),
+            ..DurationOverrides::default()
+        };
+        let mut config = Config::default();
+        overrides.apply_to(&mut config);
+        assert_eq!(config.work_duration_secs, 45 * 60);
+        assert_eq!(config.short_break_secs, Config::DEFAULT_SHORT_BREAK_SECS);
+        assert_eq!(config.long_break_secs, 20 * 60);
+        assert_eq!(overrides.summary(), "45/-/20");
+        assert!(DurationOverrides::default().is_empty());
+    }
+
+    #[test]
+    fn test_config_is_invalid_inverted_flowtime_bounds() {
+        let inverted = Config {
+            flowtime_min_break_secs: 20 * 60,
+            flowtime_max_break_secs: 10 * 60,
+            ..Config::default()
+        };
+        assert!(!inverted.is_valid());
+    }
+
+    #[test]
+    fn test_session_pause_metrics() {
+        let mut session = Session::builder()
+            .name("Paused")
+            .category("coding")
+            .started_at(Timestamp::new(0))
+            .ended_at(Timestamp::new(1000))
+            .duration_secs(DurationSecs::new(700))
+            .build()
+            .unwrap();
+        session.segments = vec![
+            SessionSegment {
+                started_at: Timestamp::new(0),
+                ended_at: Timestamp::new(300),
+            },
+            SessionSegment {
+                started_at: Timestamp::new(400),
+                ended_at: Timestamp::new(600),
+            },
+            SessionSegment {
+                started_at: Timestamp::new(800),
+                ended_at: Timestamp::new(1000),
+            },
+        ];
+
+        assert_eq!(session.pause_count(), 2);
+        assert_eq!(session.paused_secs(), DurationSecs::new(300));
+    }
+
+    #[test]
+    fn test_session_without_segments_has_no_pauses() {
+        let session = Session::builder()
+            .name("Legacy")
+            .category("coding")
+            .started_at(Timestamp::new(0))
+            .ended_at(Timestamp::new(1500))
+            .duration_secs(DurationSecs::new(1500))
+            .build()
+            .unwrap();
+
+        assert_eq!(session.pause_count(), 0);
+        assert_eq!(session.paused_secs(), DurationSecs::new(0));
+    }
+
+    #[test]
+    fn test_interruption_counts_by_kind() {
+        let mut session = Session::builder()
+            .name("Busy")
+            .category("work")
+            .started_at(Timestamp::new(0))
+            .ended_at(Timestamp::new(1500))
+            .duration_secs(DurationSecs::new(1500))
+            .build()
+            .unwrap();
+        for (kind, at) in [
+            (InterruptionKind::Internal, 100),
+            (InterruptionKind::External, 200),
+            (InterruptionKind::Internal, 300),
+        ] {
+            session.interruptions.push(Interruption {
+                kind,
+                note: None,
+                occurred_at: Timestamp::new(at),
+            });
+        }
+
+        assert_eq!(session.interruption_count(InterruptionKind::Internal), 2);
+        assert_eq!(session.interruption_count(InterruptionKind::External), 1);
+    }
+
+    #[test]
+    fn test_category_stat_completion_rate() {
+        let stat = CategoryStat {
+            name: "coding".to_string(),
+            total_seconds: 6000,
+            session_count: 4,
+            completed_count: 3,
+        };
+        assert_eq!(stat.completion_rate(), 0.75);
+
+        let empty = CategoryStat {
+            session_count: 0,
+            completed_count: 0,
+            ..stat
+        };
+        assert_eq!(empty.completion_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_duration_secs_format() {
+        assert_eq!(DurationSecs::new(59).format(), "0m");
+        assert_eq!(DurationSecs::new(25 * 60).format(), "25m");
+        assert_eq!(DurationSecs::new(90 * 60).format(), "1h 30m");
+    }
+
     #[test]
     fn test_timestamp_from_clock() {
         use crate::clock::SystemClock;
@@ -0,0 +1,178 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use super::Timestamp;
+
+/// Database row ID for a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(i64);
+
+impl From<i64> for TaskId {
+    fn from(val: i64) -> Self {
+        TaskId(val)
+    }
+}
+
+impl From<TaskId> for i64 {
+    fn from(id: TaskId) -> Self {
+        id.0
+    }
+}
+
+impl ToSql for TaskId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+impl FromSql for TaskId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(TaskId)
+    }
+}
+
+/// Whether a task is still being worked on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskStatus {
+    #[default]
+    Open,
+    Done,
+}
+
+impl TaskStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Open => "open",
+            TaskStatus::Done => "done",
+        }
+    }
+}
+
+impl ToSql for TaskStatus {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for TaskStatus {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "open" => Ok(TaskStatus::Open),
+            "done" => Ok(TaskStatus::Done),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// A planned piece of work with an estimate in pomodoros
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    pub id: Option<TaskId>,
+    pub title: String,
+    pub category: String,
+    /// Estimated number of pomodoros
+    pub estimate: i64,
+    pub status: TaskStatus,
+    /// Position in the queue, lowest first
+    pub position: i64,
+    /// Completed pomodoros so far, frozen when the task is finished
+    pub pomodoros: i64,
+    pub completed_at: Option<Timestamp>,
+}
+
+impl Task {
+    pub fn is_open(&self) -> bool {
+        self.status == TaskStatus::Open
+    }
+
+    /// Pomodoros spent beyond the estimate; negative when finished early
+    pub fn estimate_error(&self) -> i64 {
+        self.pomodoros - self.estimate
+    }
+}
+
+/// Estimated vs actual pomodoros over the finished tasks of a category
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EstimateStat {
+    pub name: String,
+    pub task_count: i64,
+    pub estimated: i64,
+    pub actual: i64,
+}
+
+impl EstimateStat {
+    /// Combine several categories into one aggregate row
+    pub fn combined(name: impl Into<String>, stats: &[EstimateStat]) -> Self {
+        Self {
+            name: name.into(),
+            task_count: stats.iter().map(|s| s.task_count).sum(),
+            estimated: stats.iter().map(|s| s.estimated).sum(),
+            actual: stats.iter().map(|s| s.actual).sum(),
+        }
+    }
+
+    /// Mean pomodoros per task beyond the estimate; positive means underestimated
+    pub fn mean_error(&self) -> f64 {
+        if self.task_count == 0 {
+            return 0.0;
+        }
+        (self.actual - self.estimated) as f64 / self.task_count as f64
+    }
+
+    /// Describe the direction of the mean error
+    pub fn verdict(&self) -> &'static str {
+        let error = self.mean_error();
+        if error > 0.0 {
+            "underestimated"
+        } else if error < 0.0 {
+            "overestimated"
+        } else {
+            "on target"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_error() {
+        let task = Task {
+            id: None,
+            title: "Report".to_string(),
+            category: "work".to_string(),
+            estimate: 3,
+            status: TaskStatus::Done,
+            position: 1,
+            pomodoros: 5,
+            completed_at: None,
+        };
+        assert_eq!(task.estimate_error(), 2);
+    }
+
+    #[test]
+    fn test_estimate_stat_mean_error() {
+        let stats = [
+            EstimateStat {
+                name: "work".to_string(),
+                task_count: 2,
+                estimated: 6,
+                actual: 9,
+            },
+            EstimateStat {
+                name: "study".to_string(),
+                task_count: 2,
+                estimated: 4,
+                actual: 3,
+            },
+        ];
+        assert_eq!(stats[0].mean_error(), 1.5);
+        assert_eq!(stats[0].verdict(), "underestimated");
+        assert_eq!(stats[1].verdict(), "overestimated");
+
+        let all = EstimateStat::combined("All", &stats);
+        assert_eq!(all.task_count, 4);
+        assert_eq!(all.mean_error(), 0.5);
+        assert_eq!(EstimateStat::default().verdict(), "on target");
+    }
+}
@@ -0,0 +1,171 @@
+use rusqlite::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use super::{HookEvent, Timestamp};
+
+/// Database row ID for a webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WebhookId(i64);
+
+impl From<i64> for WebhookId {
+    fn from(val: i64) -> Self {
+        WebhookId(val)
+    }
+}
+
+impl From<WebhookId> for i64 {
+    fn from(id: WebhookId) -> Self {
+        id.0
+    }
+}
+
+impl ToSql for WebhookId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+impl FromSql for WebhookId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(WebhookId)
+    }
+}
+
+/// Database row ID for a queued webhook delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeliveryId(i64);
+
+impl From<i64> for DeliveryId {
+    fn from(val: i64) -> Self {
+        DeliveryId(val)
+    }
+}
+
+impl From<DeliveryId> for i64 {
+    fn from(id: DeliveryId) -> Self {
+        id.0
+    }
+}
+
+impl ToSql for DeliveryId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+impl FromSql for DeliveryId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(DeliveryId)
+    }
+}
+
+/// An HTTP endpoint that timer events are posted to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub id: Option<WebhookId>,
+    pub url: String,
+    /// Extra request headers as `Name: value` pairs separated by `;`
+    pub headers: String,
+    /// Events to post, or empty for all of them
+    pub events: Vec<HookEvent>,
+}
+
+impl Webhook {
+    /// Check if the webhook wants an event
+    pub fn wants(&self, event: HookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+
+    /// Header names and values, skipping blank entries
+    pub fn header_pairs(&self) -> Vec<(&str, &str)> {
+        self.headers
+            .split(';')
+            .filter(|h| !h.trim().is_empty())
+            .map(|h| match h.split_once(':') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => (h.trim(), ""),
+            })
+            .collect()
+    }
+}
+
+/// Join events into the comma-separated form they are stored in
+pub fn format_event_filter(events: &[HookEvent]) -> String {
+    events
+        .iter()
+        .map(HookEvent::as_str)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parse a stored event filter, ignoring names that are no longer known
+pub fn parse_event_filter(s: &str) -> Vec<HookEvent> {
+    s.split(',').filter_map(HookEvent::parse).collect()
+}
+
+/// An event payload waiting to be posted to a webhook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: Option<DeliveryId>,
+    pub webhook_id: WebhookId,
+    /// JSON request body
+    pub payload: String,
+    /// Failed attempts so far
+    pub attempts: i64,
+    pub next_attempt_at: Timestamp,
+    pub last_error: Option<String>,
+}
+
+impl WebhookDelivery {
+    /// Stop retrying after this many failed attempts; the delivery stays queued as failed
+    /// until it is retried from the settings
+    pub const MAX_ATTEMPTS: i64 = 10;
+    /// Delay before the first retry, doubled after each further failure
+    const RETRY_BASE_SECS: i64 = 30;
+    const RETRY_MAX_SECS: i64 = 3600;
+
+    /// Seconds to wait before retrying after the given number of failed attempts
+    pub fn retry_delay_secs(attempts: i64) -> i64 {
+        let doublings = attempts.saturating_sub(1).clamp(0, 16) as u32;
+        (Self::RETRY_BASE_SECS << doublings).min(Self::RETRY_MAX_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_filter_round_trip() {
+        let events = vec![HookEvent::WorkCompleted, HookEvent::BreakStarted];
+        let stored = format_event_filter(&events);
+        assert_eq!(stored, "work_completed,break_started");
+        assert_eq!(parse_event_filter(&stored), events);
+        assert!(parse_event_filter("").is_empty());
+    }
+
+    #[test]
+    fn test_empty_filter_wants_everything() {
+        let mut webhook = Webhook {
+            id: None,
+            url: "http://localhost/".to_string(),
+            headers: "Authorization: Bearer abc; X-Team:core;".to_string(),
+            events: Vec::new(),
+        };
+        assert!(webhook.wants(HookEvent::Paused));
+        assert_eq!(
+            webhook.header_pairs(),
+            vec![("Authorization", "Bearer abc"), ("X-Team", "core")]
+        );
+
+        webhook.events = vec![HookEvent::WorkCompleted];
+        assert!(webhook.wants(HookEvent::WorkCompleted));
+        assert!(!webhook.wants(HookEvent::Paused));
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(WebhookDelivery::retry_delay_secs(1), 30);
+        assert_eq!(WebhookDelivery::retry_delay_secs(2), 60);
+        assert_eq!(WebhookDelivery::retry_delay_secs(9), 3600);
+    }
+}
@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+
+use crossterm::event::KeyCode;
+use ratatui::{layout::Rect, text::Line};
+
+use crate::app::{ChartType, InputField, StatsPeriod};
+use crate::keymap::Key;
+
+/// What clicking on a region of the screen does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickTarget {
+    /// Act as if this key had been pressed
+    Key(Key),
+    /// A list that the wheel scrolls; clicking it does nothing by itself
+    List,
+    /// A row of the History list, by list index
+    HistoryRow(usize),
+    /// A Stats period label
+    StatsPeriod(StatsPeriod),
+    /// A Stats chart-type label
+    ChartType(ChartType),
+    /// Focus a field of the session input modal, then press a key in it if given
+    InputField(InputField, Option<KeyCode>),
+    /// An entry of the category list in the settings modal
+    SettingsCategory(usize),
+    /// A command palette entry, by index into the matching commands
+    PaletteCommand(usize),
+}
+
+impl ClickTarget {
+    /// Whether the wheel scrolls the list this target belongs to
+    pub fn scrolls(self) -> bool {
+        matches!(
+            self,
+            ClickTarget::List
+                | ClickTarget::HistoryRow(_)
+                | ClickTarget::SettingsCategory(_)
+                | ClickTarget::PaletteCommand(_)
+        )
+    }
+}
+
+/// The clickable regions of the last drawn frame
+///
+/// Render functions only get shared access to the app, so regions are collected through a
+/// `RefCell`. Regions added later sit on top of earlier ones.
+#[derive(Debug, Default)]
+pub struct HitMap {
+    regions: RefCell<Vec<(Rect, ClickTarget)>>,
+}
+
+impl HitMap {
+    /// Forget every region, before drawing a new frame or a modal over it
+    pub fn clear(&self) {
+        self.regions.borrow_mut().clear();
+    }
+
+    pub fn add(&self, area: Rect, target: ClickTarget) {
+        if !area.is_empty() {
+            self.regions.borrow_mut().push((area, target));
+        }
+    }
+
+    /// The topmost target under a screen cell
+    pub fn target_at(&self, column: u16, row: u16) -> Option<ClickTarget> {
+        self.regions
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(area, _)| {
+                column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+            })
+            .map(|&(_, target)| target)
+    }
+}
+
+/// Where each span of a line lands when drawn centered on the first row of `area`
+///
+/// Mirrors the offset `Paragraph` uses for centered lines.
+pub fn centered_span_areas(area: Rect, line: &Line) -> Vec<Rect> {
+    let offset = (area.width / 2).saturating_sub(line.width() as u16 / 2);
+    let mut x = area.x + offset;
+    line.spans
+        .iter()
+        .map(|span| {
+            let width = (span.width() as u16).min(area.right().saturating_sub(x));
+            let span_area = Rect::new(x, area.y, width, area.height.min(1));
+            x += width;
+            span_area
+        })
+        .collect()
+}
+
+/// Regions of a centered controls line, one per control from `Keymap::hint_controls`
+///
+/// The controls are laid out as `Keymap::hints` joins them, two spaces apart.
+pub fn control_targets(area: Rect, controls: &[(Key, String)]) -> Vec<(Rect, ClickTarget)> {
+    let text = controls
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("  ");
+    let Some(line_area) = centered_span_areas(area, &Line::from(text))
+        .first()
+        .copied()
+    else {
+        return Vec::new();
+    };
+    let mut x = line_area.x;
+    controls
+        .iter()
+        .map(|(key, text)| {
+            let width = Line::from(text.as_str()).width() as u16;
+            let control = Rect::new(x, line_area.y, width, 1).intersection(area);
+            x = x.saturating_add(width + 2);
+            (control, ClickTarget::Key(*key))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_targets_follow_centered_text() {
+        // 20 columns of text centered in 30 leaves an offset of 5
+        let area = Rect::new(0, 3, 30, 1);
+        let s = Key::parse("s").unwrap();
+        let i = Key::parse("i").unwrap();
+        let controls = [(s, "[s] Start".to_string()), (i, "[i/e] Int".to_string())];
+        let targets = control_targets(area, &controls);
+        assert_eq!(
+            targets,
+            vec![
+                (Rect::new(5, 3, 9, 1), ClickTarget::Key(s)),
+                (Rect::new(16, 3, 9, 1), ClickTarget::Key(i)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_later_regions_are_on_top() {
+        let hits = HitMap::default();
+        hits.add(Rect::new(0, 0, 10, 10), ClickTarget::List);
+        hits.add(Rect::new(0, 2, 10, 1), ClickTarget::HistoryRow(2));
+        assert_eq!(hits.target_at(4, 2), Some(ClickTarget::HistoryRow(2)));
+        assert_eq!(hits.target_at(4, 3), Some(ClickTarget::List));
+        assert_eq!(hits.target_at(10, 3), None);
+        hits.clear();
+        assert_eq!(hits.target_at(4, 2), None);
+    }
+}
@@ -0,0 +1,131 @@
+use std::cmp::Reverse;
+
+use crate::app::View;
+use crate::keymap::{Action, BINDINGS, Context};
+
+/// An entry of the command palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub title: &'static str,
+    /// The view the action belongs to, switched to before running it
+    pub view: Option<View>,
+    pub action: Action,
+}
+
+/// Every command the palette offers, in the order shown before anything is typed
+///
+/// They come from the key bindings, so a command does just what its keys do. One bound in a
+/// single view runs there; one bound in several runs wherever the palette was opened.
+pub fn commands() -> impl Iterator<Item = Command> {
+    BINDINGS.iter().flat_map(|binding| {
+        let view = match binding.contexts {
+            [Context::Timer] => Some(View::Timer),
+            [Context::History] => Some(View::History),
+            [Context::Stats] => Some(View::Stats),
+            [Context::Tasks] => Some(View::Tasks),
+            _ => None,
+        };
+        binding
+            .actions
+            .iter()
+            .zip(binding.palette)
+            .map(move |(&action, &title)| Command {
+                title,
+                view,
+                action,
+            })
+    })
+}
+
+/// How well `query` matches `text`, or `None` if its characters don't all appear in order
+///
+/// Case is ignored. Characters that follow each other in the text or start one of its words
+/// score higher, so "nt" ranks "New task" above "Next stats chart".
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.chars().flat_map(char::to_lowercase) {
+        if wanted.is_whitespace() {
+            continue;
+        }
+        let found = position + text[position..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 4;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+/// The commands matching `query`, best first; ties keep the table order
+pub fn matching_commands(query: &str) -> Vec<Command> {
+    let mut scored: Vec<(u32, Command)> = commands()
+        .filter_map(|command| fuzzy_score(query, command.title).map(|score| (score, command)))
+        .collect();
+    scored.sort_by_key(|&(score, _)| Reverse(score));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_needs_every_character_in_order() {
+        assert!(fuzzy_score("pse", "Pause the timer").is_some());
+        assert!(fuzzy_score("PAUSE", "Pause the timer").is_some());
+        assert_eq!(fuzzy_score("esp", "Pause the timer"), None);
+        assert_eq!(fuzzy_score("", "Quit"), Some(0));
+    }
+
+    #[test]
+    fn test_word_starts_and_runs_rank_first() {
+        let titles: Vec<&str> = matching_commands("stats")
+            .iter()
+            .map(|command| command.title)
+            .collect();
+        assert_eq!(titles[0], "Go to Stats");
+        assert!(titles.contains(&"Next stats chart"));
+
+        let titles: Vec<&str> = matching_commands("del task")
+            .iter()
+            .map(|command| command.title)
+            .collect();
+        assert_eq!(titles[0], "Delete the selected task");
+    }
+
+    #[test]
+    fn test_empty_query_lists_everything_in_order() {
+        let commands = matching_commands("");
+        assert_eq!(commands.len(), super::commands().count());
+        assert_eq!(commands[0].action, Action::StartTimer);
+    }
+
+    #[test]
+    fn test_palette_bindings_title_every_action() {
+        for binding in BINDINGS
+            .iter()
+            .filter(|binding| !binding.palette.is_empty())
+        {
+            assert_eq!(
+                binding.palette.len(),
+                binding.actions.len(),
+                "{:?}",
+                binding.description
+            );
+        }
+        let delete = commands()
+            .find(|command| command.title == "Delete the selected task")
+            .unwrap();
+        assert_eq!(delete.view, Some(View::Tasks));
+        assert_eq!(delete.action, Action::Delete);
+        assert!(commands().all(|command| command.action != Action::Palette));
+    }
+}
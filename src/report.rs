@@ -0,0 +1,133 @@
+use std::fmt::Write;
+
+use color_eyre::eyre::eyre;
+
+use crate::app::StatsPeriod;
+use crate::clock::SystemClock;
+use crate::db::{Database, DatabaseOps};
+use crate::models::{EstimateStat, Task};
+
+/// How many tasks the report lists as worst offenders
+const WORST_OFFENDER_COUNT: usize = 5;
+
+/// Print the estimation accuracy report for a period (`grindstone report [period]`)
+pub fn run_estimation_report(period: Option<&str>) -> color_eyre::Result<()> {
+    let period = match period {
+        None => StatsPeriod::Week,
+        Some(name) => StatsPeriod::from_name(name).ok_or_else(|| {
+            eyre!(
+                "unknown period \"{}\" (expected day, week, month or year)",
+                name
+            )
+        })?,
+    };
+
+    let db = Database::open()?;
+    let (start, end) = period.time_range_with_clock(&SystemClock);
+    let stats = db.get_estimates_by_category(start, end)?;
+    let tasks = db.get_finished_tasks(start, end)?;
+
+    print!("{}", format_estimation_report(period, &stats, &tasks));
+    Ok(())
+}
+
+/// Format estimated vs actual pomodoros per category, then the worst estimated tasks
+fn format_estimation_report(period: StatsPeriod, stats: &[EstimateStat], tasks: &[Task]) -> String {
+    let mut out = format!("Estimation accuracy, {}\n\n", period.label());
+    if stats.is_empty() {
+        out.push_str("No tasks finished.\n");
+        return out;
+    }
+
+    let _ = writeln!(
+        out,
+        "{:<16}{:>6}{:>6}{:>8}  Mean error",
+        "Category", "Tasks", "Est", "Actual"
+    );
+    let overall = EstimateStat::combined("All", stats);
+    for stat in stats.iter().chain(std::iter::once(&overall)) {
+        let _ = writeln!(
+            out,
+            "{:<16}{:>6}{:>6}{:>8}  {:+.1} ({})",
+            stat.name,
+            stat.task_count,
+            stat.estimated,
+            stat.actual,
+            stat.mean_error(),
+            stat.verdict()
+        );
+    }
+
+    let offenders: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.estimate_error() != 0)
+        .take(WORST_OFFENDER_COUNT)
+        .collect();
+    if !offenders.is_empty() {
+        out.push_str("\nWorst offenders\n");
+        for task in offenders {
+            let _ = writeln!(
+                out,
+                "  {:<30} estimated {:>2}, actual {:>2} ({:+})",
+                task.title,
+                task.estimate,
+                task.pomodoros,
+                task.estimate_error()
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TaskStatus;
+
+    fn done_task(title: &str, estimate: i64, pomodoros: i64) -> Task {
+        Task {
+            id: None,
+            title: title.to_string(),
+            category: "work".to_string(),
+            estimate,
+            status: TaskStatus::Done,
+            position: 1,
+            pomodoros,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_report_lists_categories_and_offenders() {
+        let stats = vec![EstimateStat {
+            name: "work".to_string(),
+            task_count: 3,
+            estimated: 6,
+            actual: 9,
+        }];
+        let tasks = vec![
+            done_task("Migrate database", 2, 5),
+            done_task("Spot on", 2, 2),
+        ];
+
+        let report = format_estimation_report(StatsPeriod::Week, &stats, &tasks);
+        assert!(report.starts_with("Estimation accuracy, this week"));
+        assert!(report.contains("+1.0 (underestimated)"));
+        assert!(report.contains("Migrate database"));
+        assert!(report.contains("(+3)"));
+        // Accurate estimates are not offenders
+        assert!(!report.contains("Spot on"));
+    }
+
+    #[test]
+    fn test_report_without_finished_tasks() {
+        let report = format_estimation_report(StatsPeriod::Day, &[], &[]);
+        assert_eq!(report, "Estimation accuracy, today\n\nNo tasks finished.\n");
+    }
+
+    #[test]
+    fn test_period_names() {
+        assert_eq!(StatsPeriod::from_name("month"), Some(StatsPeriod::Month));
+        assert_eq!(StatsPeriod::from_name("fortnight"), None);
+    }
+}
@@ -0,0 +1,111 @@
+use std::io::{self, Write};
+
+use crate::models::DesktopNotification;
+
+/// Terminator for operating system commands (BEL, the most widely accepted one)
+const OSC_END: &str = "\x07";
+
+/// Strip characters that would end an escape sequence early or inject another one
+fn sanitize(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Ring the terminal bell
+pub fn write_bells(out: &mut impl Write, count: usize) -> io::Result<()> {
+    out.write_all("\x07".repeat(count).as_bytes())?;
+    out.flush()
+}
+
+/// Ask the terminal to raise a desktop notification
+pub fn write_notification(
+    out: &mut impl Write,
+    kind: DesktopNotification,
+    title: &str,
+    body: &str,
+) -> io::Result<()> {
+    match kind {
+        DesktopNotification::Off => return Ok(()),
+        // OSC 9 has no separate title
+        DesktopNotification::Osc9 => write!(
+            out,
+            "\x1b]9;{}: {}{}",
+            sanitize(title),
+            sanitize(body),
+            OSC_END
+        )?,
+        // The title is a `;`-separated field, so it cannot contain one
+        DesktopNotification::Osc777 => write!(
+            out,
+            "\x1b]777;notify;{};{}{}",
+            sanitize(title).replace(';', ","),
+            sanitize(body),
+            OSC_END
+        )?,
+    }
+    out.flush()
+}
+
+/// Set the window title (OSC 2)
+pub fn write_title(out: &mut impl Write, title: &str) -> io::Result<()> {
+    write!(out, "\x1b]2;{}{}", sanitize(title), OSC_END)?;
+    out.flush()
+}
+
+/// Save the current window title on the terminal's title stack
+pub fn push_title(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(b"\x1b[22;2t")?;
+    out.flush()
+}
+
+/// Restore the window title saved by `push_title`
+pub fn pop_title(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(b"\x1b[23;2t")?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn captured(write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_notifications() {
+        assert_eq!(
+            captured(|out| write_notification(
+                out,
+                DesktopNotification::Osc9,
+                "Grindstone",
+                "Break over"
+            )),
+            "\x1b]9;Grindstone: Break over\x07"
+        );
+        assert_eq!(
+            captured(|out| write_notification(
+                out,
+                DesktopNotification::Osc777,
+                "Grind;stone",
+                "Break over"
+            )),
+            "\x1b]777;notify;Grind,stone;Break over\x07"
+        );
+        assert_eq!(
+            captured(|out| write_notification(out, DesktopNotification::Off, "a", "b")),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_title_cannot_break_out_of_the_sequence() {
+        assert_eq!(
+            captured(|out| write_title(out, "WORK\x07\x1b]2;evil 12:00")),
+            "\x1b]2;WORK]2;evil 12:00\x07"
+        );
+        assert_eq!(captured(push_title), "\x1b[22;2t");
+        assert_eq!(captured(pop_title), "\x1b[23;2t");
+    }
+}
@@ -0,0 +1,447 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+use thiserror::Error;
+
+use crate::models::Category;
+use crate::timer::TimerPhase;
+
+/// The colours the interface is drawn with, by the role they play
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    /// View titles
+    pub title: Color,
+    /// Borders of dialogs
+    pub border: Color,
+    /// The focused field or option
+    pub focus: Color,
+    /// Background of the selected list row
+    pub selection: Color,
+    /// Hints, labels and other secondary text
+    pub muted: Color,
+    /// Text drawn on a coloured background
+    pub highlight_text: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    /// Flow sessions, recovered sessions and profiles
+    pub info: Color,
+    /// Overtime and interruptions
+    pub special: Color,
+    pub work: Color,
+    pub short_break: Color,
+    pub long_break: Color,
+    /// No colours at all: highlights are reversed and categories drawn plain
+    pub monochrome: bool,
+}
+
+/// A theme's colour roles as named in the themes file
+const ROLES: &[&str] = &[
+    "title",
+    "border",
+    "focus",
+    "selection",
+    "muted",
+    "highlight-text",
+    "success",
+    "warning",
+    "error",
+    "info",
+    "special",
+    "work",
+    "short-break",
+    "long-break",
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub const DEFAULT_NAME: &str = "dark";
+
+    /// The original colours, for dark terminal backgrounds
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            title: Color::Blue,
+            border: Color::Cyan,
+            focus: Color::Yellow,
+            selection: Color::DarkGray,
+            muted: Color::DarkGray,
+            highlight_text: Color::Black,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            info: Color::Cyan,
+            special: Color::Magenta,
+            work: Color::Red,
+            short_break: Color::Green,
+            long_break: Color::Blue,
+            monochrome: false,
+        }
+    }
+
+    /// Darker colours that stay readable on light backgrounds
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            title: Color::Rgb(0, 70, 160),
+            border: Color::Rgb(0, 110, 150),
+            focus: Color::Rgb(175, 95, 0),
+            selection: Color::Rgb(215, 215, 215),
+            muted: Color::Rgb(110, 110, 110),
+            highlight_text: Color::White,
+            success: Color::Rgb(0, 130, 0),
+            warning: Color::Rgb(175, 95, 0),
+            error: Color::Rgb(190, 0, 0),
+            info: Color::Rgb(0, 110, 150),
+            special: Color::Rgb(150, 0, 150),
+            work: Color::Rgb(190, 0, 0),
+            short_break: Color::Rgb(0, 130, 0),
+            long_break: Color::Rgb(0, 70, 200),
+            monochrome: false,
+        }
+    }
+
+    /// Bright colours and plain gray instead of dark gray, for the most legibility
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            title: Color::LightCyan,
+            border: Color::White,
+            focus: Color::LightYellow,
+            selection: Color::Blue,
+            muted: Color::Gray,
+            highlight_text: Color::Black,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            info: Color::LightCyan,
+            special: Color::LightMagenta,
+            work: Color::LightRed,
+            short_break: Color::LightGreen,
+            long_break: Color::LightBlue,
+            monochrome: false,
+        }
+    }
+
+    /// The Solarized accent colours
+    pub fn solarized() -> Self {
+        Self {
+            name: "solarized".to_string(),
+            title: Color::Rgb(0x26, 0x8b, 0xd2),
+            border: Color::Rgb(0x2a, 0xa1, 0x98),
+            focus: Color::Rgb(0xb5, 0x89, 0x00),
+            selection: Color::Rgb(0x07, 0x36, 0x42),
+            muted: Color::Rgb(0x58, 0x6e, 0x75),
+            highlight_text: Color::Rgb(0x00, 0x2b, 0x36),
+            success: Color::Rgb(0x85, 0x99, 0x00),
+            warning: Color::Rgb(0xb5, 0x89, 0x00),
+            error: Color::Rgb(0xdc, 0x32, 0x2f),
+            info: Color::Rgb(0x2a, 0xa1, 0x98),
+            special: Color::Rgb(0xd3, 0x36, 0x82),
+            work: Color::Rgb(0xcb, 0x4b, 0x16),
+            short_break: Color::Rgb(0x85, 0x99, 0x00),
+            long_break: Color::Rgb(0x6c, 0x71, 0xc4),
+            monochrome: false,
+        }
+    }
+
+    /// The terminal's own colours only, as asked for by `NO_COLOR`
+    pub fn monochrome() -> Self {
+        Self {
+            name: "monochrome".to_string(),
+            title: Color::Reset,
+            border: Color::Reset,
+            focus: Color::Reset,
+            selection: Color::Reset,
+            muted: Color::Reset,
+            highlight_text: Color::Reset,
+            success: Color::Reset,
+            warning: Color::Reset,
+            error: Color::Reset,
+            info: Color::Reset,
+            special: Color::Reset,
+            work: Color::Reset,
+            short_break: Color::Reset,
+            long_break: Color::Reset,
+            monochrome: true,
+        }
+    }
+
+    /// The colour of a timer phase
+    pub fn phase(&self, phase: TimerPhase) -> Color {
+        match phase {
+            TimerPhase::Work => self.work,
+            TimerPhase::ShortBreak => self.short_break,
+            TimerPhase::LongBreak => self.long_break,
+        }
+    }
+
+    /// Text on a background of the given colour, reversed when there are no colours
+    pub fn highlight(&self, background: Color) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.highlight_text).bg(background)
+        }
+    }
+
+    /// The selected row of a list
+    pub fn selected(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .bg(self.selection)
+        }
+    }
+
+    /// A category's own colour, unless colours are off
+    pub fn category(&self, color: Color) -> Color {
+        if self.monochrome { Color::Reset } else { color }
+    }
+
+    /// The colour of the category with this name, muted for one that no longer exists
+    pub fn category_named(&self, categories: &[Category], name: &str) -> Color {
+        categories
+            .iter()
+            .find(|c| c.name == name)
+            .map_or(self.muted, |c| self.category(c.color))
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "title" => &mut self.title,
+            "border" => &mut self.border,
+            "focus" => &mut self.focus,
+            "selection" => &mut self.selection,
+            "muted" => &mut self.muted,
+            "highlight-text" => &mut self.highlight_text,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "info" => &mut self.info,
+            "special" => &mut self.special,
+            "work" => &mut self.work,
+            "short-break" => &mut self.short_break,
+            "long-break" => &mut self.long_break,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ThemeError {
+    #[error("could not read themes: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("themes line {line}: {message}")]
+    Syntax { line: usize, message: String },
+}
+
+/// The built-in themes followed by any defined in the themes file
+#[derive(Debug, Clone)]
+pub struct Themes {
+    themes: Vec<Theme>,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self {
+            themes: vec![
+                Theme::dark(),
+                Theme::light(),
+                Theme::high_contrast(),
+                Theme::solarized(),
+                Theme::monochrome(),
+            ],
+        }
+    }
+}
+
+impl Themes {
+    /// Load custom themes from a file, or just the built-in ones if there is no file
+    pub fn load(path: &Path) -> Result<Self, ThemeError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parse `[name]` sections of `role = colour` lines after the built-in themes
+    ///
+    /// A theme starts as a copy of `dark`, or of the theme named by a `base = name` line.
+    /// Colours are names like `red` or `light-blue`, `#rrggbb` or a 0-255 palette index.
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self, ThemeError> {
+        let mut themes = Self::default();
+        let mut current: Option<usize> = None;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let syntax = |message: String| ThemeError::Syntax {
+                line: i + 1,
+                message,
+            };
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(syntax("expected a theme name in `[name]`".to_string()));
+                }
+                if themes.get(name).is_some() {
+                    return Err(syntax(format!("theme \"{}\" is already defined", name)));
+                }
+                themes.themes.push(Theme {
+                    name: name.to_string(),
+                    ..Theme::dark()
+                });
+                current = Some(themes.themes.len() - 1);
+                continue;
+            }
+
+            let index = current.ok_or_else(|| syntax("expected `[name]` first".to_string()))?;
+            let (role, value) = line
+                .split_once('=')
+                .ok_or_else(|| syntax("expected `role = colour`".to_string()))?;
+            let (role, value) = (role.trim(), value.trim());
+            if role == "base" {
+                let base = themes
+                    .get(value)
+                    .filter(|base| base.name != themes.themes[index].name)
+                    .ok_or_else(|| syntax(format!("unknown theme \"{}\"", value)))?;
+                themes.themes[index] = Theme {
+                    name: themes.themes[index].name.clone(),
+                    ..base.clone()
+                };
+                continue;
+            }
+            let color = parse_color(value)
+                .ok_or_else(|| syntax(format!("unknown colour \"{}\"", value)))?;
+            let slot = themes.themes[index].role_mut(role).ok_or_else(|| {
+                syntax(format!(
+                    "unknown role \"{}\" (expected base or one of {})",
+                    role,
+                    ROLES.join(", ")
+                ))
+            })?;
+            *slot = color;
+        }
+        Ok(themes)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Theme> {
+        self.themes.iter().find(|theme| theme.name == name)
+    }
+
+    /// The theme after the named one, wrapping around; the first if the name is unknown
+    pub fn next_name(&self, name: &str) -> &str {
+        let next = self
+            .themes
+            .iter()
+            .position(|theme| theme.name == name)
+            .map_or(0, |i| (i + 1) % self.themes.len());
+        &self.themes[next].name
+    }
+}
+
+/// Parse a colour name, `#rrggbb` or palette index, allowing `-` or `_` between words
+fn parse_color(value: &str) -> Option<Color> {
+    Color::from_str(&value.replace(['-', '_'], "")).ok()
+}
+
+/// Whether the `NO_COLOR` convention asks for no colours (set and not empty)
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_colors() {
+        assert_eq!(parse_color("red"), Some(Color::Red));
+        assert_eq!(parse_color("light-blue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("dark_gray"), Some(Color::DarkGray));
+        assert_eq!(parse_color("#268bd2"), Some(Color::Rgb(0x26, 0x8b, 0xd2)));
+        assert_eq!(parse_color("208"), Some(Color::Indexed(208)));
+        assert_eq!(parse_color("sparkly"), None);
+    }
+
+    #[test]
+    fn test_custom_themes_build_on_a_base() {
+        let themes = Themes::parse(
+            "# mine\n[paper]\nbase = light\nwork = #ff0000\n\n[plain]\ntitle = white\n",
+        )
+        .unwrap();
+        let paper = themes.get("paper").unwrap();
+        assert_eq!(paper.work, Color::Rgb(255, 0, 0));
+        assert_eq!(paper.title, Theme::light().title);
+        let plain = themes.get("plain").unwrap();
+        assert_eq!(plain.title, Color::White);
+        assert_eq!(plain.work, Theme::dark().work);
+        // Built-in themes come first
+        assert_eq!(themes.next_name("monochrome"), "paper");
+        assert_eq!(themes.next_name("plain"), "dark");
+        assert_eq!(themes.next_name("gone"), "dark");
+    }
+
+    #[test]
+    fn test_bad_themes_are_refused() {
+        let error = |text: &str| Themes::parse(text).unwrap_err().to_string();
+        assert_eq!(
+            error("title = red"),
+            "themes line 1: expected `[name]` first"
+        );
+        assert_eq!(
+            error("[a]\ntitel = red"),
+            "themes line 2: unknown role \"titel\" (expected base or one of \
+             title, border, focus, selection, muted, highlight-text, success, warning, \
+             error, info, special, work, short-break, long-break)"
+        );
+        assert_eq!(
+            error("[a]\ntitle = sparkly"),
+            "themes line 2: unknown colour \"sparkly\""
+        );
+        assert_eq!(
+            error("[dark]"),
+            "themes line 1: theme \"dark\" is already defined"
+        );
+        assert_eq!(error("[a]\nbase = a"), "themes line 2: unknown theme \"a\"");
+    }
+
+    #[test]
+    fn test_monochrome_reverses_instead_of_colouring() {
+        let theme = Theme::monochrome();
+        assert_eq!(
+            theme.highlight(theme.work),
+            Style::default().add_modifier(Modifier::REVERSED)
+        );
+        assert_eq!(theme.category(Color::Rgb(1, 2, 3)), Color::Reset);
+        assert_eq!(
+            Theme::dark().highlight(Color::Red),
+            Style::default().fg(Color::Black).bg(Color::Red)
+        );
+    }
+    #[test]
+    fn test_unknown_category_is_muted() {
+        let theme = Theme::light();
+        let categories = Category::defaults();
+        assert_eq!(
+            theme.category_named(&categories, "work"),
+            categories[0].color
+        );
+        assert_eq!(theme.category_named(&categories, "gone"), theme.muted);
+    }
+}
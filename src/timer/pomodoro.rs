@@ -1,29 +1,14 @@
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use crate::models::Config;
+use crate::clock::{Clock, SystemClock};
+use crate::models::{Config, CycleStep, PhaseKind, TimerMode};
 
-/// The current phase of the pomodoro cycle
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum TimerPhase {
-    #[default]
-    Work,
-    ShortBreak,
-    LongBreak,
-}
-
-impl TimerPhase {
-    pub fn label(&self) -> &'static str {
-        match self {
-            TimerPhase::Work => "WORK SESSION",
-            TimerPhase::ShortBreak => "SHORT BREAK",
-            TimerPhase::LongBreak => "LONG BREAK",
-        }
-    }
-
-    pub fn is_break(&self) -> bool {
-        matches!(self, TimerPhase::ShortBreak | TimerPhase::LongBreak)
-    }
-}
+/// The current phase of the cycle, tracked by the kind of its step
+///
+/// Custom cycles give their steps their own labels and durations; the timer only needs to
+/// know whether a step is work or which kind of break it is.
+pub type TimerPhase = PhaseKind;
 
 /// The current state of the timer
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -37,6 +22,17 @@ pub enum TimerState {
     Paused {
         elapsed: Duration,
     },
+    /// The previous phase finished and the next one waits to be acknowledged
+    AwaitingAck,
+}
+
+/// Where the cycle stood when the last phase ended, and how long that phase ran
+#[derive(Debug, Clone, Copy)]
+struct EndedPhase {
+    phase: TimerPhase,
+    cycle_index: usize,
+    sessions_completed: u32,
+    elapsed: Duration,
 }
 
 /// The pomodoro timer state machine
@@ -49,6 +45,25 @@ pub struct PomodoroTimer {
     pub long_break: Duration,
     pub sessions_until_long: u32,
     pub sessions_completed: u32,
+    pub mode: TimerMode,
+    /// Flowtime breaks last the work time divided by this ratio
+    pub flow_ratio: u32,
+    pub flow_min_break: Duration,
+    pub flow_max_break: Duration,
+    /// Break earned by the last Flowtime work period
+    pub flow_break: Duration,
+    /// User-defined phases followed instead of the standard cycle when not empty
+    pub cycle: Vec<CycleStep>,
+    /// Index of the current step in `cycle`
+    pub cycle_index: usize,
+    /// Time added to the current phase by extending it
+    pub extension: Duration,
+    /// Work periods keep counting past zero until finished by hand
+    pub overtime: bool,
+    /// The phase that ended last, kept until the next one starts so it can be snoozed
+    ended: Option<EndedPhase>,
+    /// Where elapsed time is measured from
+    clock: Arc<dyn Clock>,
 }
 
 impl Default for PomodoroTimer {
@@ -62,33 +77,128 @@ impl Default for PomodoroTimer {
             long_break: Duration::from_secs(config.long_break_secs as u64),
             sessions_until_long: config.sessions_until_long_break as u32,
             sessions_completed: 0,
+            mode: config.timer_mode,
+            flow_ratio: config.flowtime_break_ratio as u32,
+            flow_min_break: Duration::from_secs(config.flowtime_min_break_secs as u64),
+            flow_max_break: Duration::from_secs(config.flowtime_max_break_secs as u64),
+            flow_break: Duration::ZERO,
+            cycle: Vec::new(),
+            cycle_index: 0,
+            extension: Duration::ZERO,
+            overtime: config.overtime,
+            ended: None,
+            clock: Arc::new(SystemClock),
         }
     }
 }
 
 impl PomodoroTimer {
-    /// Create a new pomodoro timer with default settings
-    pub fn new() -> Self {
-        Self::default()
+    /// Create a timer with default settings that measures elapsed time with the given clock
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            ..Self::default()
+        }
+    }
+
+    /// The custom cycle step the timer is on, if a custom cycle is in use
+    ///
+    /// Flowtime ignores the cycle since its work periods have no fixed length.
+    pub fn current_step(&self) -> Option<&CycleStep> {
+        match self.mode {
+            TimerMode::Pomodoro => self.cycle.get(self.cycle_index),
+            TimerMode::Flowtime => None,
+        }
     }
 
-    /// Get the duration of the current phase
+    /// Replace the custom cycle, keeping the current step when it still exists
+    ///
+    /// A phase that has not started yet takes on the kind of the step now in its place.
+    pub fn set_cycle(&mut self, steps: Vec<CycleStep>) {
+        self.cycle = steps;
+        if self.cycle_index >= self.cycle.len() {
+            self.cycle_index = 0;
+        }
+        if !self.is_running()
+            && !self.is_paused()
+            && let Some(kind) = self.current_step().map(|step| step.kind)
+        {
+            self.phase = kind;
+        }
+    }
+
+    /// Go back to the first phase of the cycle in use, idle
+    pub fn restart_cycle(&mut self) {
+        self.cycle_index = 0;
+        self.sessions_completed = 0;
+        self.phase = self
+            .current_step()
+            .map_or(TimerPhase::Work, |step| step.kind);
+        self.state = TimerState::Idle;
+        self.extension = Duration::ZERO;
+    }
+
+    /// Position of the current phase in its cycle as (1-based step, step count)
+    pub fn cycle_position(&self) -> Option<(usize, usize)> {
+        if self.mode == TimerMode::Flowtime {
+            return None;
+        }
+        if !self.cycle.is_empty() {
+            return Some((self.cycle_index + 1, self.cycle.len()));
+        }
+        let len = 2 * self.sessions_until_long as usize;
+        let done = self.sessions_completed as usize;
+        let step = match self.phase {
+            TimerPhase::Work => 2 * done + 1,
+            TimerPhase::ShortBreak => 2 * done,
+            TimerPhase::LongBreak => len,
+        };
+        Some((step, len))
+    }
+
+    /// Get the duration of the current phase, including any extension
     pub fn current_phase_duration(&self) -> Duration {
-        match self.phase {
-            TimerPhase::Work => self.work_duration,
-            TimerPhase::ShortBreak => self.short_break,
-            TimerPhase::LongBreak => self.long_break,
+        self.planned_phase_duration() + self.extension
+    }
+
+    /// Get the duration the current phase was set to before any extension
+    pub fn planned_phase_duration(&self) -> Duration {
+        if let Some(step) = self.current_step() {
+            return Duration::from_secs(step.duration_secs as u64);
+        }
+        match (self.mode, self.phase) {
+            (_, TimerPhase::Work) => self.work_duration,
+            (TimerMode::Flowtime, _) => self.flow_break,
+            (TimerMode::Pomodoro, TimerPhase::ShortBreak) => self.short_break,
+            (TimerMode::Pomodoro, TimerPhase::LongBreak) => self.long_break,
+        }
+    }
+
+    /// Label for the current phase, naming Flowtime and custom cycle phases as such
+    pub fn phase_label(&self) -> String {
+        if let Some(step) = self.current_step() {
+            return step.label.to_uppercase();
         }
+        match (self.mode, self.phase) {
+            (TimerMode::Flowtime, TimerPhase::Work) => "FLOW".to_string(),
+            (TimerMode::Flowtime, _) => "FLOW BREAK".to_string(),
+            (TimerMode::Pomodoro, phase) => phase.label().to_string(),
+        }
+    }
+
+    /// Check if the current phase counts up with no fixed end
+    pub fn counts_up(&self) -> bool {
+        self.mode == TimerMode::Flowtime && self.phase == TimerPhase::Work
     }
 
     /// Get the elapsed time in the current phase
     pub fn elapsed(&self) -> Duration {
         match self.state {
-            TimerState::Idle => Duration::ZERO,
+            TimerState::Idle | TimerState::AwaitingAck => Duration::ZERO,
             TimerState::Running {
                 started,
                 elapsed_before_pause,
-            } => elapsed_before_pause + started.elapsed(),
+            } => elapsed_before_pause + self.clock.instant().saturating_duration_since(started),
             TimerState::Paused { elapsed } => elapsed,
         }
     }
@@ -98,9 +208,48 @@ impl PomodoroTimer {
         self.current_phase_duration().saturating_sub(self.elapsed())
     }
 
+    /// Time shown on the clock: elapsed when counting up, negative in overtime, else remaining
+    pub fn display_time(&self) -> String {
+        let in_overtime = self.in_overtime();
+        let shown = if self.counts_up() {
+            self.elapsed()
+        } else if in_overtime {
+            self.overtime_elapsed()
+        } else {
+            self.remaining()
+        };
+        let sign = if in_overtime { "-" } else { "" };
+        format!(
+            "{}{:02}:{:02}",
+            sign,
+            shown.as_secs() / 60,
+            shown.as_secs() % 60
+        )
+    }
+
+    /// Check if the current phase ends only when finished by hand
+    pub fn runs_over(&self) -> bool {
+        self.overtime && self.phase == TimerPhase::Work && !self.counts_up()
+    }
+
+    /// Time spent past the end of a phase that runs into overtime
+    pub fn overtime_elapsed(&self) -> Duration {
+        if !self.runs_over() {
+            return Duration::ZERO;
+        }
+        self.elapsed().saturating_sub(self.current_phase_duration())
+    }
+
+    /// Check if the current phase has run past its end into overtime
+    pub fn in_overtime(&self) -> bool {
+        self.runs_over()
+            && (self.is_running() || self.is_paused())
+            && self.elapsed() >= self.current_phase_duration()
+    }
+
     /// Check if the current phase is finished
     pub fn is_finished(&self) -> bool {
-        self.elapsed() >= self.current_phase_duration()
+        !self.counts_up() && !self.runs_over() && self.elapsed() >= self.current_phase_duration()
     }
 
     /// Check if the timer is currently running
@@ -118,18 +267,29 @@ impl PomodoroTimer {
         matches!(self.state, TimerState::Idle)
     }
 
+    /// Check if the timer is waiting for the next phase to be acknowledged
+    pub fn is_awaiting_ack(&self) -> bool {
+        matches!(self.state, TimerState::AwaitingAck)
+    }
+
+    /// Hold the timer at the start of the current phase until acknowledged
+    pub fn await_ack(&mut self) {
+        self.state = TimerState::AwaitingAck;
+    }
+
     /// Start or resume the timer
     pub fn start(&mut self) {
+        self.ended = None;
         match self.state {
-            TimerState::Idle => {
+            TimerState::Idle | TimerState::AwaitingAck => {
                 self.state = TimerState::Running {
-                    started: Instant::now(),
+                    started: self.clock.instant(),
                     elapsed_before_pause: Duration::ZERO,
                 };
             }
             TimerState::Paused { elapsed } => {
                 self.state = TimerState::Running {
-                    started: Instant::now(),
+                    started: self.clock.instant(),
                     elapsed_before_pause: elapsed,
                 };
             }
@@ -148,13 +308,35 @@ impl PomodoroTimer {
         }
     }
 
+    /// Move the moment a paused timer stopped back by the given time
+    pub fn rewind_pause(&mut self, by: Duration) {
+        if let TimerState::Paused { elapsed } = &mut self.state {
+            *elapsed = elapsed.saturating_sub(by);
+        }
+    }
+
     /// Reset the timer to idle state
     pub fn reset(&mut self) {
         self.state = TimerState::Idle;
+        self.extension = Duration::ZERO;
+        self.ended = None;
     }
 
     /// Advance to the next phase
     pub fn advance_phase(&mut self) {
+        self.ended = Some(EndedPhase {
+            phase: self.phase,
+            cycle_index: self.cycle_index,
+            sessions_completed: self.sessions_completed,
+            elapsed: self.elapsed(),
+        });
+        if self.current_step().is_some() {
+            self.cycle_index = (self.cycle_index + 1) % self.cycle.len();
+            self.phase = self.cycle[self.cycle_index].kind;
+            self.state = TimerState::Idle;
+            self.extension = Duration::ZERO;
+            return;
+        }
         match self.phase {
             TimerPhase::Work => {
                 self.sessions_completed += 1;
@@ -170,13 +352,65 @@ impl PomodoroTimer {
             }
         }
         self.state = TimerState::Idle;
+        self.extension = Duration::ZERO;
+    }
+
+    /// Add time to the current phase; phases with no fixed end cannot be extended
+    pub fn extend(&mut self, by: Duration) {
+        if !self.counts_up() && (self.is_running() || self.is_paused()) {
+            self.extension += by;
+        }
+    }
+
+    /// The phase that just ended, while the one after it waits to be acknowledged
+    pub fn ended_phase(&self) -> Option<TimerPhase> {
+        self.ended
+            .filter(|_| self.is_awaiting_ack())
+            .map(|ended| ended.phase)
+    }
+
+    /// Go back into the phase that just ended and run it for `by` more
+    ///
+    /// Only possible while the next phase awaits acknowledgement. The time spent waiting
+    /// does not count towards the phase.
+    pub fn snooze(&mut self, by: Duration) {
+        let Some(ended) = self.ended.take().filter(|_| self.is_awaiting_ack()) else {
+            return;
+        };
+        self.phase = ended.phase;
+        self.cycle_index = ended.cycle_index;
+        self.sessions_completed = ended.sessions_completed;
+        self.extension = ended.elapsed.saturating_sub(self.planned_phase_duration()) + by;
+        self.state = TimerState::Running {
+            started: self.clock.instant(),
+            elapsed_before_pause: ended.elapsed,
+        };
+    }
+
+    /// End a Flowtime work period and move to a break proportional to the time worked
+    pub fn finish_flow(&mut self) {
+        if !self.counts_up() {
+            return;
+        }
+        self.flow_break = (self.elapsed() / self.flow_ratio.max(1))
+            .max(self.flow_min_break)
+            .min(self.flow_max_break);
+        self.phase = TimerPhase::ShortBreak;
+        self.state = TimerState::Idle;
+        self.extension = Duration::ZERO;
     }
 
     /// Skip the current break and start a new work session
+    ///
+    /// A custom cycle moves on to its next step instead.
     pub fn skip_break(&mut self) {
-        if self.phase.is_break() {
+        if self.phase.is_break() && self.current_step().is_some() {
+            self.advance_phase();
+            self.ended = None;
+        } else if self.phase.is_break() {
             self.phase = TimerPhase::Work;
             self.state = TimerState::Idle;
+            self.extension = Duration::ZERO;
         }
     }
 
@@ -186,10 +420,18 @@ impl PomodoroTimer {
         self.short_break = Duration::from_secs(config.short_break_secs as u64);
         self.long_break = Duration::from_secs(config.long_break_secs as u64);
         self.sessions_until_long = config.sessions_until_long_break as u32;
+        self.mode = config.timer_mode;
+        self.flow_ratio = config.flowtime_break_ratio as u32;
+        self.flow_min_break = Duration::from_secs(config.flowtime_min_break_secs as u64);
+        self.flow_max_break = Duration::from_secs(config.flowtime_max_break_secs as u64);
+        self.overtime = config.overtime;
     }
 
     /// Get the progress as a ratio (0.0 to 1.0)
     pub fn progress(&self) -> f64 {
+        if self.counts_up() {
+            return 0.0;
+        }
         let total = self.current_phase_duration().as_secs_f64();
         if total == 0.0 {
             return 1.0;
@@ -203,9 +445,13 @@ impl PomodoroTimer {
 mod tests {
     use super::*;
 
+    fn new_timer() -> PomodoroTimer {
+        PomodoroTimer::new(Arc::new(SystemClock))
+    }
+
     #[test]
     fn test_new_timer_is_idle() {
-        let timer = PomodoroTimer::new();
+        let timer = new_timer();
         assert!(timer.is_idle());
         assert_eq!(timer.phase, TimerPhase::Work);
         assert_eq!(timer.elapsed(), Duration::ZERO);
@@ -213,14 +459,14 @@ mod tests {
 
     #[test]
     fn test_start_changes_state_to_running() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.start();
         assert!(timer.is_running());
     }
 
     #[test]
     fn test_pause_and_resume() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.start();
         std::thread::sleep(Duration::from_millis(10));
         timer.pause();
@@ -235,7 +481,7 @@ mod tests {
 
     #[test]
     fn test_reset_returns_to_idle() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.start();
         timer.reset();
         assert!(timer.is_idle());
@@ -244,7 +490,7 @@ mod tests {
 
     #[test]
     fn test_phase_advancement() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.advance_phase();
         assert_eq!(timer.phase, TimerPhase::ShortBreak);
         assert_eq!(timer.sessions_completed, 1);
@@ -255,7 +501,7 @@ mod tests {
 
     #[test]
     fn test_long_break_after_four_sessions() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         for _ in 0..3 {
             timer.advance_phase(); // Work -> ShortBreak
             timer.advance_phase(); // ShortBreak -> Work
@@ -267,7 +513,7 @@ mod tests {
 
     #[test]
     fn test_sessions_completed_with_threshold_one() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.sessions_until_long = 1;
         timer.sessions_completed = 0;
 
@@ -279,7 +525,7 @@ mod tests {
 
     #[test]
     fn test_zero_duration_work_phase() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.work_duration = Duration::ZERO;
 
         // Zero duration means immediately finished
@@ -289,7 +535,7 @@ mod tests {
 
     #[test]
     fn test_zero_duration_progress() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.work_duration = Duration::ZERO;
 
         // Progress should be 1.0 (100%) for zero duration to avoid division by zero
@@ -298,7 +544,7 @@ mod tests {
 
     #[test]
     fn test_zero_duration_break_phase() {
-        let mut timer = PomodoroTimer::new();
+        let mut timer = new_timer();
         timer.short_break = Duration::ZERO;
         timer.phase = TimerPhase::ShortBreak;
 
@@ -306,4 +552,165 @@ mod tests {
         assert!(timer.is_finished());
         assert_eq!(timer.progress(), 1.0);
     }
+
+    #[test]
+    fn test_awaiting_ack_starts_fresh() {
+        let mut timer = new_timer();
+        timer.advance_phase();
+        timer.await_ack();
+        assert!(timer.is_awaiting_ack());
+        assert!(!timer.is_running());
+        assert_eq!(timer.remaining(), timer.short_break);
+
+        timer.start();
+        assert!(timer.is_running());
+        assert!(!timer.is_awaiting_ack());
+    }
+
+    #[test]
+    fn test_flowtime_work_counts_up_until_finished() {
+        let mut timer = new_timer();
+        timer.mode = TimerMode::Flowtime;
+        timer.work_duration = Duration::ZERO;
+        timer.state = TimerState::Paused {
+            elapsed: Duration::from_secs(50 * 60),
+        };
+        assert!(timer.counts_up());
+        assert!(!timer.is_finished());
+        assert_eq!(timer.progress(), 0.0);
+
+        timer.finish_flow();
+        assert_eq!(timer.phase, TimerPhase::ShortBreak);
+        assert!(timer.is_idle());
+        assert_eq!(timer.flow_break, Duration::from_secs(10 * 60));
+        assert_eq!(timer.remaining(), Duration::from_secs(10 * 60));
+        assert_eq!(timer.sessions_completed, 0);
+    }
+
+    #[test]
+    fn test_flowtime_break_respects_bounds() {
+        let mut timer = new_timer();
+        timer.mode = TimerMode::Flowtime;
+        timer.state = TimerState::Paused {
+            elapsed: Duration::from_secs(60),
+        };
+        timer.finish_flow();
+        assert_eq!(timer.flow_break, timer.flow_min_break);
+
+        timer.advance_phase();
+        timer.state = TimerState::Paused {
+            elapsed: Duration::from_secs(5 * 60 * 60),
+        };
+        timer.finish_flow();
+        assert_eq!(timer.flow_break, timer.flow_max_break);
+    }
+
+    fn step(label: &str, kind: TimerPhase, minutes: i64) -> CycleStep {
+        CycleStep {
+            id: None,
+            label: label.to_string(),
+            kind,
+            duration_secs: minutes * 60,
+        }
+    }
+
+    #[test]
+    fn test_custom_cycle_follows_steps_and_wraps() {
+        let mut timer = new_timer();
+        timer.set_cycle(vec![
+            step("Warm-up", TimerPhase::Work, 5),
+            step("Work", TimerPhase::Work, 50),
+            step("Break", TimerPhase::ShortBreak, 10),
+            step("Long break", TimerPhase::LongBreak, 30),
+        ]);
+        assert_eq!(timer.phase_label(), "WARM-UP");
+        assert_eq!(timer.remaining(), Duration::from_secs(5 * 60));
+        assert_eq!(timer.cycle_position(), Some((1, 4)));
+
+        timer.advance_phase();
+        assert_eq!(timer.phase, TimerPhase::Work);
+        assert_eq!(timer.current_phase_duration(), Duration::from_secs(50 * 60));
+
+        timer.advance_phase();
+        assert_eq!(timer.phase, TimerPhase::ShortBreak);
+        assert_eq!(timer.cycle_position(), Some((3, 4)));
+
+        // Skipping a break moves to the next step rather than back to work
+        timer.skip_break();
+        assert_eq!(timer.phase, TimerPhase::LongBreak);
+
+        timer.advance_phase();
+        assert_eq!(timer.cycle_position(), Some((1, 4)));
+        assert_eq!(timer.phase_label(), "WARM-UP");
+    }
+
+    #[test]
+    fn test_standard_cycle_position() {
+        let mut timer = new_timer();
+        assert_eq!(timer.cycle_position(), Some((1, 8)));
+        timer.advance_phase();
+        assert_eq!(timer.cycle_position(), Some((2, 8)));
+        timer.advance_phase();
+        assert_eq!(timer.cycle_position(), Some((3, 8)));
+        for _ in 0..5 {
+            timer.advance_phase();
+        }
+        assert_eq!(timer.phase, TimerPhase::LongBreak);
+        assert_eq!(timer.cycle_position(), Some((8, 8)));
+
+        timer.mode = TimerMode::Flowtime;
+        assert_eq!(timer.cycle_position(), None);
+    }
+
+    #[test]
+    fn test_extend_adds_to_remaining_until_phase_changes() {
+        let mut timer = new_timer();
+        timer.extend(Duration::from_secs(300));
+        assert_eq!(timer.extension, Duration::ZERO);
+
+        timer.state = TimerState::Paused {
+            elapsed: Duration::from_secs(25 * 60),
+        };
+        assert!(timer.is_finished());
+        timer.extend(Duration::from_secs(300));
+        timer.extend(Duration::from_secs(300));
+        assert!(!timer.is_finished());
+        assert_eq!(timer.remaining(), Duration::from_secs(600));
+        assert_eq!(timer.planned_phase_duration(), timer.work_duration);
+        assert!(timer.progress() < 1.0);
+
+        timer.advance_phase();
+        assert_eq!(timer.extension, Duration::ZERO);
+        assert_eq!(timer.remaining(), timer.short_break);
+    }
+
+    #[test]
+    fn test_overtime_keeps_work_going_past_zero() {
+        let mut timer = new_timer();
+        timer.overtime = true;
+        timer.state = TimerState::Paused {
+            elapsed: Duration::from_secs(27 * 60),
+        };
+        assert!(!timer.is_finished());
+        assert!(timer.in_overtime());
+        assert_eq!(timer.overtime_elapsed(), Duration::from_secs(2 * 60));
+
+        // Breaks still end on their own
+        timer.phase = TimerPhase::ShortBreak;
+        assert!(timer.is_finished());
+        assert!(!timer.in_overtime());
+    }
+
+    #[test]
+    fn test_rewind_pause_gives_back_time() {
+        let mut timer = new_timer();
+        timer.state = TimerState::Paused {
+            elapsed: Duration::from_secs(10 * 60),
+        };
+        timer.rewind_pause(Duration::from_secs(3 * 60));
+        assert_eq!(timer.elapsed(), Duration::from_secs(7 * 60));
+
+        timer.rewind_pause(Duration::from_secs(60 * 60));
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+    }
 }
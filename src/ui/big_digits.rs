@@ -0,0 +1,57 @@
+/// Height of every glyph in font pixels
+const GLYPH_HEIGHT: usize = 5;
+
+/// A block font for the clock, `#` marking a filled pixel
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 12] = [
+    ('0', ["###", "# #", "# #", "# #", "###"]),
+    ('1', ["  #", "  #", "  #", "  #", "  #"]),
+    ('2', ["###", "  #", "###", "#  ", "###"]),
+    ('3', ["###", "  #", "###", "  #", "###"]),
+    ('4', ["# #", "# #", "###", "  #", "  #"]),
+    ('5', ["###", "#  ", "###", "  #", "###"]),
+    ('6', ["###", "#  ", "###", "# #", "###"]),
+    ('7', ["###", "  #", "  #", "  #", "  #"]),
+    ('8', ["###", "# #", "###", "# #", "###"]),
+    ('9', ["###", "# #", "###", "  #", "###"]),
+    (':', [" ", "#", " ", "#", " "]),
+    ('-', ["   ", "   ", "###", "   ", "   "]),
+];
+
+fn glyph(c: char) -> Option<&'static [&'static str; GLYPH_HEIGHT]> {
+    GLYPHS.iter().find(|(g, _)| *g == c).map(|(_, rows)| rows)
+}
+
+/// The rows of `text` drawn in the block font, as large as fits in `width` by `height`
+///
+/// Pixels are twice as wide as they are tall so the digits keep their shape in terminal
+/// cells, unless only the smallest square size fits. `None` when nothing fits or the text
+/// has characters the font lacks, so the caller can fall back to plain text.
+pub fn big_text(text: &str, width: u16, height: u16) -> Option<Vec<String>> {
+    let glyphs = text.chars().map(glyph).collect::<Option<Vec<_>>>()?;
+    // Font pixels across, with a blank pixel between glyphs
+    let pixels_wide: usize =
+        glyphs.iter().map(|g| g[0].len()).sum::<usize>() + glyphs.len().saturating_sub(1);
+    let (width, height) = (width as usize, height as usize);
+
+    let fits = |sx: usize, sy: usize| pixels_wide * sx <= width && GLYPH_HEIGHT * sy <= height;
+    let (sx, sy) = (1..=height / GLYPH_HEIGHT)
+        .rev()
+        .map(|sy| (2 * sy, sy))
+        .chain(std::iter::once((1, 1)))
+        .find(|&(sx, sy)| fits(sx, sy))?;
+
+    let rows = (0..GLYPH_HEIGHT).flat_map(|row| {
+        let line: String = glyphs
+            .iter()
+            .map(|g| {
+                g[row]
+                    .chars()
+                    .map(|pixel| if pixel == '#' { "█" } else { " " }.repeat(sx))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(&" ".repeat(sx));
+        std::iter::repeat_n(line, sy)
+    });
+    Some(rows.collect())
+}
@@ -0,0 +1,101 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{LineGauge, Paragraph},
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::ui;
+
+use super::timer::time_colors;
+
+/// Render the timer in as few rows as possible, for small panes
+///
+/// One line holds the phase, time left and session name and one the progress bar; a third,
+/// if there is room, shows notifications or the main timer keys.
+pub fn render_compact(frame: &mut Frame, area: Rect, app: &App) {
+    let rows = Layout::vertical([
+        Constraint::Length(1), // Phase, time and session
+        Constraint::Length(1), // Progress bar
+        Constraint::Length(1), // Keys or notification
+    ])
+    .split(area);
+
+    let (display_color, gauge_color) = time_colors(app);
+    let timer_color = app.theme.phase(app.timer.phase);
+    let status = if app.timer.is_awaiting_ack() {
+        " (DONE)"
+    } else if app.timer.in_overtime() {
+        " (OVERTIME)"
+    } else if app.timer.is_running() {
+        ""
+    } else if app.timer.is_paused() {
+        " (PAUSED)"
+    } else {
+        " (READY)"
+    };
+    let phase_style = if app.timer.is_awaiting_ack() && app.alert_flash_on() {
+        app.theme.highlight(timer_color).bold()
+    } else {
+        Style::default().fg(timer_color).bold()
+    };
+    let session = app
+        .current_session()
+        .map(|session| format!("  \"{}\"", session.name))
+        .unwrap_or_default();
+    let status_line = Line::from(vec![
+        Span::styled(app.timer.phase_label(), phase_style),
+        Span::raw(status),
+        Span::raw("  "),
+        Span::styled(
+            app.timer.display_time(),
+            Style::default().fg(display_color).bold(),
+        ),
+        Span::styled(session, Style::default().fg(app.theme.muted)),
+    ]);
+    frame.render_widget(Paragraph::new(status_line).centered(), rows[0]);
+
+    let gauge = LineGauge::default()
+        .filled_style(Style::default().fg(gauge_color))
+        .unfilled_style(Style::default().fg(app.theme.muted))
+        .label("")
+        .ratio(app.timer.progress());
+    frame.render_widget(gauge, rows[1]);
+
+    // Only the keys that do something in the timer's current state
+    let quit: (&[Action], &str) = (&[Action::Quit], "Quit");
+    let pause: (&[Action], &str) = (&[Action::PauseTimer], "Pause");
+    let skip_break: (&[Action], &str) = (&[Action::StartTimer], "Skip Break");
+    let stop: (&[Action], &str) = if app.timer.counts_up() || app.timer.in_overtime() {
+        (&[Action::StopTimer], "Finish")
+    } else {
+        (&[Action::StopTimer], "Stop")
+    };
+    let keys = if app.timer.is_awaiting_ack() {
+        vec![
+            (&[Action::Confirm][..], "Continue"),
+            (&[Action::ResetTimer], "Reset"),
+            quit,
+        ]
+    } else if app.timer.phase.is_break() && app.timer.is_running() {
+        vec![skip_break, pause, quit]
+    } else if app.timer.phase.is_break() {
+        vec![skip_break, quit]
+    } else if app.timer.is_running() {
+        vec![pause, stop, quit]
+    } else if app.timer.is_paused() {
+        vec![(&[Action::StartTimer][..], "Resume"), stop, quit]
+    } else if app.current_session().is_some() {
+        vec![
+            (&[Action::StartTimer][..], "Start"),
+            (&[Action::New], "New"),
+            quit,
+        ]
+    } else {
+        vec![(&[Action::New][..], "New"), quit]
+    };
+    ui::render_footer(frame, rows[2], app, &app.keymap.hints(&keys));
+}
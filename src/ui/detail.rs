@@ -1,19 +1,29 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
 use crate::app::App;
-use crate::models::Category;
+use crate::models::{InterruptionKind, Session, TimerMode};
+use crate::theme::Theme;
 
 /// Render the session detail modal as an overlay
 pub fn render_detail_modal(frame: &mut Frame, area: Rect, app: &App) {
+    let selected = app.data.sessions.get(app.detail.selected_session_index);
+    let timeline = selected
+        .map(|session| build_timeline_lines(session, &app.theme))
+        .unwrap_or_default();
+    let interruptions = selected
+        .map(|session| build_interruption_lines(session, &app.theme))
+        .unwrap_or_default();
+
     // Calculate modal size and position (centered)
     let modal_width = 60.min(area.width.saturating_sub(4));
-    let modal_height = 16.min(area.height.saturating_sub(4));
+    let modal_height = (18 + timeline.len() as u16 + interruptions.len() as u16)
+        .min(area.height.saturating_sub(4));
     let modal_x = (area.width.saturating_sub(modal_width)) / 2;
     let modal_y = (area.height.saturating_sub(modal_height)) / 2;
 
@@ -26,7 +36,7 @@ pub fn render_detail_modal(frame: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
         .title(" Session Details ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(app.theme.border));
 
     let inner = block.inner(modal_area);
     frame.render_widget(block, modal_area);
@@ -36,21 +46,25 @@ pub fn render_detail_modal(frame: &mut Frame, area: Rect, app: &App) {
     if session_idx >= app.data.sessions.len() {
         // Invalid index, show error
         let error_text = "Error: Session not found";
-        frame.render_widget(Paragraph::new(error_text).centered().fg(Color::Red), inner);
+        frame.render_widget(
+            Paragraph::new(error_text).centered().fg(app.theme.error),
+            inner,
+        );
         return;
     }
 
     let session = &app.data.sessions[session_idx];
 
     let chunks = Layout::vertical([
-        Constraint::Length(2), // Name
-        Constraint::Length(2), // Category
-        Constraint::Length(4), // Description
-        Constraint::Length(2), // Duration
-        Constraint::Length(2), // Started
-        Constraint::Length(2), // Ended
-        Constraint::Length(1), // Spacer
-        Constraint::Length(1), // Controls
+        Constraint::Length(2),                         // Name
+        Constraint::Length(2),                         // Category
+        Constraint::Length(4),                         // Description
+        Constraint::Length(2),                         // Duration and outcome
+        Constraint::Length(2),                         // Started
+        Constraint::Length(2),                         // Ended
+        Constraint::Length(timeline.len() as u16 + 1), // Timeline
+        Constraint::Min(1),                            // Interruptions
+        Constraint::Length(1),                         // Controls
     ])
     .split(inner);
 
@@ -62,7 +76,9 @@ pub fn render_detail_modal(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(Paragraph::new(name_line), chunks[0]);
 
     // Category with color
-    let cat_color = get_category_color(&app.data.categories, &session.category);
+    let cat_color = app
+        .theme
+        .category_named(&app.data.categories, &session.category);
     let category_line = Line::from(vec![
         Span::styled("Category: ", Style::default().bold()),
         Span::styled("■ ", Style::default().fg(cat_color)),
@@ -77,7 +93,7 @@ pub fn render_detail_modal(frame: &mut Frame, area: Rect, app: &App) {
         "(no description)".to_string()
     };
     let desc_style = if session.description.is_none() {
-        Style::default().dark_gray()
+        Style::default().fg(app.theme.muted)
     } else {
         Style::default()
     };
@@ -87,15 +103,37 @@ pub fn render_detail_modal(frame: &mut Frame, area: Rect, app: &App) {
     ];
     frame.render_widget(Paragraph::new(desc_line), chunks[2]);
 
-    // Duration
-    let duration_line = Line::from(vec![
-        Span::styled("Duration: ", Style::default().bold()),
-        Span::styled(
-            session.format_duration(),
-            Style::default().fg(Color::Yellow),
-        ),
-    ]);
-    frame.render_widget(Paragraph::new(duration_line), chunks[3]);
+    // Duration and outcome
+    let duration_lines = vec![
+        Line::from(vec![
+            Span::styled("Duration: ", Style::default().bold()),
+            Span::styled(
+                session.format_duration(),
+                Style::default().fg(app.theme.warning),
+            ),
+            Span::styled(
+                match session.mode {
+                    TimerMode::Pomodoro if session.overtime_secs.as_secs() > 0 => format!(
+                        " of {} planned, {} overtime",
+                        session.planned_secs.format(),
+                        session.overtime_secs.format()
+                    ),
+                    TimerMode::Pomodoro => format!(" of {} planned", session.planned_secs.format()),
+                    TimerMode::Flowtime => " in flowtime".to_string(),
+                },
+                Style::default().fg(app.theme.muted),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Outcome: ", Style::default().bold()),
+            Span::raw(format!(
+                "{} {}",
+                session.outcome.icon(),
+                session.outcome.as_str()
+            )),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(duration_lines), chunks[3]);
 
     // Start time
     let start_dt = session.start_datetime();
@@ -115,19 +153,97 @@ pub fn render_detail_modal(frame: &mut Frame, area: Rect, app: &App) {
     ]);
     frame.render_widget(Paragraph::new(end_line), chunks[5]);
 
+    // Segment timeline
+    let mut timeline_lines = vec![Line::from(vec![
+        Span::styled("Timeline: ", Style::default().bold()),
+        Span::styled(
+            format!(
+                "{} pause(s), {} paused",
+                session.pause_count(),
+                session.paused_secs().format()
+            ),
+            Style::default().fg(app.theme.muted),
+        ),
+    ])];
+    timeline_lines.extend(timeline);
+    frame.render_widget(Paragraph::new(timeline_lines), chunks[6]);
+
+    // Interruptions
+    let mut interruption_lines = vec![Line::from(vec![
+        Span::styled("Interruptions: ", Style::default().bold()),
+        Span::styled(
+            format!(
+                "{} internal, {} external",
+                session.interruption_count(InterruptionKind::Internal),
+                session.interruption_count(InterruptionKind::External)
+            ),
+            Style::default().fg(app.theme.muted),
+        ),
+    ])];
+    interruption_lines.extend(interruptions);
+    frame.render_widget(Paragraph::new(interruption_lines), chunks[7]);
+
     // Controls
     let controls = Line::from(vec![
         Span::styled("[Esc]", Style::default().bold()),
         Span::raw(" Close"),
     ]);
-    frame.render_widget(Paragraph::new(controls).centered().dark_gray(), chunks[7]);
+    frame.render_widget(
+        Paragraph::new(controls).centered().fg(app.theme.muted),
+        chunks[8],
+    );
+}
+
+/// Build one line per running segment, with the pause gaps between them
+fn build_timeline_lines(session: &Session, theme: &Theme) -> Vec<Line<'static>> {
+    if session.segments.is_empty() {
+        return vec![Line::from(Span::styled(
+            "  (no segments recorded)",
+            Style::default().fg(theme.muted),
+        ))];
+    }
+
+    let mut lines = Vec::new();
+    for (i, segment) in session.segments.iter().enumerate() {
+        if i > 0 {
+            let gap = segment.started_at - session.segments[i - 1].ended_at;
+            lines.push(Line::from(Span::styled(
+                format!("    paused {}", gap.format()),
+                Style::default().fg(theme.muted),
+            )));
+        }
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "  {} - {}  ",
+                segment.started_at.to_datetime().format("%H:%M"),
+                segment.ended_at.to_datetime().format("%H:%M"),
+            )),
+            Span::styled(
+                format!("running {}", segment.duration().format()),
+                Style::default().fg(theme.success),
+            ),
+        ]));
+    }
+    lines
 }
 
-/// Look up a category's color by name, with gray fallback
-fn get_category_color(categories: &[Category], name: &str) -> Color {
-    categories
+/// Build one line per logged interruption with its time and note
+fn build_interruption_lines(session: &Session, theme: &Theme) -> Vec<Line<'static>> {
+    session
+        .interruptions
         .iter()
-        .find(|c| c.name == name)
-        .map(|c| c.color)
-        .unwrap_or(Color::Gray)
+        .map(|i| {
+            Line::from(vec![
+                Span::raw(format!(
+                    "  {}  ",
+                    i.occurred_at.to_datetime().format("%H:%M")
+                )),
+                Span::styled(
+                    format!("{} {:<9}", i.kind.mark(), i.kind.as_str()),
+                    Style::default().fg(theme.special),
+                ),
+                Span::raw(i.note.clone().unwrap_or_default()),
+            ])
+        })
+        .collect()
 }
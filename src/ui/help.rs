@@ -0,0 +1,75 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+
+/// Render the key bindings of the current context as an overlay
+///
+/// The rows come straight from the keymap, so they always match what the keys do.
+pub fn render_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let context = app.key_context();
+    let rows = app.keymap.help(context);
+    let key_width = rows
+        .iter()
+        .map(|(keys, _)| Line::from(keys.as_str()).width())
+        .max()
+        .unwrap_or(0);
+    let row_width = rows
+        .iter()
+        .map(|(_, description)| key_width + 2 + description.len())
+        .max()
+        .unwrap_or(0);
+
+    // Size the modal to its rows, within the terminal
+    let modal_width = (row_width as u16 + 4)
+        .max(30)
+        .min(area.width.saturating_sub(4));
+    let modal_height = (rows.len() as u16 + 4).min(area.height.saturating_sub(2));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    // Clear the area behind the modal
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Keys: {} ", context.title()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // Bindings
+        Constraint::Length(1), // Footer
+    ])
+    .split(inner);
+
+    let lines: Vec<Line> = rows
+        .iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:>width$}  ", keys, width = key_width),
+                    Style::default().fg(app.theme.focus).bold(),
+                ),
+                Span::raw(*description),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new("Press any key to close")
+            .centered()
+            .fg(app.theme.muted),
+        chunks[1],
+    );
+}
@@ -2,13 +2,16 @@ use chrono::{Datelike, Local};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
 
-use crate::app::App;
-use crate::models::Category;
+use crate::app::{App, HistoryEntry, View};
+use crate::keymap::Action;
+use crate::models::{BreakPeriod, Category, InterruptionKind, Session, SessionOutcome, TimerMode};
+use crate::mouse::ClickTarget;
+use crate::theme::Theme;
 use crate::ui;
 
 /// Render the history view
@@ -22,59 +25,78 @@ pub fn render_history(frame: &mut Frame, area: Rect, app: &mut App) {
     .split(area);
 
     // Title
-    let title = Line::from("Session History").bold().blue().centered();
+    let title = Line::from("Session History")
+        .bold()
+        .fg(app.theme.title)
+        .centered();
     frame.render_widget(
         Paragraph::new(title).block(Block::default().borders(Borders::BOTTOM)),
         chunks[0],
     );
 
     // Session list grouped by day
-    let items: Vec<ListItem> = build_history_items(&app.data.sessions, &app.data.categories);
+    let items: Vec<ListItem> = build_history_items(
+        &app.data.history_entries(),
+        &app.data.categories,
+        &app.theme,
+    );
+    let item_count = items.len();
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::NONE))
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .bg(Color::DarkGray),
-        )
+        .highlight_style(app.theme.selected())
         .highlight_symbol("> ");
 
     frame.render_stateful_widget(list, chunks[1], &mut app.data.history_state);
 
+    // Every item is one line, so visible rows follow on from the scroll offset
+    app.hits.add(chunks[1], ClickTarget::List);
+    let offset = app.data.history_state.offset();
+    for (row, index) in (offset..item_count)
+        .take(chunks[1].height as usize)
+        .enumerate()
+    {
+        let row_area = Rect::new(chunks[1].x, chunks[1].y + row as u16, chunks[1].width, 1);
+        app.hits.add(row_area, ClickTarget::HistoryRow(index));
+    }
+
     // Controls
-    let controls = "[j/k] Navigate  [Enter] Details  [d] Delete";
+    let controls = app.keymap.hints(&[
+        (&[Action::SelectNext, Action::SelectPrev], "Navigate"),
+        (&[Action::Confirm], "Details"),
+        (&[Action::Delete], "Delete"),
+        (
+            &[Action::ToggleBreaks],
+            if app.data.show_breaks {
+                "Hide Breaks"
+            } else {
+                "Show Breaks"
+            },
+        ),
+    ]);
     frame.render_widget(
         Paragraph::new(controls)
             .centered()
-            .dark_gray()
+            .fg(app.theme.muted)
             .block(Block::default().borders(Borders::TOP)),
         chunks[2],
     );
 
     // Footer / notification
-    ui::render_footer(frame, chunks[3], app, "[Tab] Timer  [t] Stats  [q] Quit");
-}
-
-/// Look up a category's color by name, with gray fallback
-fn get_category_color(categories: &[Category], name: &str) -> Color {
-    categories
-        .iter()
-        .find(|c| c.name == name)
-        .map(|c| c.color)
-        .unwrap_or(Color::Gray)
+    ui::render_footer(frame, chunks[3], app, &app.keymap.nav_hints(View::History));
 }
 
 fn build_history_items(
-    sessions: &[crate::models::Session],
+    entries: &[HistoryEntry],
     categories: &[Category],
+    theme: &Theme,
 ) -> Vec<ListItem<'static>> {
     let mut items = Vec::new();
     let mut current_date: Option<(i32, u32, u32)> = None;
     let today = Local::now().date_naive();
 
-    for session in sessions {
-        let dt = session.start_datetime();
+    for entry in entries {
+        let dt = entry.started_at().to_datetime();
         let date = (dt.year(), dt.month(), dt.day());
 
         // Add date header if new day
@@ -93,26 +115,12 @@ fn build_history_items(
             )])));
         }
 
-        // Session line
-        let start_time = session.start_datetime().format("%H:%M");
-        let end_time = session.end_datetime().format("%H:%M");
-        let duration = session.format_duration();
-        let cat_color = get_category_color(categories, &session.category);
-
-        let line = Line::from(vec![
-            Span::styled("  ", Style::default()),
-            Span::styled(session.name.clone(), Style::default().bold()),
-            Span::raw("  "),
-            Span::styled(session.category.clone(), Style::default().fg(cat_color)),
-            Span::raw("  "),
-            Span::styled(duration, Style::default().fg(Color::Yellow)),
-            Span::raw("  "),
-            Span::styled(
-                format!("{} - {}", start_time, end_time),
-                Style::default().dark_gray(),
-            ),
-        ]);
-        items.push(ListItem::new(line));
+        match entry {
+            HistoryEntry::Session { session, .. } => {
+                items.push(build_session_item(session, categories, theme));
+            }
+            HistoryEntry::Break(period) => items.push(build_break_item(period, theme)),
+        }
     }
 
     if items.is_empty() {
@@ -123,3 +131,81 @@ fn build_history_items(
 
     items
 }
+
+fn build_session_item(
+    session: &Session,
+    categories: &[Category],
+    theme: &Theme,
+) -> ListItem<'static> {
+    let start_time = session.start_datetime().format("%H:%M");
+    let end_time = session.end_datetime().format("%H:%M");
+    let duration = session.format_duration();
+    let cat_color = theme.category_named(categories, &session.category);
+
+    let outcome_color = match session.outcome {
+        SessionOutcome::Completed => theme.success,
+        SessionOutcome::Stopped => theme.warning,
+        SessionOutcome::Abandoned => theme.error,
+        SessionOutcome::Recovered => theme.info,
+    };
+
+    let mut spans = vec![
+        Span::styled("  ", Style::default()),
+        Span::styled(
+            format!("{} ", session.outcome.icon()),
+            Style::default().fg(outcome_color),
+        ),
+        Span::styled(session.name.clone(), Style::default().bold()),
+        Span::raw("  "),
+        Span::styled(session.category.clone(), Style::default().fg(cat_color)),
+        Span::raw("  "),
+        Span::styled(duration, Style::default().fg(theme.warning)),
+        Span::raw("  "),
+        Span::styled(
+            format!("{} - {}", start_time, end_time),
+            Style::default().fg(theme.muted),
+        ),
+    ];
+    if session.mode == TimerMode::Flowtime {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled("flow", Style::default().fg(theme.info)));
+    }
+    if !session.interruptions.is_empty() {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!(
+                "'{} -{}",
+                session.interruption_count(InterruptionKind::Internal),
+                session.interruption_count(InterruptionKind::External)
+            ),
+            Style::default().fg(theme.special),
+        ));
+    }
+    ListItem::new(Line::from(spans))
+}
+
+fn build_break_item(period: &BreakPeriod, theme: &Theme) -> ListItem<'static> {
+    let mut spans = vec![
+        Span::raw("  "),
+        Span::styled(
+            period.kind.label(),
+            Style::default().italic().fg(theme.muted),
+        ),
+        Span::raw("  "),
+        Span::styled(period.duration().format(), Style::default().fg(theme.muted)),
+        Span::raw("  "),
+        Span::styled(
+            format!(
+                "{} - {}",
+                period.started_at.to_datetime().format("%H:%M"),
+                period.ended_at.to_datetime().format("%H:%M")
+            ),
+            Style::default().fg(theme.muted),
+        ),
+    ];
+    if period.skipped {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled("skipped", Style::default().fg(theme.error)));
+    }
+    ListItem::new(Line::from(spans))
+}
@@ -1,18 +1,21 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use crossterm::event::KeyCode;
+
 use crate::app::{App, InputField};
+use crate::mouse::{ClickTarget, centered_span_areas};
 
 /// Render the session input modal as an overlay
 pub fn render_input_modal(frame: &mut Frame, area: Rect, app: &App) {
     // Calculate modal size and position (centered)
     let modal_width = 50.min(area.width.saturating_sub(4));
-    let modal_height = 12.min(area.height.saturating_sub(4));
+    let modal_height = 14.min(area.height.saturating_sub(4));
     let modal_x = (area.width.saturating_sub(modal_width)) / 2;
     let modal_y = (area.height.saturating_sub(modal_height)) / 2;
 
@@ -25,7 +28,7 @@ pub fn render_input_modal(frame: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
         .title(" New Session ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(app.theme.border));
 
     let inner = block.inner(modal_area);
     frame.render_widget(block, modal_area);
@@ -34,13 +37,14 @@ pub fn render_input_modal(frame: &mut Frame, area: Rect, app: &App) {
         Constraint::Length(3), // Name field
         Constraint::Length(3), // Description field
         Constraint::Length(2), // Category selector
+        Constraint::Length(2), // Task selector
         Constraint::Length(2), // Controls
     ])
     .split(inner);
 
     // Name field
     let name_style = if app.input.field == InputField::Name {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(app.theme.focus)
     } else {
         Style::default()
     };
@@ -54,10 +58,12 @@ pub fn render_input_modal(frame: &mut Frame, area: Rect, app: &App) {
         app.input.name.to_string()
     };
     frame.render_widget(Paragraph::new(name_text).block(name_block), chunks[0]);
+    app.hits
+        .add(chunks[0], ClickTarget::InputField(InputField::Name, None));
 
     // Description field
     let desc_style = if app.input.field == InputField::Description {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(app.theme.focus)
     } else {
         Style::default()
     };
@@ -71,24 +77,52 @@ pub fn render_input_modal(frame: &mut Frame, area: Rect, app: &App) {
         app.input.description.to_string()
     };
     frame.render_widget(Paragraph::new(desc_text).block(desc_block), chunks[1]);
+    app.hits.add(
+        chunks[1],
+        ClickTarget::InputField(InputField::Description, None),
+    );
 
     // Category selector
     let selected_cat = &app.data.categories[app.input.selected_category];
     let cat_style = if app.input.field == InputField::Category {
-        Style::default().fg(Color::Yellow).bold()
+        Style::default().fg(app.theme.focus).bold()
     } else {
         Style::default()
     };
     let category_line = Line::from(vec![
         Span::raw("Category: "),
-        Span::styled("< ", Style::default().dark_gray()),
-        Span::styled("■ ", Style::default().fg(selected_cat.color)),
+        Span::styled("< ", Style::default().fg(app.theme.muted)),
+        Span::styled(
+            "■ ",
+            Style::default().fg(app.theme.category(selected_cat.color)),
+        ),
         Span::styled(&selected_cat.name, cat_style),
-        Span::styled(" >", Style::default().dark_gray()),
+        Span::styled(" >", Style::default().fg(app.theme.muted)),
         Span::raw("  (←/→ to change)"),
     ]);
+    add_selector_targets(app, chunks[2], &category_line, InputField::Category);
     frame.render_widget(Paragraph::new(category_line).centered(), chunks[2]);
 
+    // Task selector
+    let open_tasks = app.data.open_tasks();
+    let task_name = match app.input.selected_task.and_then(|i| open_tasks.get(i)) {
+        Some(task) => format!("{} ({}/{})", task.title, task.pomodoros, task.estimate),
+        None => "(none)".to_string(),
+    };
+    let task_style = if app.input.field == InputField::Task {
+        Style::default().fg(app.theme.focus).bold()
+    } else {
+        Style::default()
+    };
+    let task_line = Line::from(vec![
+        Span::raw("Task: "),
+        Span::styled("< ", Style::default().fg(app.theme.muted)),
+        Span::styled(task_name, task_style),
+        Span::styled(" >", Style::default().fg(app.theme.muted)),
+    ]);
+    add_selector_targets(app, chunks[3], &task_line, InputField::Task);
+    frame.render_widget(Paragraph::new(task_line).centered(), chunks[3]);
+
     // Controls
     let controls = Line::from(vec![
         Span::styled("[Enter]", Style::default().bold()),
@@ -98,5 +132,30 @@ pub fn render_input_modal(frame: &mut Frame, area: Rect, app: &App) {
         Span::styled("[Esc]", Style::default().bold()),
         Span::raw(" Cancel"),
     ]);
-    frame.render_widget(Paragraph::new(controls).centered().dark_gray(), chunks[3]);
+    frame.render_widget(
+        Paragraph::new(controls).centered().fg(app.theme.muted),
+        chunks[4],
+    );
+}
+
+/// Make the arrows of a `Label: < value >` selector step it, and the value focus it
+fn add_selector_targets(app: &App, area: Rect, line: &Line, field: InputField) {
+    let spans = centered_span_areas(area, line);
+    let Some(right) = line.spans.iter().rposition(|span| span.content == " >") else {
+        return;
+    };
+    app.hits.add(
+        spans[1..right]
+            .iter()
+            .fold(spans[1], |acc, &span| acc.union(span)),
+        ClickTarget::InputField(field, None),
+    );
+    app.hits.add(
+        spans[1],
+        ClickTarget::InputField(field, Some(KeyCode::Left)),
+    );
+    app.hits.add(
+        spans[right],
+        ClickTarget::InputField(field, Some(KeyCode::Right)),
+    );
 }
@@ -0,0 +1,79 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::models::InterruptionKind;
+
+/// Render the interruption logging modal as an overlay
+pub fn render_interruption_modal(frame: &mut Frame, area: Rect, app: &App) {
+    // Calculate modal size and position (centered)
+    let modal_width = 50.min(area.width.saturating_sub(4));
+    let modal_height = 8.min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    // Clear the area behind the modal
+    frame.render_widget(Clear, modal_area);
+
+    // Modal block
+    let title = match app.interruption.kind {
+        InterruptionKind::Internal => " Internal Interruption ",
+        InterruptionKind::External => " External Interruption ",
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Timestamp
+        Constraint::Length(3), // Note field
+        Constraint::Length(2), // Controls
+    ])
+    .split(inner);
+
+    // Timestamp
+    let at = app
+        .interruption
+        .occurred_at
+        .to_datetime()
+        .format("%H:%M:%S");
+    frame.render_widget(
+        Paragraph::new(format!("Logged at {}", at))
+            .centered()
+            .fg(app.theme.muted),
+        chunks[0],
+    );
+
+    // Note field
+    let note_block = Block::default()
+        .title("Note (optional)")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.focus));
+    frame.render_widget(
+        Paragraph::new(format!("{}_", app.interruption.note)).block(note_block),
+        chunks[1],
+    );
+
+    // Controls
+    let controls = Line::from(vec![
+        Span::styled("[Enter]", Style::default().bold()),
+        Span::raw(" Log   "),
+        Span::styled("[Esc]", Style::default().bold()),
+        Span::raw(" Cancel"),
+    ]);
+    frame.render_widget(
+        Paragraph::new(controls).centered().fg(app.theme.muted),
+        chunks[2],
+    );
+}
@@ -1,32 +1,44 @@
+mod big_digits;
+mod compact;
 mod detail;
+mod help;
 mod history;
 mod input;
+mod interruption;
+mod palette;
 mod settings;
 mod stats;
+mod tasks;
 mod timer;
 
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     widgets::Paragraph,
 };
 
 use crate::app::{App, NotificationLevel};
 
+pub use compact::render_compact;
 pub use detail::render_detail_modal;
+pub use help::render_help_overlay;
 pub use history::render_history;
 pub use input::render_input_modal;
+pub use interruption::render_interruption_modal;
+pub use palette::render_palette_modal;
 pub use settings::render_settings_modal;
 pub use stats::render_stats;
+pub use tasks::{render_task_input_modal, render_tasks};
 pub use timer::render_timer;
 
 /// Render the footer area with either a notification or navigation text
 pub fn render_footer(frame: &mut Frame, area: Rect, app: &App, nav_text: &str) {
     if let Some(ref n) = app.notification {
         let color = match n.level {
-            NotificationLevel::Warning => Color::Yellow,
-            NotificationLevel::Error => Color::Red,
+            NotificationLevel::Info => app.theme.info,
+            NotificationLevel::Warning => app.theme.warning,
+            NotificationLevel::Error => app.theme.error,
         };
         frame.render_widget(
             Paragraph::new(n.message.as_str())
@@ -35,6 +47,9 @@ pub fn render_footer(frame: &mut Frame, area: Rect, app: &App, nav_text: &str) {
             area,
         );
     } else {
-        frame.render_widget(Paragraph::new(nav_text).centered().dark_gray(), area);
+        frame.render_widget(
+            Paragraph::new(nav_text).centered().fg(app.theme.muted),
+            area,
+        );
     }
 }
@@ -0,0 +1,110 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::mouse::ClickTarget;
+
+/// Render the command palette as an overlay
+pub fn render_palette_modal(frame: &mut Frame, area: Rect, app: &App) {
+    // Calculate modal size and position (centered, towards the top)
+    let modal_width = 60.min(area.width.saturating_sub(4));
+    let modal_height = 16.min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 3;
+
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    // Clear the area behind the modal
+    frame.render_widget(Clear, modal_area);
+
+    // Modal block
+    let block = Block::default()
+        .title(" Commands ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Query field
+        Constraint::Min(1),    // Matching commands
+    ])
+    .split(inner);
+
+    // Query field
+    let query_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.focus));
+    frame.render_widget(
+        Paragraph::new(format!("> {}_", app.palette.query)).block(query_block),
+        chunks[0],
+    );
+
+    let matches = app.palette.matches();
+    if matches.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No matching commands")
+                .centered()
+                .fg(app.theme.muted),
+            chunks[1],
+        );
+        return;
+    }
+
+    // Keep the selected command in view
+    let list_area = chunks[1];
+    let visible = list_area.height as usize;
+    let offset = (app.palette.selected + 1).saturating_sub(visible);
+    app.hits.add(list_area, ClickTarget::List);
+
+    let lines: Vec<Line> = matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(index, command)| {
+            let row = Rect::new(
+                list_area.x,
+                list_area.y + (index - offset) as u16,
+                list_area.width,
+                1,
+            );
+            app.hits.add(row, ClickTarget::PaletteCommand(index));
+
+            // The key that does the same, so the palette teaches the bindings
+            let key = app
+                .keymap
+                .keys(command.action)
+                .first()
+                .map(|key| key.to_string())
+                .unwrap_or_default();
+            let view = command
+                .view
+                .map(|view| format!("{:?}: ", view))
+                .unwrap_or_default();
+            let title_width = list_area.width as usize;
+            let title = format!(" {}{}", view, command.title);
+            let padding =
+                title_width.saturating_sub(title.chars().count() + key.chars().count() + 1);
+
+            let style = if index == app.palette.selected {
+                app.theme.highlight(app.theme.focus)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::raw(title),
+                Span::raw(" ".repeat(padding)),
+                Span::styled(format!("{} ", key), Style::default().fg(app.theme.muted)),
+            ])
+            .style(style)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), list_area);
+}
@@ -1,18 +1,23 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
-use crate::app::{App, CategoryField, SettingsField, SettingsMode};
+use crate::app::{
+    App, CategoryField, HookField, SettingsField, SettingsMode, StepField, WebhookField,
+};
+use crate::models::HookEvent;
+use crate::mouse::ClickTarget;
+use crate::timer::TimerPhase;
 
 /// Render the settings modal as an overlay
 pub fn render_settings_modal(frame: &mut Frame, area: Rect, app: &App) {
     // Calculate modal size and position (centered)
-    let modal_width = 50.min(area.width.saturating_sub(4));
-    let modal_height = 18.min(area.height.saturating_sub(4));
+    let modal_width = 68.min(area.width.saturating_sub(4));
+    let modal_height = 24.min(area.height.saturating_sub(4));
     let modal_x = (area.width.saturating_sub(modal_width)) / 2;
     let modal_y = (area.height.saturating_sub(modal_height)) / 2;
 
@@ -25,7 +30,7 @@ pub fn render_settings_modal(frame: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
         .title(" Settings ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(app.theme.border));
 
     let inner = block.inner(modal_area);
     frame.render_widget(block, modal_area);
@@ -43,16 +48,115 @@ pub fn render_settings_modal(frame: &mut Frame, area: Rect, app: &App) {
     // Content based on mode
     match app.settings.mode {
         SettingsMode::Timer => render_timer_settings(frame, chunks[1], app),
+        SettingsMode::Alerts => render_alert_settings(frame, chunks[1], app),
+        SettingsMode::Profiles => render_profile_settings(frame, chunks[1], app),
+        SettingsMode::Cycle => render_cycle_settings(frame, chunks[1], app),
+        SettingsMode::Hooks => render_hook_settings(frame, chunks[1], app),
+        SettingsMode::Webhooks => render_webhook_settings(frame, chunks[1], app),
         SettingsMode::Categories => render_category_settings(frame, chunks[1], app),
     }
 
     // Controls based on mode
     let controls = match app.settings.mode {
-        SettingsMode::Timer => Line::from(vec![
+        SettingsMode::Timer | SettingsMode::Alerts => Line::from(vec![
             Span::styled("[Enter]", Style::default().bold()),
             Span::raw(" Save  "),
             Span::styled("[Tab/↑↓]", Style::default().bold()),
             Span::raw(" Navigate  "),
+            Span::styled("[Space]", Style::default().bold()),
+            Span::raw(" Toggle  "),
+            Span::styled("[h/l]", Style::default().bold()),
+            Span::raw(" Mode  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Close"),
+        ]),
+        SettingsMode::Profiles if app.settings.new_profile_name.is_some() => Line::from(vec![
+            Span::styled("[Enter]", Style::default().bold()),
+            Span::raw(" Create  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Cancel"),
+        ]),
+        SettingsMode::Profiles => Line::from(vec![
+            Span::styled("[Enter]", Style::default().bold()),
+            Span::raw(" Use  "),
+            Span::styled("[n]", Style::default().bold()),
+            Span::raw(" New  "),
+            Span::styled("[d]", Style::default().bold()),
+            Span::raw(" Delete  "),
+            Span::styled("[j/k]", Style::default().bold()),
+            Span::raw(" Nav  "),
+            Span::styled("[h/l]", Style::default().bold()),
+            Span::raw(" Mode  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Close"),
+        ]),
+        SettingsMode::Cycle if app.settings.step_form.is_some() => Line::from(vec![
+            Span::styled("[Enter]", Style::default().bold()),
+            Span::raw(" Save  "),
+            Span::styled("[Tab]", Style::default().bold()),
+            Span::raw(" Next field  "),
+            Span::styled("[Space]", Style::default().bold()),
+            Span::raw(" Kind  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Cancel"),
+        ]),
+        SettingsMode::Cycle => Line::from(vec![
+            Span::styled("[n]", Style::default().bold()),
+            Span::raw(" New  "),
+            Span::styled("[e]", Style::default().bold()),
+            Span::raw(" Edit  "),
+            Span::styled("[d]", Style::default().bold()),
+            Span::raw(" Del  "),
+            Span::styled("[J/K]", Style::default().bold()),
+            Span::raw(" Move  "),
+            Span::styled("[Space]", Style::default().bold()),
+            Span::raw(" On/Off  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Close"),
+        ]),
+        SettingsMode::Hooks if app.settings.hook_form.is_some() => Line::from(vec![
+            Span::styled("[Enter]", Style::default().bold()),
+            Span::raw(" Save  "),
+            Span::styled("[Tab]", Style::default().bold()),
+            Span::raw(" Next field  "),
+            Span::styled("[Space]", Style::default().bold()),
+            Span::raw(" Event  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Cancel"),
+        ]),
+        SettingsMode::Webhooks if app.settings.webhook_form.is_some() => Line::from(vec![
+            Span::styled("[Enter]", Style::default().bold()),
+            Span::raw(" Save  "),
+            Span::styled("[Tab]", Style::default().bold()),
+            Span::raw(" Next field  "),
+            Span::styled("[←→/Space]", Style::default().bold()),
+            Span::raw(" Events  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Cancel"),
+        ]),
+        SettingsMode::Webhooks => Line::from(vec![
+            Span::styled("[n]", Style::default().bold()),
+            Span::raw(" New  "),
+            Span::styled("[e]", Style::default().bold()),
+            Span::raw(" Edit  "),
+            Span::styled("[d]", Style::default().bold()),
+            Span::raw(" Delete  "),
+            Span::styled("[r]", Style::default().bold()),
+            Span::raw(" Retry  "),
+            Span::styled("[j/k]", Style::default().bold()),
+            Span::raw(" Nav  "),
+            Span::styled("[Esc]", Style::default().bold()),
+            Span::raw(" Close"),
+        ]),
+        SettingsMode::Hooks => Line::from(vec![
+            Span::styled("[n]", Style::default().bold()),
+            Span::raw(" New  "),
+            Span::styled("[e]", Style::default().bold()),
+            Span::raw(" Edit  "),
+            Span::styled("[d]", Style::default().bold()),
+            Span::raw(" Delete  "),
+            Span::styled("[j/k]", Style::default().bold()),
+            Span::raw(" Nav  "),
             Span::styled("[h/l]", Style::default().bold()),
             Span::raw(" Mode  "),
             Span::styled("[Esc]", Style::default().bold()),
@@ -73,60 +177,70 @@ pub fn render_settings_modal(frame: &mut Frame, area: Rect, app: &App) {
             Span::raw(" Close"),
         ]),
     };
-    frame.render_widget(Paragraph::new(controls).centered().dark_gray(), chunks[2]);
+    frame.render_widget(
+        Paragraph::new(controls).centered().fg(app.theme.muted),
+        chunks[2],
+    );
 }
 
 /// Render the mode tab selector
 fn render_mode_tabs(frame: &mut Frame, area: Rect, app: &App) {
-    let timer_style = if app.settings.mode == SettingsMode::Timer {
-        Style::default().fg(Color::Cyan).bold()
-    } else {
-        Style::default().dark_gray()
-    };
-    let cat_style = if app.settings.mode == SettingsMode::Categories {
-        Style::default().fg(Color::Cyan).bold()
-    } else {
-        Style::default().dark_gray()
+    let tab_style = |mode: SettingsMode| {
+        if app.settings.mode == mode {
+            Style::default().fg(app.theme.info).bold()
+        } else {
+            Style::default().fg(app.theme.muted)
+        }
     };
 
     let tabs = Line::from(vec![
-        Span::styled("← Timer", timer_style),
-        Span::raw("   "),
-        Span::styled("Categories →", cat_style),
+        Span::styled("← Timer", tab_style(SettingsMode::Timer)),
+        Span::raw("  "),
+        Span::styled("Alerts", tab_style(SettingsMode::Alerts)),
+        Span::raw("  "),
+        Span::styled("Profiles", tab_style(SettingsMode::Profiles)),
+        Span::raw("  "),
+        Span::styled("Cycle", tab_style(SettingsMode::Cycle)),
+        Span::raw("  "),
+        Span::styled("Hooks", tab_style(SettingsMode::Hooks)),
+        Span::raw("  "),
+        Span::styled("Webhooks", tab_style(SettingsMode::Webhooks)),
+        Span::raw("  "),
+        Span::styled("Categories →", tab_style(SettingsMode::Categories)),
     ]);
     frame.render_widget(Paragraph::new(tabs).centered(), area);
 }
 
 /// Render timer settings content
 fn render_timer_settings(frame: &mut Frame, area: Rect, app: &App) {
+    let [header, area] = Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(area);
+    let profile_name = app.active_profile().map_or("(none)", |p| p.name.as_str());
+    frame.render_widget(
+        Paragraph::new(format!("Editing profile: {}", profile_name)).fg(app.theme.muted),
+        header,
+    );
+
     let chunks = Layout::vertical([
-        Constraint::Length(2), // Work duration
-        Constraint::Length(2), // Short break
-        Constraint::Length(2), // Long break
-        Constraint::Length(2), // Sessions until long break
+        Constraint::Length(1), // Work duration
+        Constraint::Length(1), // Short break
+        Constraint::Length(1), // Long break
+        Constraint::Length(1), // Sessions until long break
+        Constraint::Length(1), // Extend by
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Auto-start breaks
+        Constraint::Length(1), // Auto-start work
+        Constraint::Length(1), // Overtime
+        Constraint::Length(1), // Pause when unfocused
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Timer mode
+        Constraint::Length(1), // Flowtime break ratio
+        Constraint::Length(1), // Flowtime minimum break
+        Constraint::Length(1), // Flowtime maximum break
     ])
     .split(area);
 
-    // Helper to render a settings row
     let render_row = |field: SettingsField, label: &str, value: i64, unit: &str| {
-        let is_selected = app.settings.field == field;
-        let style = if is_selected {
-            Style::default().fg(Color::Yellow).bold()
-        } else {
-            Style::default()
-        };
-
-        let value_text = if is_selected {
-            format!("{}_", app.settings.editing_value)
-        } else {
-            format_duration_value(field, value)
-        };
-
-        Line::from(vec![
-            Span::styled(format!("{:<24}", label), style),
-            Span::styled(value_text, style),
-            Span::raw(format!(" {}", unit)),
-        ])
+        settings_row(app, field, label, value, unit)
     };
 
     frame.render_widget(
@@ -168,13 +282,623 @@ fn render_timer_settings(frame: &mut Frame, area: Rect, app: &App) {
         )),
         chunks[3],
     );
+
+    frame.render_widget(
+        Paragraph::new(render_row(
+            SettingsField::ExtendBy,
+            "Extend phase by:",
+            app.settings.editing_config.extend_secs,
+            "min",
+        )),
+        chunks[4],
+    );
+
+    let render_toggle =
+        |field: SettingsField, label: &str, value: bool| toggle_row(app, field, label, value);
+
+    frame.render_widget(
+        Paragraph::new(render_toggle(
+            SettingsField::AutoStartBreaks,
+            "Auto-start breaks:",
+            app.settings.editing_config.auto_start_breaks,
+        )),
+        chunks[6],
+    );
+
+    frame.render_widget(
+        Paragraph::new(render_toggle(
+            SettingsField::AutoStartWork,
+            "Auto-start work:",
+            app.settings.editing_config.auto_start_work,
+        )),
+        chunks[7],
+    );
+
+    frame.render_widget(
+        Paragraph::new(render_toggle(
+            SettingsField::Overtime,
+            "Overtime after work:",
+            app.settings.editing_config.overtime,
+        )),
+        chunks[8],
+    );
+
+    frame.render_widget(
+        Paragraph::new(render_row(
+            SettingsField::FocusPause,
+            "Pause when unfocused:",
+            app.settings.editing_config.focus_pause_secs,
+            "sec (0 = off)",
+        )),
+        chunks[9],
+    );
+
+    let mode_style = if app.settings.field == SettingsField::TimerMode {
+        Style::default().fg(app.theme.focus).bold()
+    } else {
+        Style::default()
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!("{:<24}", "Timer mode:"), mode_style),
+            Span::styled(
+                format!("< {} >", app.settings.editing_config.timer_mode.label()),
+                mode_style,
+            ),
+        ])),
+        chunks[11],
+    );
+
+    frame.render_widget(
+        Paragraph::new(render_row(
+            SettingsField::FlowtimeRatio,
+            "Flowtime break ratio:",
+            app.settings.editing_config.flowtime_break_ratio,
+            ": 1",
+        )),
+        chunks[12],
+    );
+
+    frame.render_widget(
+        Paragraph::new(render_row(
+            SettingsField::FlowtimeMinBreak,
+            "Flowtime min break:",
+            app.settings.editing_config.flowtime_min_break_secs,
+            "min",
+        )),
+        chunks[13],
+    );
+
+    frame.render_widget(
+        Paragraph::new(render_row(
+            SettingsField::FlowtimeMaxBreak,
+            "Flowtime max break:",
+            app.settings.editing_config.flowtime_max_break_secs,
+            "min",
+        )),
+        chunks[14],
+    );
+}
+
+/// Render a numeric settings row, showing the typed value while it is focused
+fn settings_row(
+    app: &App,
+    field: SettingsField,
+    label: &str,
+    value: i64,
+    unit: &str,
+) -> Line<'static> {
+    let is_selected = app.settings.field == field;
+    let style = if is_selected {
+        Style::default().fg(app.theme.focus).bold()
+    } else {
+        Style::default()
+    };
+
+    let value_text = if is_selected {
+        format!("{}_", app.settings.editing_value)
+    } else {
+        format_duration_value(field, value)
+    };
+
+    Line::from(vec![
+        Span::styled(format!("{:<24}", label), style),
+        Span::styled(value_text, style),
+        Span::raw(format!(" {}", unit)),
+    ])
+}
+
+/// Render an on/off settings row
+fn toggle_row(app: &App, field: SettingsField, label: &str, value: bool) -> Line<'static> {
+    let style = if app.settings.field == field {
+        Style::default().fg(app.theme.focus).bold()
+    } else {
+        Style::default()
+    };
+    let value_text = if value { "[x] on" } else { "[ ] off" };
+
+    Line::from(vec![
+        Span::styled(format!("{:<24}", label), style),
+        Span::styled(value_text, style),
+    ])
+}
+
+/// Render pre-end warnings, per-phase alert patterns, quiet hours, desktop signals and the theme
+fn render_alert_settings(frame: &mut Frame, area: Rect, app: &App) {
+    let config = &app.settings.editing_config;
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // First warning
+        Constraint::Length(1), // Second warning
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Pattern header
+        Constraint::Length(3), // Pattern per phase
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Quiet hours
+        Constraint::Length(1), // Quiet start
+        Constraint::Length(1), // Quiet end
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Desktop notification
+        Constraint::Length(1), // Window title
+        Constraint::Length(1), // Theme
+    ])
+    .split(area);
+
+    frame.render_widget(
+        Paragraph::new(settings_row(
+            app,
+            SettingsField::FirstWarning,
+            "Warn before end:",
+            config.first_warning_secs,
+            "sec (0 = off)",
+        )),
+        chunks[0],
+    );
+    frame.render_widget(
+        Paragraph::new(settings_row(
+            app,
+            SettingsField::SecondWarning,
+            "Warn again before end:",
+            config.second_warning_secs,
+            "sec (0 = off)",
+        )),
+        chunks[1],
+    );
+
+    frame.render_widget(
+        Paragraph::new(format!(
+            "{:<16}{:<8}{:<8}{}",
+            "At end of", "Bells", "Flash", "Gauge"
+        ))
+        .fg(app.theme.muted),
+        chunks[3],
+    );
+
+    // One row per phase with a cell per part of its pattern
+    let cell_style = |field: SettingsField| {
+        if app.settings.field == field {
+            Style::default().fg(app.theme.focus).bold()
+        } else {
+            Style::default()
+        }
+    };
+    let check = |on: bool| if on { "[x]" } else { "[ ]" };
+    let pattern_lines: Vec<Line> = [
+        ("Work", TimerPhase::Work),
+        ("Short break", TimerPhase::ShortBreak),
+        ("Long break", TimerPhase::LongBreak),
+    ]
+    .into_iter()
+    .map(|(label, phase)| {
+        let alert = config.alert_for(phase);
+        let bells_field = SettingsField::AlertBells(phase);
+        let bells = if app.settings.field == bells_field {
+            format!("{}_", app.settings.editing_value)
+        } else {
+            alert.bells.to_string()
+        };
+        Line::from(vec![
+            Span::raw(format!("{:<16}", label)),
+            Span::styled(format!("{:<8}", bells), cell_style(bells_field)),
+            Span::styled(
+                format!("{:<8}", check(alert.flash)),
+                cell_style(SettingsField::AlertFlash(phase)),
+            ),
+            Span::styled(
+                check(alert.gauge),
+                cell_style(SettingsField::AlertGauge(phase)),
+            ),
+        ])
+    })
+    .collect();
+    frame.render_widget(Paragraph::new(pattern_lines), chunks[4]);
+
+    frame.render_widget(
+        Paragraph::new(toggle_row(
+            app,
+            SettingsField::QuietHours,
+            "Quiet hours (no bells):",
+            config.quiet_hours.enabled,
+        )),
+        chunks[6],
+    );
+    frame.render_widget(
+        Paragraph::new(settings_row(
+            app,
+            SettingsField::QuietStart,
+            "Quiet from hour:",
+            config.quiet_hours.start_hour,
+            ":00",
+        )),
+        chunks[7],
+    );
+    frame.render_widget(
+        Paragraph::new(settings_row(
+            app,
+            SettingsField::QuietEnd,
+            "Quiet until hour:",
+            config.quiet_hours.end_hour,
+            ":00",
+        )),
+        chunks[8],
+    );
+
+    let choice_style = |field: SettingsField| {
+        if app.settings.field == field {
+            Style::default().fg(app.theme.focus).bold()
+        } else {
+            Style::default()
+        }
+    };
+    let notify_style = choice_style(SettingsField::DesktopNotification);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!("{:<24}", "Desktop notification:"), notify_style),
+            Span::styled(
+                format!("< {} >", config.desktop_notification.label()),
+                notify_style,
+            ),
+        ])),
+        chunks[10],
+    );
+    frame.render_widget(
+        Paragraph::new(toggle_row(
+            app,
+            SettingsField::WindowTitle,
+            "Time in window title:",
+            config.window_title,
+        )),
+        chunks[11],
+    );
+    let theme_style = choice_style(SettingsField::Theme);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!("{:<24}", "Theme:"), theme_style),
+            Span::styled(format!("< {} >", config.theme), theme_style),
+        ])),
+        chunks[12],
+    );
+}
+
+/// Render the profile list, or the name form when creating one
+fn render_profile_settings(frame: &mut Frame, area: Rect, app: &App) {
+    if let Some(name) = &app.settings.new_profile_name {
+        let lines = vec![
+            Line::from(vec![
+                Span::raw("Name: "),
+                Span::styled(format!("{}_", name), Style::default().fg(app.theme.focus)),
+            ]),
+            Line::from(""),
+            Line::from("Starts with the current timer durations.".fg(app.theme.muted)),
+        ];
+        frame.render_widget(Paragraph::new(lines), area);
+        return;
+    }
+
+    let lines: Vec<Line> = app
+        .data
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(i, profile)| {
+            let is_selected = i == app.settings.profile_list_index;
+            let prefix = if is_selected { "> " } else { "  " };
+            let style = if is_selected {
+                Style::default().fg(app.theme.focus).bold()
+            } else {
+                Style::default()
+            };
+            let active = if app.data.config.active_profile == profile.id && profile.id.is_some() {
+                " (active)"
+            } else {
+                ""
+            };
+
+            Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{:<16}", profile.name), style),
+                Span::raw(profile.summary()),
+                Span::styled(active, Style::default().fg(app.theme.success)),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Render the custom cycle steps, or the step form when creating or editing one
+fn render_cycle_settings(frame: &mut Frame, area: Rect, app: &App) {
+    if let Some(form) = &app.settings.step_form {
+        let field_style = |field: StepField| {
+            if form.field == field {
+                Style::default().fg(app.theme.focus).bold()
+            } else {
+                Style::default()
+            }
+        };
+        let cursor = |field: StepField| if form.field == field { "_" } else { "" };
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Label:    ", field_style(StepField::Label)),
+                Span::styled(
+                    format!("{}{}", form.label, cursor(StepField::Label)),
+                    field_style(StepField::Label),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Kind:     ", field_style(StepField::Kind)),
+                Span::styled(
+                    format!("< {} >", form.kind.label()),
+                    field_style(StepField::Kind),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Duration: ", field_style(StepField::Minutes)),
+                Span::styled(
+                    format!("{}{}", form.minutes, cursor(StepField::Minutes)),
+                    field_style(StepField::Minutes),
+                ),
+                Span::raw(" min"),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(lines), area);
+        return;
+    }
+
+    let enabled = if app.data.config.custom_cycle {
+        Span::styled("[x] on", Style::default().fg(app.theme.success))
+    } else {
+        Span::styled("[ ] off", Style::default().fg(app.theme.muted))
+    };
+    let mut lines = vec![
+        Line::from(vec![Span::raw("Use custom cycle: "), enabled]),
+        Line::from(""),
+    ];
+
+    if app.data.cycle_steps.is_empty() {
+        lines.push(Line::from(
+            "No steps yet - press [n] to add one.".fg(app.theme.muted),
+        ));
+    }
+    lines.extend(app.data.cycle_steps.iter().enumerate().map(|(i, step)| {
+        let is_selected = i == app.settings.cycle_list_index;
+        let prefix = if is_selected { "> " } else { "  " };
+        let style = if is_selected {
+            Style::default().fg(app.theme.focus).bold()
+        } else {
+            Style::default()
+        };
+        let kind_color = if step.kind.is_break() {
+            app.theme.short_break
+        } else {
+            app.theme.work
+        };
+
+        Line::from(vec![
+            Span::styled(format!("{}{}. ", prefix, i + 1), style),
+            Span::styled(format!("{:<20}", step.label), style),
+            Span::styled(
+                format!("{:<13}", step.kind.label().to_lowercase()),
+                Style::default().fg(kind_color),
+            ),
+            Span::raw(format!("{}m", step.duration_secs / 60)),
+        ])
+    }));
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Render the hook list, or the form when creating or editing one
+fn render_hook_settings(frame: &mut Frame, area: Rect, app: &App) {
+    if let Some(form) = &app.settings.hook_form {
+        let field_style = |field: HookField| {
+            if form.field == field {
+                Style::default().fg(app.theme.focus).bold()
+            } else {
+                Style::default()
+            }
+        };
+        let cursor = |field: HookField| if form.field == field { "_" } else { "" };
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("On:      ", field_style(HookField::Event)),
+                Span::styled(
+                    format!("< {} >", form.event.label()),
+                    field_style(HookField::Event),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("Command:", field_style(HookField::Command))),
+            Line::from(Span::styled(
+                format!("{}{}", form.command, cursor(HookField::Command)),
+                field_style(HookField::Command),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Timeout: ", field_style(HookField::Timeout)),
+                Span::styled(
+                    format!("{}{}", form.timeout, cursor(HookField::Timeout)),
+                    field_style(HookField::Timeout),
+                ),
+                Span::raw(" sec"),
+            ]),
+            Line::from(""),
+            Line::from(
+                "Session details arrive as GRINDSTONE_* variables and JSON on stdin."
+                    .fg(app.theme.muted),
+            ),
+        ];
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+        return;
+    }
+
+    if app.data.hooks.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No hooks yet - press [n] to run a command on a timer event.")
+                .fg(app.theme.muted),
+            area,
+        );
+        return;
+    }
+    let lines: Vec<Line> = app
+        .data
+        .hooks
+        .iter()
+        .enumerate()
+        .map(|(i, hook)| {
+            let is_selected = i == app.settings.hook_list_index;
+            let prefix = if is_selected { "> " } else { "  " };
+            let style = if is_selected {
+                Style::default().fg(app.theme.focus).bold()
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(format!("{}{:<16}", prefix, hook.event.label()), style),
+                Span::styled(hook.command.clone(), style),
+                Span::styled(
+                    format!("  ({}s)", hook.timeout_secs),
+                    Style::default().fg(app.theme.muted),
+                ),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Render the webhook list, or the form when creating or editing one
+fn render_webhook_settings(frame: &mut Frame, area: Rect, app: &App) {
+    if let Some(form) = &app.settings.webhook_form {
+        let field_style = |field: WebhookField| {
+            if form.field == field {
+                Style::default().fg(app.theme.focus).bold()
+            } else {
+                Style::default()
+            }
+        };
+        let cursor = |field: WebhookField| if form.field == field { "_" } else { "" };
+        let events: Vec<Span> = HookEvent::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, event)| {
+                let mark = if form.events.contains(event) {
+                    "x"
+                } else {
+                    " "
+                };
+                let style = if form.field == WebhookField::Events && i == form.event_index {
+                    Style::default().fg(app.theme.focus).bold()
+                } else {
+                    Style::default()
+                };
+                Span::styled(format!("[{}] {}  ", mark, event.label()), style)
+            })
+            .collect();
+        let lines = vec![
+            Line::from(Span::styled("URL:", field_style(WebhookField::Url))),
+            Line::from(Span::styled(
+                format!("{}{}", form.url, cursor(WebhookField::Url)),
+                field_style(WebhookField::Url),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Headers (Name: value; ...):",
+                field_style(WebhookField::Headers),
+            )),
+            Line::from(Span::styled(
+                format!("{}{}", form.headers, cursor(WebhookField::Headers)),
+                field_style(WebhookField::Headers),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Events (none ticked posts all):",
+                field_style(WebhookField::Events),
+            )),
+            Line::from(events),
+            Line::from(""),
+            Line::from(
+                "Events are POSTed as JSON and retried while the receiver is down."
+                    .fg(app.theme.muted),
+            ),
+        ];
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+        return;
+    }
+
+    if app.data.webhooks.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No webhooks yet - press [n] to post timer events to a URL.")
+                .fg(app.theme.muted),
+            area,
+        );
+        return;
+    }
+    let lines: Vec<Line> = app
+        .data
+        .webhooks
+        .iter()
+        .enumerate()
+        .map(|(i, webhook)| {
+            let is_selected = i == app.settings.webhook_list_index;
+            let prefix = if is_selected { "> " } else { "  " };
+            let style = if is_selected {
+                Style::default().fg(app.theme.focus).bold()
+            } else {
+                Style::default()
+            };
+            let events = if webhook.events.is_empty() {
+                "all events".to_string()
+            } else {
+                format!("{} events", webhook.events.len())
+            };
+            let failed = webhook
+                .id
+                .and_then(|id| app.data.failed_deliveries.get(&id))
+                .map(|count| format!("  {} failed", count))
+                .unwrap_or_default();
+            Line::from(vec![
+                Span::styled(format!("{}{}", prefix, webhook.url), style),
+                Span::styled(
+                    format!("  ({})", events),
+                    Style::default().fg(app.theme.muted),
+                ),
+                Span::styled(failed, Style::default().fg(app.theme.error)),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
 }
 
 /// Render category settings content
 fn render_category_settings(frame: &mut Frame, area: Rect, app: &App) {
     match app.settings.category_field {
         CategoryField::List => render_category_list(frame, area, app),
-        CategoryField::Name | CategoryField::Color => render_category_form(frame, area, app),
+        CategoryField::Name
+        | CategoryField::Color
+        | CategoryField::WorkDuration
+        | CategoryField::ShortBreak
+        | CategoryField::LongBreak => render_category_form(frame, area, app),
     }
 }
 
@@ -189,15 +913,22 @@ fn render_category_list(frame: &mut Frame, area: Rect, app: &App) {
             let is_selected = i == app.settings.category_list_index;
             let prefix = if is_selected { "> " } else { "  " };
             let style = if is_selected {
-                Style::default().fg(Color::Yellow).bold()
+                Style::default().fg(app.theme.focus).bold()
             } else {
                 Style::default()
             };
 
+            let durations = if cat.durations.is_empty() {
+                String::new()
+            } else {
+                format!("  {}", cat.durations.summary())
+            };
+
             Line::from(vec![
                 Span::styled(prefix, style),
-                Span::styled("■ ", Style::default().fg(cat.color)),
+                Span::styled("■ ", Style::default().fg(app.theme.category(cat.color))),
                 Span::styled(&cat.name, style),
+                Span::styled(durations, Style::default().fg(app.theme.muted)),
             ])
         })
         .collect();
@@ -206,10 +937,15 @@ fn render_category_list(frame: &mut Frame, area: Rect, app: &App) {
         frame.render_widget(
             Paragraph::new("No categories. Press [n] to create one.")
                 .centered()
-                .dark_gray(),
+                .fg(app.theme.muted),
             area,
         );
     } else {
+        app.hits.add(area, ClickTarget::List);
+        for i in 0..lines.len().min(area.height as usize) {
+            let row = Rect::new(area.x, area.y + i as u16, area.width, 1);
+            app.hits.add(row, ClickTarget::SettingsCategory(i));
+        }
         frame.render_widget(Paragraph::new(lines), area);
     }
 }
@@ -220,7 +956,10 @@ fn render_category_form(frame: &mut Frame, area: Rect, app: &App) {
         Constraint::Length(1), // Title
         Constraint::Length(2), // Name field
         Constraint::Length(2), // Color field
-        Constraint::Length(1), // Preview
+        Constraint::Length(2), // Preview
+        Constraint::Length(1), // Work duration override
+        Constraint::Length(1), // Short break override
+        Constraint::Length(1), // Long break override
         Constraint::Min(1),    // Spacer
         Constraint::Length(1), // Controls
     ])
@@ -236,7 +975,7 @@ fn render_category_form(frame: &mut Frame, area: Rect, app: &App) {
 
     // Name field
     let name_style = if app.settings.category_field == CategoryField::Name {
-        Style::default().fg(Color::Yellow).bold()
+        Style::default().fg(app.theme.focus).bold()
     } else {
         Style::default()
     };
@@ -255,7 +994,7 @@ fn render_category_form(frame: &mut Frame, area: Rect, app: &App) {
 
     // Color field
     let color_style = if app.settings.category_field == CategoryField::Color {
-        Style::default().fg(Color::Yellow).bold()
+        Style::default().fg(app.theme.focus).bold()
     } else {
         Style::default()
     };
@@ -282,6 +1021,49 @@ fn render_category_form(frame: &mut Frame, area: Rect, app: &App) {
         chunks[3],
     );
 
+    // Duration overrides, blank to follow the timer settings
+    let render_minutes = |field: CategoryField, label: &str, value: &str| {
+        let is_selected = app.settings.category_field == field;
+        let style = if is_selected {
+            Style::default().fg(app.theme.focus).bold()
+        } else {
+            Style::default()
+        };
+        let value_text = match (is_selected, value.is_empty()) {
+            (true, _) => format!("{}_", value),
+            (false, true) => "default".to_string(),
+            (false, false) => format!("{} min", value),
+        };
+        Line::from(vec![
+            Span::styled(format!("{:<13}", label), style),
+            Span::styled(value_text, style),
+        ])
+    };
+    frame.render_widget(
+        Paragraph::new(render_minutes(
+            CategoryField::WorkDuration,
+            "Work:",
+            &app.settings.new_category_work,
+        )),
+        chunks[4],
+    );
+    frame.render_widget(
+        Paragraph::new(render_minutes(
+            CategoryField::ShortBreak,
+            "Short break:",
+            &app.settings.new_category_short_break,
+        )),
+        chunks[5],
+    );
+    frame.render_widget(
+        Paragraph::new(render_minutes(
+            CategoryField::LongBreak,
+            "Long break:",
+            &app.settings.new_category_long_break,
+        )),
+        chunks[6],
+    );
+
     // Controls
     let action = if app.settings.editing_category_id.is_some() {
         "Save"
@@ -294,17 +1076,37 @@ fn render_category_form(frame: &mut Frame, area: Rect, app: &App) {
             action
         ))
         .centered()
-        .dark_gray(),
-        chunks[5],
+        .fg(app.theme.muted),
+        chunks[8],
     );
 }
 
 /// Format a config value for display (convert seconds to minutes for durations)
 fn format_duration_value(field: SettingsField, value: i64) -> String {
     match field {
-        SettingsField::WorkDuration | SettingsField::ShortBreak | SettingsField::LongBreak => {
-            format!("{}", value / 60)
-        }
-        SettingsField::SessionsUntilLong => format!("{}", value),
+        SettingsField::WorkDuration
+        | SettingsField::ShortBreak
+        | SettingsField::LongBreak
+        | SettingsField::ExtendBy
+        | SettingsField::FlowtimeMinBreak
+        | SettingsField::FlowtimeMaxBreak => format!("{}", value / 60),
+        SettingsField::SessionsUntilLong
+        | SettingsField::AutoStartBreaks
+        | SettingsField::AutoStartWork
+        | SettingsField::Overtime
+        | SettingsField::FocusPause
+        | SettingsField::TimerMode
+        | SettingsField::FlowtimeRatio
+        | SettingsField::FirstWarning
+        | SettingsField::SecondWarning
+        | SettingsField::AlertBells(_)
+        | SettingsField::AlertFlash(_)
+        | SettingsField::AlertGauge(_)
+        | SettingsField::QuietHours
+        | SettingsField::QuietStart
+        | SettingsField::QuietEnd
+        | SettingsField::DesktopNotification
+        | SettingsField::WindowTitle
+        | SettingsField::Theme => format!("{}", value),
     }
 }
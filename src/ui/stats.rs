@@ -12,59 +12,69 @@ use ratatui::{
     },
 };
 
-use crate::app::{App, ChartType, StatsPeriod};
-use crate::models::{Category, CategoryStat};
+use crate::app::{App, ChartType, StatsPeriod, View};
+use crate::keymap::Action;
+use crate::models::{
+    Category, CategoryStat, DailyInterruptions, DurationSecs, EstimateStat, ProfileStat, Task,
+};
+use crate::mouse::{ClickTarget, centered_span_areas};
+use crate::theme::Theme;
 use crate::ui;
 
+const PERIODS: [(StatsPeriod, &str); 4] = [
+    (StatsPeriod::Day, "Day"),
+    (StatsPeriod::Week, "Week"),
+    (StatsPeriod::Month, "Month"),
+    (StatsPeriod::Year, "Year"),
+];
+
+const CHARTS: [(ChartType, &str); 5] = [
+    (ChartType::Bar, "Bar"),
+    (ChartType::Pie, "Pie"),
+    (ChartType::Interruptions, "Interruptions"),
+    (ChartType::Estimates, "Estimates"),
+    (ChartType::Profiles, "Profiles"),
+];
+
 /// Render the statistics view
 pub fn render_stats(frame: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::vertical([
         Constraint::Length(3), // Title
         Constraint::Length(3), // Period selector
         Constraint::Min(1),    // Chart area
-        Constraint::Length(3), // Summary
+        Constraint::Length(3), // Summary (two lines)
         Constraint::Length(2), // Controls
         Constraint::Length(1), // Footer
     ])
     .split(area);
 
     // Title
-    let title = Line::from("Time Statistics").bold().blue().centered();
+    let title = Line::from("Time Statistics")
+        .bold()
+        .fg(app.theme.title)
+        .centered();
     frame.render_widget(
         Paragraph::new(title).block(Block::default().borders(Borders::BOTTOM)),
         chunks[0],
     );
 
-    // Period selector
-    let periods = ["Day", "Week", "Month", "Year"];
-    let selected_idx = match app.data.stats_period {
-        StatsPeriod::Day => 0,
-        StatsPeriod::Week => 1,
-        StatsPeriod::Month => 2,
-        StatsPeriod::Year => 3,
-    };
-
-    let period_spans: Vec<Span> = periods
-        .iter()
-        .enumerate()
-        .flat_map(|(i, p)| {
-            let style = if i == selected_idx {
-                Style::default().bold().fg(Color::Cyan)
-            } else {
-                Style::default().dark_gray()
-            };
-            vec![
-                Span::raw(if i == selected_idx { "[ " } else { "  " }),
-                Span::styled(*p, style),
-                Span::raw(if i == selected_idx { " ]" } else { "  " }),
-            ]
-        })
-        .collect();
+    // Period selector, with the chart types on the line below
+    let period_row = Rect::new(chunks[1].x, chunks[1].y, chunks[1].width, 1);
+    let chart_row =
+        Rect::new(chunks[1].x, chunks[1].y + 1, chunks[1].width, 1).intersection(chunks[1]);
+    let period_labels = PERIODS.map(|(period, label)| (label, period == app.data.stats_period));
+    let period_line = selector_line(&period_labels, &app.theme);
+    for (span_area, (period, _)) in label_areas(period_row, &period_line).zip(PERIODS) {
+        app.hits.add(span_area, ClickTarget::StatsPeriod(period));
+    }
+    frame.render_widget(Paragraph::new(period_line).centered(), period_row);
 
-    frame.render_widget(
-        Paragraph::new(Line::from(period_spans)).centered(),
-        chunks[1],
-    );
+    let chart_labels = CHARTS.map(|(chart, label)| (label, chart == app.data.chart_type));
+    let chart_line = selector_line(&chart_labels, &app.theme);
+    for (span_area, (chart, _)) in label_areas(chart_row, &chart_line).zip(CHARTS) {
+        app.hits.add(span_area, ClickTarget::ChartType(chart));
+    }
+    frame.render_widget(Paragraph::new(chart_line).centered(), chart_row);
 
     // Chart area - horizontal bar chart showing time by category
     let chart_chunks = Layout::horizontal([
@@ -79,20 +89,53 @@ pub fn render_stats(frame: &mut Frame, area: Rect, app: &App) {
             chart_chunks[0],
             &app.data.category_stats,
             &app.data.categories,
+            &app.theme,
         ),
         ChartType::Pie => render_pie_chart(
             frame,
             chart_chunks[0],
             &app.data.category_stats,
             &app.data.categories,
+            &app.theme,
         ),
+        ChartType::Interruptions => render_interruptions_chart(
+            frame,
+            chart_chunks[0],
+            &app.data.daily_interruptions,
+            &app.theme,
+        ),
+        ChartType::Estimates => render_estimates_table(
+            frame,
+            chart_chunks[0],
+            &app.data.estimate_stats,
+            &app.data.categories,
+            &app.theme,
+        ),
+        ChartType::Profiles => {
+            render_profile_chart(frame, chart_chunks[0], &app.data.profile_stats, &app.theme)
+        }
+    }
+    match app.data.chart_type {
+        ChartType::Bar | ChartType::Pie => render_legend(
+            frame,
+            chart_chunks[1],
+            &app.data.category_stats,
+            &app.data.categories,
+            &app.theme,
+        ),
+        ChartType::Interruptions => render_interruptions_legend(
+            frame,
+            chart_chunks[1],
+            &app.data.daily_interruptions,
+            &app.theme,
+        ),
+        ChartType::Estimates => {
+            render_worst_offenders(frame, chart_chunks[1], &app.data.finished_tasks, &app.theme)
+        }
+        ChartType::Profiles => {
+            render_profile_legend(frame, chart_chunks[1], &app.data.profile_stats, &app.theme)
+        }
     }
-    render_legend(
-        frame,
-        chart_chunks[1],
-        &app.data.category_stats,
-        &app.data.categories,
-    );
 
     // Summary stats
     let total_secs: i64 = app
@@ -104,11 +147,31 @@ pub fn render_stats(frame: &mut Frame, area: Rect, app: &App) {
     let total_hours = total_secs / 3600;
     let total_mins = (total_secs % 3600) / 60;
     let session_count = app.data.sessions.len();
+    let pauses = &app.data.pause_stats;
+    let breaks = &app.data.break_stats;
 
-    let summary = format!(
-        "Total: {}h {}m  |  Sessions: {}",
-        total_hours, total_mins, session_count
-    );
+    let work_break_ratio = if breaks.total_seconds > 0 {
+        format!("{:.1}:1", total_secs as f64 / breaks.total_seconds as f64)
+    } else {
+        "-".to_string()
+    };
+    let summary = vec![
+        Line::from(format!(
+            "Total: {}h {}m  |  Sessions: {}  |  Paused: {} ({} pauses)",
+            total_hours,
+            total_mins,
+            session_count,
+            DurationSecs::new(pauses.paused_secs).format(),
+            pauses.pause_count
+        )),
+        Line::from(format!(
+            "Breaks: {} ({})  |  Work:Break {}  |  Skipped breaks: {}",
+            breaks.count,
+            DurationSecs::new(breaks.total_seconds).format(),
+            work_break_ratio,
+            breaks.skipped
+        )),
+    ];
     frame.render_widget(
         Paragraph::new(summary)
             .centered()
@@ -117,26 +180,49 @@ pub fn render_stats(frame: &mut Frame, area: Rect, app: &App) {
     );
 
     // Controls
-    let controls = "[</> or h/l] Change Period  [v] Toggle Chart";
+    let controls = app.keymap.hints(&[
+        (&[Action::PrevPeriod, Action::NextPeriod], "Change Period"),
+        (&[Action::NextChart], "Next Chart"),
+    ]);
     frame.render_widget(
         Paragraph::new(controls)
             .centered()
-            .dark_gray()
+            .fg(app.theme.muted)
             .block(Block::default().borders(Borders::TOP)),
         chunks[4],
     );
 
     // Footer / notification
-    ui::render_footer(frame, chunks[5], app, "[Tab] Timer  [h] History  [q] Quit");
+    ui::render_footer(frame, chunks[5], app, &app.keymap.nav_hints(View::Stats));
 }
 
-/// Look up a category's color by name, with gray fallback
-fn get_category_color(categories: &[Category], name: &str) -> Color {
-    categories
+/// A row of labels with the selected one bracketed
+fn selector_line(labels: &[(&'static str, bool)], theme: &Theme) -> Line<'static> {
+    let spans: Vec<Span> = labels
         .iter()
-        .find(|c| c.name == name)
-        .map(|c| c.color)
-        .unwrap_or(Color::Gray)
+        .flat_map(|&(label, selected)| {
+            let style = if selected {
+                Style::default().bold().fg(theme.info)
+            } else {
+                Style::default().fg(theme.muted)
+            };
+            vec![
+                Span::raw(if selected { "[ " } else { "  " }),
+                Span::styled(label, style),
+                Span::raw(if selected { " ]" } else { "  " }),
+            ]
+        })
+        .collect();
+    Line::from(spans)
+}
+
+/// The area of each label of a centered `selector_line`, brackets and padding included
+fn label_areas(area: Rect, line: &Line) -> impl Iterator<Item = Rect> {
+    centered_span_areas(area, line)
+        .chunks(3)
+        .map(|spans| spans[0].union(spans[2]))
+        .collect::<Vec<_>>()
+        .into_iter()
 }
 
 fn render_bar_chart(
@@ -144,12 +230,13 @@ fn render_bar_chart(
     area: Rect,
     stats: &[CategoryStat],
     categories: &[Category],
+    theme: &Theme,
 ) {
     if stats.is_empty() {
         frame.render_widget(
             Paragraph::new("No data for this period")
                 .centered()
-                .dark_gray(),
+                .fg(theme.muted),
             area,
         );
         return;
@@ -167,7 +254,7 @@ fn render_bar_chart(
             } else {
                 format!("{}m", mins)
             };
-            let color = get_category_color(categories, &stat.name);
+            let color = theme.category_named(categories, &stat.name);
             Bar::default()
                 .value(stat.total_seconds as u64)
                 .label(Line::from(stat.name.clone()))
@@ -195,12 +282,13 @@ fn render_pie_chart(
     area: Rect,
     stats: &[CategoryStat],
     categories: &[Category],
+    theme: &Theme,
 ) {
     if stats.is_empty() {
         frame.render_widget(
             Paragraph::new("No data for this period")
                 .centered()
-                .dark_gray(),
+                .fg(theme.muted),
             area,
         );
         return;
@@ -218,7 +306,7 @@ fn render_pie_chart(
     for stat in stats {
         let proportion = stat.total_seconds as f64 / total_secs as f64;
         let sweep = proportion * 2.0 * PI;
-        let color = get_category_color(categories, &stat.name);
+        let color = theme.category_named(categories, &stat.name);
         slices.push((current_angle, current_angle + sweep, color));
         current_angle += sweep;
     }
@@ -263,7 +351,13 @@ fn render_pie_chart(
     frame.render_widget(canvas, area);
 }
 
-fn render_legend(frame: &mut Frame, area: Rect, stats: &[CategoryStat], categories: &[Category]) {
+fn render_legend(
+    frame: &mut Frame,
+    area: Rect,
+    stats: &[CategoryStat],
+    categories: &[Category],
+    theme: &Theme,
+) {
     let total_secs: i64 = stats.iter().map(|s| s.total_seconds).sum();
     if total_secs == 0 {
         return;
@@ -280,17 +374,259 @@ fn render_legend(frame: &mut Frame, area: Rect, stats: &[CategoryStat], categori
             } else {
                 format!("{}m", mins)
             };
-            let color = get_category_color(categories, &stat.name);
+            let color = theme.category_named(categories, &stat.name);
             Line::from(vec![
                 Span::styled("■ ", Style::default().fg(color)),
                 Span::styled(format!("{:<12}", stat.name), Style::default().fg(color)),
                 Span::raw(format!("{:>8}  ({:.0}%)", time_str, pct)),
+                Span::styled(
+                    format!("  ✓{:.0}%", stat.completion_rate() * 100.0),
+                    Style::default().fg(theme.success),
+                ),
             ])
         })
         .collect();
 
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Breakdown (✓ completion rate)"),
+        ),
+        area,
+    );
+}
+
+fn render_interruptions_chart(
+    frame: &mut Frame,
+    area: Rect,
+    days: &[DailyInterruptions],
+    theme: &Theme,
+) {
+    if days.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No interruptions this period")
+                .centered()
+                .fg(theme.muted),
+            area,
+        );
+        return;
+    }
+
+    let groups: Vec<BarGroup> = days
+        .iter()
+        .map(|day| {
+            // Label with MM-DD to keep groups narrow
+            let label = day.day.get(5..).unwrap_or(&day.day).to_string();
+            BarGroup::default().label(Line::from(label)).bars(&[
+                Bar::default()
+                    .value(day.internal as u64)
+                    .style(Style::default().fg(theme.warning)),
+                Bar::default()
+                    .value(day.external as u64)
+                    .style(Style::default().fg(theme.special)),
+            ])
+        })
+        .collect();
+
+    let mut chart = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Interruptions per Day"),
+        )
+        .bar_width(2)
+        .bar_gap(0)
+        .group_gap(2);
+    for group in groups {
+        chart = chart.data(group);
+    }
+
+    frame.render_widget(chart, area);
+}
+
+fn render_interruptions_legend(
+    frame: &mut Frame,
+    area: Rect,
+    days: &[DailyInterruptions],
+    theme: &Theme,
+) {
+    let internal: i64 = days.iter().map(|d| d.internal).sum();
+    let external: i64 = days.iter().map(|d| d.external).sum();
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("■ ", Style::default().fg(theme.warning)),
+            Span::styled(
+                format!("{:<12}", "internal"),
+                Style::default().fg(theme.warning),
+            ),
+            Span::raw(format!("{:>8}", internal)),
+        ]),
+        Line::from(vec![
+            Span::styled("■ ", Style::default().fg(theme.special)),
+            Span::styled(
+                format!("{:<12}", "external"),
+                Style::default().fg(theme.special),
+            ),
+            Span::raw(format!("{:>8}", external)),
+        ]),
+    ];
+
     frame.render_widget(
         Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Breakdown")),
         area,
     );
 }
+
+/// Color for a mean estimation error: red when over budget, green otherwise
+fn error_color(error: f64, theme: &Theme) -> Color {
+    if error > 0.0 {
+        theme.error
+    } else {
+        theme.success
+    }
+}
+
+fn render_estimates_table(
+    frame: &mut Frame,
+    area: Rect,
+    stats: &[EstimateStat],
+    categories: &[Category],
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Estimated vs Actual Pomodoros");
+    if stats.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No tasks finished this period")
+                .centered()
+                .fg(theme.muted)
+                .block(block),
+            area,
+        );
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(format!(
+            "  {:<12}{:>6}{:>6}{:>8}  Mean error",
+            "Category", "Tasks", "Est", "Actual"
+        ))
+        .bold(),
+    ];
+    let overall = EstimateStat::combined("All", stats);
+    for stat in stats.iter().chain(std::iter::once(&overall)) {
+        let color = theme.category_named(categories, &stat.name);
+        lines.push(Line::from(vec![
+            Span::styled("■ ", Style::default().fg(color)),
+            Span::raw(format!(
+                "{:<12}{:>6}{:>6}{:>8}  ",
+                stat.name, stat.task_count, stat.estimated, stat.actual
+            )),
+            Span::styled(
+                format!("{:+.1} ({})", stat.mean_error(), stat.verdict()),
+                Style::default().fg(error_color(stat.mean_error(), theme)),
+            ),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_worst_offenders(frame: &mut Frame, area: Rect, tasks: &[Task], theme: &Theme) {
+    let lines: Vec<Line> = tasks
+        .iter()
+        .filter(|t| t.estimate_error() != 0)
+        .map(|task| {
+            let error = task.estimate_error();
+            Line::from(vec![
+                Span::raw(format!("{:<16} ", task.title)),
+                Span::raw(format!("{}/{} ", task.pomodoros, task.estimate)),
+                Span::styled(
+                    format!("{:+}", error),
+                    Style::default().fg(error_color(error as f64, theme)),
+                ),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Worst Offenders (actual/est)"),
+        ),
+        area,
+    );
+}
+
+fn render_profile_chart(frame: &mut Frame, area: Rect, stats: &[ProfileStat], theme: &Theme) {
+    if stats.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No data for this period")
+                .centered()
+                .fg(theme.muted),
+            area,
+        );
+        return;
+    }
+
+    let max_secs = stats.iter().map(|s| s.total_seconds).max().unwrap_or(1);
+    let bars: Vec<Bar> = stats
+        .iter()
+        .map(|stat| {
+            Bar::default()
+                .value(stat.total_seconds as u64)
+                .label(Line::from(stat.name.clone()))
+                .text_value(DurationSecs::new(stat.total_seconds).format())
+                .style(Style::default().fg(theme.info))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Time by Profile"),
+        )
+        .bar_width(5)
+        .bar_gap(2)
+        .data(BarGroup::default().bars(&bars))
+        .max(max_secs as u64);
+
+    frame.render_widget(chart, area);
+}
+
+fn render_profile_legend(frame: &mut Frame, area: Rect, stats: &[ProfileStat], theme: &Theme) {
+    let lines: Vec<Line> = stats
+        .iter()
+        .map(|stat| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<12}", stat.name),
+                    Style::default().fg(theme.info),
+                ),
+                Span::raw(format!(
+                    "{:>3} x {:>6}  ",
+                    stat.session_count,
+                    DurationSecs::new(stat.mean_secs()).format()
+                )),
+                Span::styled(
+                    format!("✓{:.0}%", stat.completion_rate() * 100.0),
+                    Style::default().fg(theme.success),
+                ),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Sessions x mean length"),
+        ),
+        area,
+    );
+}
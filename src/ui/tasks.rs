@@ -0,0 +1,199 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+use crate::app::{App, TaskField, View};
+use crate::keymap::Action;
+use crate::models::{Category, Task, TaskStatus};
+use crate::theme::Theme;
+use crate::ui;
+
+/// Render the task queue view
+pub fn render_tasks(frame: &mut Frame, area: Rect, app: &mut App) {
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Title
+        Constraint::Min(1),    // Task list
+        Constraint::Length(1), // Controls
+        Constraint::Length(1), // Footer
+    ])
+    .split(area);
+
+    // Title
+    let title = Line::from("Task Queue")
+        .bold()
+        .fg(app.theme.title)
+        .centered();
+    frame.render_widget(
+        Paragraph::new(title).block(Block::default().borders(Borders::BOTTOM)),
+        chunks[0],
+    );
+
+    // Open tasks first, then recently finished ones
+    let items: Vec<ListItem> = if app.data.tasks.is_empty() {
+        vec![ListItem::new(Line::from(
+            "No tasks - press [n] to plan one".fg(app.theme.muted),
+        ))]
+    } else {
+        app.data
+            .tasks
+            .iter()
+            .map(|t| build_task_item(t, &app.data.categories, &app.theme))
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::NONE))
+        .highlight_style(app.theme.selected())
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, chunks[1], &mut app.data.task_state);
+
+    // Controls
+    frame.render_widget(
+        Paragraph::new(app.keymap.hints(&[
+            (&[Action::SelectNext, Action::SelectPrev], "Navigate"),
+            (&[Action::MoveDown, Action::MoveUp], "Reorder"),
+            (&[Action::New], "New"),
+            (&[Action::Confirm], "Start"),
+            (&[Action::CompleteTask], "Done"),
+            (&[Action::Delete], "Delete"),
+        ]))
+        .centered()
+        .fg(app.theme.muted)
+        .block(Block::default().borders(Borders::TOP)),
+        chunks[2],
+    );
+
+    // Footer / notification
+    ui::render_footer(frame, chunks[3], app, &app.keymap.nav_hints(View::Tasks));
+}
+
+/// Build a list row showing a task's progress against its estimate
+fn build_task_item(task: &Task, categories: &[Category], theme: &Theme) -> ListItem<'static> {
+    let cat_color = theme.category_named(categories, &task.category);
+
+    let (mark, title_style) = match task.status {
+        TaskStatus::Open => ("○ ", Style::default()),
+        TaskStatus::Done => ("✓ ", Style::default().fg(theme.muted).crossed_out()),
+    };
+
+    let progress_style = if task.pomodoros > task.estimate {
+        Style::default().fg(theme.error)
+    } else {
+        Style::default().fg(theme.warning)
+    };
+    let progress = match task.status {
+        TaskStatus::Open => format!("  {}/{} pomodoros", task.pomodoros, task.estimate),
+        TaskStatus::Done => format!("  {} actual / {} estimated", task.pomodoros, task.estimate),
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::raw(mark),
+        Span::styled(task.title.clone(), title_style),
+        Span::styled(
+            format!("  ■ {}", task.category),
+            Style::default().fg(cat_color),
+        ),
+        Span::styled(progress, progress_style),
+    ]))
+}
+
+/// Render the new task modal as an overlay
+pub fn render_task_input_modal(frame: &mut Frame, area: Rect, app: &App) {
+    // Calculate modal size and position (centered)
+    let modal_width = 50.min(area.width.saturating_sub(4));
+    let modal_height = 12.min(area.height.saturating_sub(4));
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    // Clear the area behind the modal
+    frame.render_widget(Clear, modal_area);
+
+    // Modal block
+    let block = Block::default()
+        .title(" New Task ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Title field
+        Constraint::Length(3), // Estimate field
+        Constraint::Length(2), // Category selector
+        Constraint::Length(2), // Controls
+    ])
+    .split(inner);
+
+    let input = &app.task_input;
+    let field_style = |field: TaskField| {
+        if input.field == field {
+            Style::default().fg(app.theme.focus)
+        } else {
+            Style::default()
+        }
+    };
+    let field_text = |field: TaskField, value: &str| {
+        if input.field == field {
+            format!("{}_", value)
+        } else {
+            value.to_string()
+        }
+    };
+
+    // Title field
+    let title_block = Block::default()
+        .title("Title")
+        .borders(Borders::ALL)
+        .border_style(field_style(TaskField::Title));
+    frame.render_widget(
+        Paragraph::new(field_text(TaskField::Title, input.title.as_ref())).block(title_block),
+        chunks[0],
+    );
+
+    // Estimate field
+    let estimate_block = Block::default()
+        .title("Estimated pomodoros")
+        .borders(Borders::ALL)
+        .border_style(field_style(TaskField::Estimate));
+    frame.render_widget(
+        Paragraph::new(field_text(TaskField::Estimate, &input.estimate)).block(estimate_block),
+        chunks[1],
+    );
+
+    // Category selector
+    let selected_cat = &app.data.categories[input.selected_category];
+    let category_line = Line::from(vec![
+        Span::raw("Category: "),
+        Span::styled("< ", Style::default().fg(app.theme.muted)),
+        Span::styled(
+            "■ ",
+            Style::default().fg(app.theme.category(selected_cat.color)),
+        ),
+        Span::styled(&selected_cat.name, field_style(TaskField::Category).bold()),
+        Span::styled(" >", Style::default().fg(app.theme.muted)),
+        Span::raw("  (←/→ to change)"),
+    ]);
+    frame.render_widget(Paragraph::new(category_line).centered(), chunks[2]);
+
+    // Controls
+    let controls = Line::from(vec![
+        Span::styled("[Enter]", Style::default().bold()),
+        Span::raw(" Add   "),
+        Span::styled("[Tab]", Style::default().bold()),
+        Span::raw(" Next Field   "),
+        Span::styled("[Esc]", Style::default().bold()),
+        Span::raw(" Cancel"),
+    ]);
+    frame.render_widget(
+        Paragraph::new(controls).centered().fg(app.theme.muted),
+        chunks[3],
+    );
+}
@@ -6,105 +6,268 @@ use ratatui::{
     widgets::{Block, Borders, Gauge, Paragraph},
 };
 
-use crate::app::App;
-use crate::timer::TimerPhase;
+use crate::app::{App, View};
+use crate::keymap::Action;
+use crate::mouse;
 use crate::ui;
 
+use super::big_digits;
+
 /// Render the timer view
 pub fn render_timer(frame: &mut Frame, area: Rect, app: &App) {
+    // The big clock takes whatever height the other rows leave over
+    let (display, info) = if app.data.config.big_clock {
+        (Constraint::Min(5), Constraint::Length(2))
+    } else {
+        (Constraint::Length(5), Constraint::Min(1))
+    };
     let chunks = Layout::vertical([
         Constraint::Length(3), // Title
-        Constraint::Length(5), // Timer display
+        display,               // Timer display
+        Constraint::Length(3), // Progress bar
         Constraint::Length(3), // Phase label
-        Constraint::Length(3), // Session info
-        Constraint::Min(1),    // Spacer
+        info,                  // Session info
         Constraint::Length(3), // Controls
         Constraint::Length(3), // Stats bar
     ])
     .split(area);
 
     // Title
-    let title = Line::from("Grindstone").bold().blue().centered();
+    let title = Line::from("Grindstone")
+        .bold()
+        .fg(app.theme.title)
+        .centered();
     frame.render_widget(
         Paragraph::new(title).block(Block::default().borders(Borders::BOTTOM)),
         chunks[0],
     );
 
-    // Timer display (big countdown)
-    let remaining = app.timer.remaining();
-    let minutes = remaining.as_secs() / 60;
-    let seconds = remaining.as_secs() % 60;
-    let time_str = format!("{:02}:{:02}", minutes, seconds);
+    // Timer display (big countdown, count-up during Flowtime work, negative in overtime)
+    let in_overtime = app.timer.in_overtime();
+    let time_str = app.timer.display_time();
 
-    let timer_color = match app.timer.phase {
-        TimerPhase::Work => Color::Red,
-        TimerPhase::ShortBreak => Color::Green,
-        TimerPhase::LongBreak => Color::Blue,
-    };
+    let timer_color = app.theme.phase(app.timer.phase);
+    let (display_color, gauge_color) = time_colors(app);
 
-    let timer_display = Paragraph::new(Line::from(vec![Span::styled(
-        time_str,
-        Style::default().fg(timer_color).bold(),
-    )]))
-    .centered()
-    .block(Block::default());
-    frame.render_widget(timer_display, chunks[1]);
+    let time_style = Style::default().fg(display_color).bold();
+    let big_lines = app
+        .data
+        .config
+        .big_clock
+        .then(|| big_digits::big_text(&time_str, chunks[1].width, chunks[1].height))
+        .flatten();
+    if let Some(big_lines) = big_lines {
+        // Centre the digits vertically; the paragraph centres them across
+        let top = (chunks[1].height as usize).saturating_sub(big_lines.len()) / 2;
+        let lines: Vec<Line> = std::iter::repeat_n(Line::default(), top)
+            .chain(big_lines.into_iter().map(Line::from))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).centered().style(time_style),
+            chunks[1],
+        );
+    } else {
+        // Too small for the block font, or switched off
+        let timer_display = Paragraph::new(Line::from(vec![Span::styled(time_str, time_style)]))
+            .centered()
+            .block(Block::default());
+        frame.render_widget(timer_display, chunks[1]);
+    }
 
     // Progress bar
     let progress = app.timer.progress();
     let gauge = Gauge::default()
         .block(Block::default())
-        .gauge_style(Style::default().fg(timer_color))
+        .gauge_style(Style::default().fg(gauge_color))
         .ratio(progress);
     frame.render_widget(gauge, chunks[2]);
 
     // Phase label
-    let phase_text = app.timer.phase.label();
-    let status = if app.timer.is_running() {
+    let phase_text = app.timer.phase_label();
+    let status = if in_overtime {
+        " (OVERTIME)"
+    } else if app.timer.is_running() {
         ""
     } else if app.timer.is_paused() {
         " (PAUSED)"
     } else {
         " (READY)"
     };
-    let phase_line = Line::from(vec![
-        Span::styled(phase_text, Style::default().fg(timer_color).bold()),
-        Span::raw(status),
-    ]);
-    frame.render_widget(Paragraph::new(phase_line).centered(), chunks[3]);
+    let phase_line = if app.timer.is_awaiting_ack() {
+        // Flash a prompt until the finished phase is acknowledged
+        let prompt = match app.keymap.hints(&[(&[Action::Confirm], "to start")]) {
+            keys if keys.is_empty() => format!(" {} ", phase_text),
+            keys => format!(" {} - press {} ", phase_text, keys),
+        };
+        let style = if app.alert_flash_on() {
+            app.theme.highlight(timer_color).bold()
+        } else {
+            Style::default().fg(timer_color).bold()
+        };
+        Line::from(Span::styled(prompt, style))
+    } else {
+        let profile = app
+            .active_profile()
+            .map(|p| format!("  [{}]", p.name))
+            .unwrap_or_default();
+        let extension = if app.timer.extension.is_zero() {
+            String::new()
+        } else {
+            format!(" +{}m", app.timer.extension.as_secs() / 60)
+        };
+        Line::from(vec![
+            Span::styled(phase_text, Style::default().fg(timer_color).bold()),
+            Span::styled(extension, Style::default().fg(app.theme.warning)),
+            Span::raw(status),
+            Span::styled(profile, Style::default().fg(app.theme.muted)),
+        ])
+    };
+    // Position in the cycle, with the upcoming step of a custom cycle
+    let cycle_line = app.timer.cycle_position().map(|(step, len)| {
+        let dots: String = (1..=len)
+            .map(|i| match i.cmp(&step) {
+                std::cmp::Ordering::Less => '●',
+                std::cmp::Ordering::Equal => '◉',
+                std::cmp::Ordering::Greater => '○',
+            })
+            .collect();
+        let next = app
+            .timer
+            .cycle
+            .get(step % len)
+            .filter(|_| app.timer.current_step().is_some())
+            .map(|s| format!("  next: {}", s.summary()))
+            .unwrap_or_default();
+        Line::from(format!("Step {} of {}  {}{}", step, len, dots, next)).fg(app.theme.muted)
+    });
+    let phase_lines: Vec<Line> = std::iter::once(phase_line).chain(cycle_line).collect();
+    frame.render_widget(Paragraph::new(phase_lines).centered(), chunks[3]);
 
     // Session info
     let session_info = if let Some(session) = app.current_session() {
-        format!("Session: \"{}\" ({})", session.name, session.category)
+        let marks: String = session
+            .interruptions
+            .iter()
+            .map(|i| i.kind.mark())
+            .collect();
+        if marks.is_empty() {
+            format!("Session: \"{}\" ({})", session.name, session.category)
+        } else {
+            format!(
+                "Session: \"{}\" ({})  Interruptions: {}",
+                session.name, session.category, marks
+            )
+        }
     } else {
-        "No session - press [n] to start a new session".to_string()
+        match app
+            .keymap
+            .hints(&[(&[Action::New], "to start a new session")])
+        {
+            keys if keys.is_empty() => "No session".to_string(),
+            keys => format!("No session - press {}", keys),
+        }
     };
+    let task_info = app.current_task().map(|task| {
+        // While a task is being worked on, the current pomodoro is the next one
+        let current = if task.is_open() {
+            task.pomodoros + 1
+        } else {
+            task.pomodoros
+        };
+        format!(
+            "Task: {} - pomodoro {} of {}",
+            task.title, current, task.estimate
+        )
+    });
+    let session_lines: Vec<Line> = std::iter::once(Line::from(session_info))
+        .chain(task_info.map(Line::from))
+        .collect();
     frame.render_widget(
-        Paragraph::new(session_info).centered().dark_gray(),
+        Paragraph::new(session_lines).centered().fg(app.theme.muted),
         chunks[4],
     );
 
     // Controls
-    let controls = if app.timer.phase.is_break() {
-        "[s] Skip Break  [p] Pause  [r] Reset  [n] New Session"
+    let start: (&[Action], &str) = (&[Action::StartTimer], "Start");
+    let resume: (&[Action], &str) = (&[Action::StartTimer], "Resume");
+    let skip_break: (&[Action], &str) = (&[Action::StartTimer], "Skip Break");
+    let pause: (&[Action], &str) = (&[Action::PauseTimer], "Pause");
+    let reset: (&[Action], &str) = (&[Action::ResetTimer], "Reset");
+    let stop: (&[Action], &str) = (&[Action::StopTimer], "Stop");
+    let finish: (&[Action], &str) = (&[Action::StopTimer], "Finish & Break");
+    let extend: (&[Action], &str) = (&[Action::ExtendPhase], "Extend");
+    let snooze: &[(&[Action], &str)] = if app.timer.ended_phase().is_some() {
+        &[(&[Action::ExtendPhase], "Snooze")]
+    } else {
+        &[]
+    };
+    let new_session: (&[Action], &str) = (&[Action::New], "New Session");
+    let interruption: (&[Action], &str) = (
+        &[Action::InternalInterruption, Action::ExternalInterruption],
+        "Interruption",
+    );
+    let controls_entries = if app.timer.is_awaiting_ack() && app.timer.phase.is_break() {
+        [
+            &[(&[Action::Confirm][..], "Start Break"), skip_break],
+            snooze,
+            &[reset],
+        ]
+        .concat()
+    } else if app.timer.is_awaiting_ack() {
+        [
+            &[(&[Action::Confirm][..], "Start Work")],
+            snooze,
+            &[reset, new_session],
+        ]
+        .concat()
+    } else if app.timer.phase.is_break() {
+        vec![skip_break, pause, extend, reset, new_session]
+    } else if (app.timer.counts_up() || in_overtime) && app.timer.is_running() {
+        vec![pause, finish, reset, interruption]
+    } else if (app.timer.counts_up() && app.timer.is_paused()) || in_overtime {
+        vec![resume, finish, reset, interruption]
     } else if app.timer.is_running() {
-        "[p] Pause  [x] Stop  [r] Reset"
+        vec![pause, stop, extend, reset, interruption]
     } else if app.timer.is_paused() {
-        "[s] Resume  [x] Stop  [r] Reset"
+        vec![resume, stop, extend, reset, interruption]
     } else {
-        "[s] Start  [n] New Session"
+        vec![
+            start,
+            new_session,
+            (&[Action::CycleProfile], "Profile"),
+            (&[Action::ToggleMode], "Mode"),
+            (&[Action::ToggleBigClock], "Big Clock"),
+        ]
     };
-    let controls_line = Line::from(controls).centered().dark_gray();
+    let controls = app.keymap.hint_controls(&controls_entries);
+    let controls_line = Line::from(app.keymap.hints(&controls_entries))
+        .centered()
+        .fg(app.theme.muted);
+    let controls_block = Block::default().borders(Borders::TOP);
+    for (area, target) in mouse::control_targets(controls_block.inner(chunks[5]), &controls) {
+        app.hits.add(area, target);
+    }
     frame.render_widget(
-        Paragraph::new(controls_line).block(Block::default().borders(Borders::TOP)),
+        Paragraph::new(controls_line).block(controls_block),
         chunks[5],
     );
 
     // Navigation bar / notification
-    ui::render_footer(
-        frame,
-        chunks[6],
-        app,
-        "[Tab] Timer  [h] History  [t] Stats  [q] Quit",
-    );
+    ui::render_footer(frame, chunks[6], app, &app.keymap.nav_hints(View::Timer));
+}
+
+/// Colours of the time and of the progress bar
+pub fn time_colors(app: &App) -> (Color, Color) {
+    let display_color = if app.timer.in_overtime() {
+        app.theme.special
+    } else {
+        app.theme.phase(app.timer.phase)
+    };
+    // Past a warning point the gauge turns yellow, if the phase's alert pattern asks for it
+    let gauge_color = if !app.timer.in_overtime() && app.gauge_warning() {
+        app.theme.warning
+    } else {
+        display_color
+    };
+    (display_color, gauge_color)
 }
@@ -1,4 +1,5 @@
-use crate::models::Category;
+use crate::models::{Category, Profile};
+use crate::webhooks::check_url;
 
 /// Validation result with error message
 pub type ValidationResult = Result<(), &'static str>;
@@ -39,9 +40,75 @@ pub fn validate_update_category_name(
     Ok(())
 }
 
+/// Validate a name for a new timer profile
+pub fn validate_new_profile_name(name: &str, existing: &[Profile]) -> ValidationResult {
+    if name.trim().is_empty() {
+        return Err("Profile name cannot be empty");
+    }
+    if existing.iter().any(|p| p.name == name.trim()) {
+        return Err("Profile already exists");
+    }
+    Ok(())
+}
+
+/// Validate the label and duration in minutes of a custom cycle step
+pub fn validate_cycle_step(label: &str, minutes: &str) -> ValidationResult {
+    if label.trim().is_empty() {
+        return Err("Step label cannot be empty");
+    }
+    match minutes.parse::<i64>() {
+        Ok(m) if m > 0 => Ok(()),
+        _ => Err("Step duration must be at least one minute"),
+    }
+}
+
+/// Validate the command and timeout in seconds of a hook
+pub fn validate_hook(command: &str, timeout_secs: &str) -> ValidationResult {
+    if command.trim().is_empty() {
+        return Err("Hook command cannot be empty");
+    }
+    match timeout_secs.parse::<i64>() {
+        Ok(s) if s > 0 => Ok(()),
+        _ => Err("Hook timeout must be at least one second"),
+    }
+}
+
+/// Validate the URL and `Name: value; ...` headers of a webhook
+pub fn validate_webhook(url: &str, headers: &str) -> ValidationResult {
+    if check_url(url).is_err() {
+        return Err("Webhook URL must look like https://host/path or http://host:port/path");
+    }
+    let valid_header = |h: &str| {
+        h.split_once(':').is_some_and(|(name, _)| {
+            !name.trim().is_empty() && !name.trim().contains(char::is_whitespace)
+        })
+    };
+    if headers
+        .split(';')
+        .filter(|h| !h.trim().is_empty())
+        .all(valid_header)
+    {
+        Ok(())
+    } else {
+        Err("Headers must be written as Name: value; Other: value")
+    }
+}
+
+/// Parse a category duration override in minutes, where blank means no override
+pub fn parse_override_minutes(minutes: &str) -> Result<Option<i64>, &'static str> {
+    if minutes.trim().is_empty() {
+        return Ok(None);
+    }
+    match minutes.trim().parse::<i64>() {
+        Ok(m) if m > 0 => Ok(Some(m * 60)),
+        _ => Err("Durations must be at least one minute, or blank for the default"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::DurationOverrides;
     use ratatui::style::Color;
 
     fn make_categories() -> Vec<Category> {
@@ -50,11 +117,13 @@ mod tests {
                 id: None,
                 name: "Work".to_string(),
                 color: Color::Red,
+                durations: DurationOverrides::default(),
             },
             Category {
                 id: None,
                 name: "Study".to_string(),
                 color: Color::Blue,
+                durations: DurationOverrides::default(),
             },
         ]
     }
@@ -130,4 +199,46 @@ mod tests {
             Err("Category already exists")
         );
     }
+
+    #[test]
+    fn test_validate_new_profile_name() {
+        let profiles = vec![crate::models::Profile::from_config(
+            "Pomodoro",
+            &crate::models::Config::default(),
+        )];
+        assert_eq!(
+            validate_new_profile_name(" ", &profiles),
+            Err("Profile name cannot be empty")
+        );
+        assert_eq!(
+            validate_new_profile_name("Pomodoro", &profiles),
+            Err("Profile already exists")
+        );
+        assert!(validate_new_profile_name("Sprint", &profiles).is_ok());
+    }
+
+    #[test]
+    fn test_validate_cycle_step() {
+        assert!(validate_cycle_step("Warm-up", "5").is_ok());
+        assert!(validate_cycle_step("  ", "5").is_err());
+        assert!(validate_cycle_step("Review", "0").is_err());
+        assert!(validate_cycle_step("Review", "").is_err());
+    }
+
+    #[test]
+    fn test_parse_override_minutes() {
+        assert_eq!(parse_override_minutes(""), Ok(None));
+        assert_eq!(parse_override_minutes("45"), Ok(Some(45 * 60)));
+        assert!(parse_override_minutes("0").is_err());
+    }
+
+    #[test]
+    fn test_validate_webhook() {
+        assert!(validate_webhook("http://localhost:9000/in", "").is_ok());
+        assert!(validate_webhook("http://localhost/in", "X-Key: a; X-Team: b;").is_ok());
+        assert!(validate_webhook("https://example.com/in", "").is_ok());
+        assert!(validate_webhook("ftp://example.com/in", "").is_err());
+        assert!(validate_webhook("http://localhost/in", "no colon").is_err());
+        assert!(validate_webhook("http://localhost/in", "Bad Name: x").is_err());
+    }
 }
@@ -0,0 +1,222 @@
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Duration;
+
+use thiserror::Error;
+use ureq::Agent;
+use ureq::http::Uri;
+
+use crate::models::{DeliveryId, Webhook};
+
+/// How long to wait for a receiver to connect and answer
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why a webhook could not be delivered
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("invalid URL: {0}")]
+    InvalidUrl(&'static str),
+
+    #[error("request failed: {0}")]
+    Request(#[from] ureq::Error),
+
+    #[error("receiver answered {0}")]
+    Status(u16),
+}
+
+/// Check that a URL is one a webhook can be posted to: `http` or `https` with a host
+pub fn check_url(url: &str) -> Result<(), WebhookError> {
+    let uri: Uri = url
+        .trim()
+        .parse()
+        .map_err(|_| WebhookError::InvalidUrl("not a URL"))?;
+    match uri.scheme_str() {
+        Some("http" | "https") => {}
+        _ => {
+            return Err(WebhookError::InvalidUrl(
+                "only http:// and https:// are supported",
+            ));
+        }
+    }
+    if uri.host().is_none_or(str::is_empty) {
+        return Err(WebhookError::InvalidUrl("missing host"));
+    }
+    // A port that isn't a number parses, but reads back as no port at all
+    let authority = uri.authority().map_or("", |a| a.as_str());
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+    let has_port = authority
+        .rsplit_once(':')
+        .is_some_and(|(_, after)| !after.ends_with(']'));
+    if has_port && uri.port_u16().is_none() {
+        return Err(WebhookError::InvalidUrl("bad port"));
+    }
+    Ok(())
+}
+
+/// POST a JSON body to a webhook, succeeding on any 2xx answer
+pub fn post(webhook: &Webhook, body: &str, timeout: Duration) -> Result<(), WebhookError> {
+    check_url(&webhook.url)?;
+    let agent: Agent = Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .http_status_as_error(false)
+        .user_agent("grindstone")
+        .build()
+        .into();
+
+    let mut request = agent
+        .post(webhook.url.trim())
+        .header("Content-Type", "application/json");
+    for (name, value) in webhook.header_pairs() {
+        request = request.header(name, value);
+    }
+    let status = request.send(body)?.status().as_u16();
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(WebhookError::Status(status))
+    }
+}
+
+/// The outcome of one delivery attempt, reported back to the event loop
+#[derive(Debug)]
+pub struct DeliveryResult {
+    pub id: DeliveryId,
+    pub result: Result<(), WebhookError>,
+}
+
+/// Posts queued deliveries in the background and collects the results
+pub struct WebhookSender {
+    sender: Sender<DeliveryResult>,
+    receiver: Receiver<DeliveryResult>,
+}
+
+impl Default for WebhookSender {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver }
+    }
+}
+
+impl WebhookSender {
+    /// Start posting a delivery on its own thread, without waiting for it to finish
+    pub fn send(&self, id: DeliveryId, webhook: &Webhook, payload: &str) {
+        let webhook = webhook.clone();
+        let payload = payload.to_string();
+        let sender = self.sender.clone();
+
+        std::thread::spawn(move || {
+            let result = post(&webhook, &payload, REQUEST_TIMEOUT);
+            let _ = sender.send(DeliveryResult { id, result });
+        });
+    }
+
+    /// Results of attempts finished since the last call
+    pub fn results(&self) -> Vec<DeliveryResult> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    use super::*;
+
+    /// Accept one request on a local port, answer with the given status and return what was sent
+    pub(crate) fn receiver(status: u16) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            // Read the head, then as much body as it announces
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(str::to_string)
+                        })
+                        .and_then(|l| l.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if request.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let reply = format!("HTTP/1.1 {} Whatever\r\nContent-Length: 0\r\n\r\n", status);
+            stream.write_all(reply.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            id: None,
+            url: url.to_string(),
+            headers: "Authorization: Bearer secret".to_string(),
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_url() {
+        assert!(check_url("http://example.com:8080/hooks/in").is_ok());
+        assert!(check_url("https://example.com/hooks/in").is_ok());
+        assert!(check_url("http://[::1]:9000/in").is_ok());
+        assert!(check_url("ftp://example.com/").is_err());
+        assert!(check_url("example.com/in").is_err());
+        assert!(check_url("http://:80/").is_err());
+        assert!(check_url("http://host:port/").is_err());
+    }
+
+    #[test]
+    fn test_post_sends_json_and_headers() {
+        let (url, handle) = receiver(204);
+        let body = r#"{"event":"work_completed"}"#;
+        post(&webhook(&url), body, Duration::from_secs(5)).unwrap();
+
+        // Header names may be sent in any case
+        let request = handle.join().unwrap();
+        let lowered = request.to_lowercase();
+        assert!(request.starts_with("POST /events HTTP/1.1\r\n"));
+        assert!(lowered.contains("content-type: application/json\r\n"));
+        assert!(lowered.contains("authorization: bearer secret\r\n"));
+        assert!(request.ends_with(body));
+    }
+
+    #[test]
+    fn test_post_reports_error_status() {
+        let (url, handle) = receiver(503);
+        let result = post(&webhook(&url), "{}", Duration::from_secs(5));
+        assert!(matches!(result, Err(WebhookError::Status(503))));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_post_fails_when_receiver_is_down() {
+        // Bind then drop a listener to find a port nothing is listening on
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url = format!("http://127.0.0.1:{}/", port);
+        let result = post(&webhook(&url), "{}", Duration::from_secs(5));
+        assert!(matches!(result, Err(WebhookError::Request(_))));
+    }
+}